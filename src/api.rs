@@ -0,0 +1,330 @@
+use std::str::FromStr;
+
+use alloy_primitives::Address;
+use serde::Serialize;
+
+use crate::model::{SavedSnapshot, TopologySnapshot};
+use crate::render::render_json;
+use crate::serve::ExporterState;
+
+/// A role an address plays in the ecosystem, as reported by `/whois`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AddressRole {
+    /// Field name, as in `inspect --format json`.
+    pub role: &'static str,
+    /// `None` for ecosystem-wide contracts such as the Bridgehub.
+    pub chain_id: Option<u64>,
+}
+
+/// A JSON response; `status` is the HTTP status line suffix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: &'static str,
+    pub body: String,
+}
+
+impl ApiResponse {
+    fn ok(body: String) -> Self {
+        Self {
+            status: "200 OK",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: impl ToString) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message.to_string() }).to_string(),
+        }
+    }
+}
+
+/// Answers a `GET` for `path` from the exporter's latest successful round:
+/// `/topology`, `/chains/{id}`, `/whois/{address}` and `/health`. Nothing is
+/// read from the RPC, so a request never waits on a scan. The query string
+/// is ignored.
+pub fn respond(state: &ExporterState, path: &str) -> ApiResponse {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let snapshot = state.snapshot.as_ref();
+    match segments[..] {
+        ["health"] => health(state),
+        ["topology"] => match snapshot {
+            Some(snapshot) => ApiResponse::ok(render_json(&snapshot.topology, &[])),
+            None => no_round_yet(),
+        },
+        ["chains", chain_id] => match (chain_id.parse(), snapshot) {
+            (Err(_), _) => {
+                ApiResponse::error("400 Bad Request", format!("invalid chain id `{chain_id}`"))
+            }
+            (Ok(_), None) => no_round_yet(),
+            (Ok(chain_id), Some(snapshot)) => chain(snapshot, chain_id),
+        },
+        ["whois", address] => match (Address::from_str(address), snapshot) {
+            (Err(_), _) => {
+                ApiResponse::error("400 Bad Request", format!("invalid address `{address}`"))
+            }
+            (Ok(_), None) => no_round_yet(),
+            (Ok(address), Some(snapshot)) => ApiResponse::ok(
+                serde_json::json!({
+                    "address": address,
+                    "roles": whois(snapshot, address),
+                })
+                .to_string(),
+            ),
+        },
+        _ => ApiResponse::error("404 Not Found", format!("no endpoint at `{path}`")),
+    }
+}
+
+fn health(state: &ExporterState) -> ApiResponse {
+    match &state.snapshot {
+        Some(snapshot) => ApiResponse::ok(
+            serde_json::json!({
+                "status": "ok",
+                "block_number": snapshot.block_number,
+                "last_success_unix": state.last_success_unix,
+            })
+            .to_string(),
+        ),
+        None => ApiResponse {
+            status: "503 Service Unavailable",
+            body: serde_json::json!({
+                "status": "unavailable",
+                "error": "no scan round has succeeded yet",
+            })
+            .to_string(),
+        },
+    }
+}
+
+/// `inspect --chain-id --format json` output for one chain of `snapshot`.
+fn chain(snapshot: &SavedSnapshot, chain_id: u64) -> ApiResponse {
+    let Some(inspection) = snapshot
+        .inspections
+        .iter()
+        .find(|inspection| inspection.chain.chain_id == chain_id)
+    else {
+        return ApiResponse::error(
+            "404 Not Found",
+            format!("chain {chain_id} is not registered on the Bridgehub"),
+        );
+    };
+    let topology = &snapshot.topology;
+    let chain_topology = TopologySnapshot {
+        bridgehub: topology.bridgehub,
+        chain_ids: vec![chain_id],
+        chain_ctms: topology
+            .chain_ctms
+            .iter()
+            .filter(|chain| chain.chain_id == chain_id)
+            .cloned()
+            .collect(),
+        ctms: topology
+            .ctms
+            .iter()
+            .filter(|ctm| inspection.chain.ctm == Some(ctm.address))
+            .cloned()
+            .collect(),
+        chain_data_availability: vec![],
+        history: None,
+        warnings: vec![],
+    };
+    ApiResponse::ok(render_json(
+        &chain_topology,
+        std::slice::from_ref(inspection),
+    ))
+}
+
+fn no_round_yet() -> ApiResponse {
+    ApiResponse::error("503 Service Unavailable", "no scan round has succeeded yet")
+}
+
+/// Every role `address` plays in `snapshot`, ecosystem-wide roles first and
+/// then by chain in snapshot order.
+pub fn whois(snapshot: &SavedSnapshot, address: Address) -> Vec<AddressRole> {
+    let mut roles = Vec::new();
+    if snapshot.topology.bridgehub == address {
+        roles.push(AddressRole {
+            role: "bridgehub",
+            chain_id: None,
+        });
+    }
+    for inspection in &snapshot.inspections {
+        let chain = &inspection.chain;
+        let candidates = [
+            ("ctm", chain.ctm),
+            ("chain_contract", chain.chain_contract),
+            ("admin", chain.admin),
+            ("admin_owner", chain.admin_owner),
+            ("admin_pending_owner", chain.admin_pending_owner),
+            ("validator_timelock", chain.validator_timelock),
+            ("validator_timelock_owner", chain.validator_timelock_owner),
+            (
+                "validator_timelock_pending_owner",
+                chain.validator_timelock_pending_owner,
+            ),
+            ("base_token", chain.base_token.address),
+            ("l1_da_validator", chain.data_availability.l1_validator),
+            ("l2_da_validator", chain.data_availability.l2_validator),
+            ("transaction_filterer", chain.transaction_filterer),
+        ];
+        let validators = chain
+            .timelock_validators
+            .iter()
+            .flatten()
+            .map(|validator| ("timelock_validator", Some(validator.address)));
+        let multisig_validators = chain
+            .multisig_validators
+            .iter()
+            .flatten()
+            .map(|validator| ("multisig_validator", Some(*validator)));
+        roles.extend(
+            candidates
+                .into_iter()
+                .chain(validators)
+                .chain(multisig_validators)
+                .filter(|(_, candidate)| *candidate == Some(address))
+                .map(|(role, _)| AddressRole {
+                    role,
+                    chain_id: Some(chain.chain_id),
+                }),
+        );
+    }
+    roles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloy_primitives::address;
+
+    use crate::model::{ChainCtm, ChainInspection, ChainSummary, TimelockValidator};
+
+    fn chain(chain_id: u64) -> ChainInspection {
+        ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id,
+                ctm: Some(address!("0x000000000000000000000000000000000000000c")),
+                validator_timelock_owner: Some(address!(
+                    "0x00000000000000000000000000000000000000a1"
+                )),
+                timelock_validators: Some(vec![TimelockValidator {
+                    address: address!("0x00000000000000000000000000000000000000b1"),
+                    roles: vec!["committer".to_string()],
+                }]),
+                admin_owner: Some(address!("0x00000000000000000000000000000000000000a1")),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        }
+    }
+
+    fn snapshot() -> SavedSnapshot {
+        SavedSnapshot {
+            format_version: 1,
+            network_chain_id: Some(1),
+            block_number: Some(100),
+            saved_at_unix: 0,
+            topology: TopologySnapshot {
+                bridgehub: address!("0x0000000000000000000000000000000000000001"),
+                chain_ids: vec![324, 325],
+                chain_ctms: vec![ChainCtm {
+                    chain_id: 324,
+                    ctm: address!("0x000000000000000000000000000000000000000c"),
+                }],
+                ctms: vec![],
+                chain_data_availability: vec![],
+                history: None,
+                warnings: vec![],
+            },
+            inspections: vec![chain(324), chain(325)],
+        }
+    }
+
+    #[test]
+    fn finds_every_role_of_an_address() {
+        let snapshot = snapshot();
+
+        let owner = address!("0x00000000000000000000000000000000000000a1");
+        let role = |role, chain_id| AddressRole { role, chain_id };
+        assert_eq!(
+            whois(&snapshot, owner),
+            vec![
+                role("admin_owner", Some(324)),
+                role("validator_timelock_owner", Some(324)),
+                role("admin_owner", Some(325)),
+                role("validator_timelock_owner", Some(325)),
+            ]
+        );
+        assert_eq!(
+            whois(
+                &snapshot,
+                address!("0x00000000000000000000000000000000000000b1")
+            ),
+            vec![
+                role("timelock_validator", Some(324)),
+                role("timelock_validator", Some(325)),
+            ]
+        );
+        assert_eq!(
+            whois(
+                &snapshot,
+                address!("0x0000000000000000000000000000000000000001")
+            ),
+            vec![role("bridgehub", None)]
+        );
+    }
+
+    #[test]
+    fn answers_from_the_latest_round() {
+        let mut state = ExporterState::default();
+        assert_eq!(respond(&state, "/health").status, "503 Service Unavailable");
+        assert_eq!(
+            respond(&state, "/topology").status,
+            "503 Service Unavailable"
+        );
+
+        state.snapshot = Some(snapshot());
+        state.last_success_unix = Some(1_700_000_000);
+        assert_eq!(
+            respond(&state, "/health?probe=1").body,
+            r#"{"block_number":100,"last_success_unix":1700000000,"status":"ok"}"#
+        );
+        let topology: serde_json::Value =
+            serde_json::from_str(&respond(&state, "/topology").body).unwrap();
+        assert_eq!(
+            topology["topology"]["chain_ids"],
+            serde_json::json!([324, 325])
+        );
+
+        let chain: serde_json::Value =
+            serde_json::from_str(&respond(&state, "/chains/324").body).unwrap();
+        assert_eq!(chain["topology"]["chain_ids"], serde_json::json!([324]));
+        assert_eq!(chain["chains"][0]["chain"]["chain_id"], 324);
+        assert_eq!(respond(&state, "/chains/9").status, "404 Not Found");
+
+        let whois = respond(&state, "/whois/0x00000000000000000000000000000000000000b1");
+        assert_eq!(
+            whois.body,
+            r#"{"address":"0x00000000000000000000000000000000000000b1","roles":[{"chain_id":324,"role":"timelock_validator"},{"chain_id":325,"role":"timelock_validator"}]}"#
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_routes_and_malformed_parameters() {
+        let state = ExporterState::default();
+        assert_eq!(respond(&state, "/chains/abc").status, "400 Bad Request");
+        assert_eq!(respond(&state, "/whois/0x12").status, "400 Bad Request");
+        assert_eq!(respond(&state, "/metrics/extra").status, "404 Not Found");
+    }
+}
@@ -0,0 +1,215 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::model::{CHAIN_FIELDS, Severity};
+
+use super::rules::{BUILTIN_RULES, BuiltinRule};
+
+/// Rules file: tunes built-in rules under `[rules.<id>]` and adds
+/// declarative `[[rule]]` checks over chain fields.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditConfig {
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleSettings>,
+    #[serde(default, rename = "rule")]
+    pub custom: Vec<CustomRule>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSettings {
+    pub enabled: Option<bool>,
+    pub severity: Option<Severity>,
+    /// `stale-pending-owner`: maximum age of a pending ownership transfer.
+    pub max_pending_days: Option<u64>,
+    /// `multisig-threshold`: minimum signing threshold as a share of validators.
+    pub min_threshold_ratio: Option<f64>,
+    /// `protocol-version-skew`: minor versions a chain may lag its CTM.
+    pub max_minor_versions_behind: Option<u64>,
+}
+
+/// A user-defined check; a finding is raised when any listed condition
+/// does not hold for `field`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+    pub id: String,
+    pub severity: Severity,
+    pub field: String,
+    pub description: Option<String>,
+    /// Chains the rule applies to; all audited chains when empty.
+    #[serde(default)]
+    pub chains: Vec<u64>,
+    pub equals: Option<String>,
+    pub one_of: Option<Vec<String>>,
+    pub not_one_of: Option<Vec<String>>,
+    /// Whether the field must be resolved (`true`) or unset (`false`).
+    pub set: Option<bool>,
+    /// Whether the address in the field must have deployed code.
+    pub contract: Option<bool>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl CustomRule {
+    fn has_condition(&self) -> bool {
+        self.equals.is_some()
+            || self.one_of.is_some()
+            || self.not_one_of.is_some()
+            || self.set.is_some()
+            || self.contract.is_some()
+            || self.min.is_some()
+            || self.max.is_some()
+    }
+}
+
+impl AuditConfig {
+    pub fn settings(&self, rule: &BuiltinRule) -> RuleSettings {
+        self.rules.get(rule.id).cloned().unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self, rule: &BuiltinRule) -> bool {
+        self.settings(rule).enabled.unwrap_or(true)
+    }
+
+    pub fn severity(&self, rule: &BuiltinRule) -> Severity {
+        self.settings(rule).severity.unwrap_or(rule.severity)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuditConfigError {
+    #[error("failed to read rules file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid rules file {path}: {source}")]
+    Toml {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error("invalid rules file {path}: {message}")]
+    Invalid { path: String, message: String },
+}
+
+pub fn load_audit_config(path: &Path) -> Result<AuditConfig, AuditConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| AuditConfigError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let config: AuditConfig =
+        toml::from_str(&contents).map_err(|source| AuditConfigError::Toml {
+            path: path.display().to_string(),
+            source,
+        })?;
+    validate(&config).map_err(|message| AuditConfigError::Invalid {
+        path: path.display().to_string(),
+        message,
+    })?;
+    Ok(config)
+}
+
+fn validate(config: &AuditConfig) -> Result<(), String> {
+    let builtin_ids: Vec<_> = BUILTIN_RULES.iter().map(|rule| rule.id).collect();
+    for (id, settings) in &config.rules {
+        if !builtin_ids.contains(&id.as_str()) {
+            return Err(format!(
+                "unknown built-in rule `{id}` (expected one of: {})",
+                builtin_ids.join(", ")
+            ));
+        }
+        if let Some(ratio) = settings.min_threshold_ratio
+            && !(ratio > 0.0 && ratio <= 1.0)
+        {
+            return Err(format!(
+                "rule `{id}` min_threshold_ratio must be in (0, 1], got {ratio}"
+            ));
+        }
+    }
+
+    for rule in &config.custom {
+        if builtin_ids.contains(&rule.id.as_str()) {
+            return Err(format!(
+                "custom rule `{}` reuses a built-in rule id",
+                rule.id
+            ));
+        }
+        if !CHAIN_FIELDS.contains(&rule.field.as_str()) {
+            return Err(format!(
+                "custom rule `{}` targets unknown field `{}` (expected one of: {})",
+                rule.id,
+                rule.field,
+                CHAIN_FIELDS.join(", ")
+            ));
+        }
+        if !rule.has_condition() {
+            return Err(format!("custom rule `{}` has no condition", rule.id));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Result<AuditConfig, String> {
+        let config: AuditConfig = toml::from_str(contents).map_err(|err| err.to_string())?;
+        validate(&config)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn parses_builtin_overrides_and_custom_rules() {
+        let config = parse(
+            r#"
+[rules.stale-pending-owner]
+severity = "critical"
+max_pending_days = 3
+
+[rules.protocol-version-skew]
+enabled = false
+
+[[rule]]
+id = "known-admin-owner"
+severity = "high"
+field = "admin_owner"
+one_of = ["0x0000000000000000000000000000000000000004"]
+"#,
+        )
+        .expect("config should parse");
+
+        let stale = &BUILTIN_RULES
+            .iter()
+            .find(|rule| rule.id == "stale-pending-owner")
+            .unwrap();
+        assert_eq!(config.severity(stale), Severity::Critical);
+        assert_eq!(config.settings(stale).max_pending_days, Some(3));
+        let skew = BUILTIN_RULES
+            .iter()
+            .find(|rule| rule.id == "protocol-version-skew")
+            .unwrap();
+        assert!(!config.is_enabled(skew));
+        assert_eq!(config.custom.len(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_rules_fields_and_empty_conditions() {
+        assert!(parse("[rules.no-such-rule]\nenabled = false\n").is_err());
+        assert!(
+            parse("[[rule]]\nid = \"x\"\nseverity = \"low\"\nfield = \"owner\"\nset = true\n")
+                .unwrap_err()
+                .contains("unknown field `owner`")
+        );
+        assert!(
+            parse("[[rule]]\nid = \"x\"\nseverity = \"low\"\nfield = \"admin\"\n")
+                .unwrap_err()
+                .contains("has no condition")
+        );
+        assert!(parse("[rules.multisig-threshold]\nmin_threshold_ratio = 1.5\n").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use alloy_primitives::Address;
+
+use crate::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::model::{AuditReport, ChainSummary};
+use crate::rpc::RpcClient;
+use crate::scanner::{
+    HistoryOptions, InspectOptions, ScanError, ScanOptions, inspect_bridgehub_chain,
+    scan_bridgehub_topology, timeline,
+};
+
+pub mod config;
+pub mod rules;
+
+use config::AuditConfig;
+use rules::BUILTIN_RULES;
+
+/// On-chain facts rules need beyond `ChainSummary`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditFacts {
+    /// Whether an address has deployed code.
+    pub code: BTreeMap<Address, bool>,
+    /// Unix timestamp at which the pending ownership transfer of a contract
+    /// started, keyed by contract address.
+    pub pending_since: BTreeMap<Address, u64>,
+    /// Reference time for age-based rules (latest block timestamp).
+    pub now_unix: Option<u64>,
+}
+
+impl AuditFacts {
+    pub fn has_code(&self, address: Address) -> Option<bool> {
+        self.code.get(&address).copied()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditOptions {
+    pub inspect: InspectOptions,
+    /// Chains to audit; every registered chain when empty.
+    pub chain_ids: Vec<u64>,
+    /// Log window searched for the start of pending ownership transfers.
+    pub history: Option<HistoryOptions>,
+}
+
+/// Scans and inspects the Bridgehub, gathers the facts the enabled rules
+/// need and evaluates them. Fact lookups that fail become warnings and the
+/// affected checks are skipped.
+pub fn audit_bridgehub(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    config: &AuditConfig,
+    options: &AuditOptions,
+) -> Result<AuditReport, ScanError> {
+    let scan_options = ScanOptions {
+        data_availability: false,
+        da_validators: options.inspect.da_validators.clone(),
+        history: None,
+    };
+    let topology = scan_bridgehub_topology(client, bridgehub, &scan_options)?;
+    let mut warnings = topology.warnings.clone();
+
+    let chain_ids = if options.chain_ids.is_empty() {
+        topology.chain_ids.clone()
+    } else {
+        options.chain_ids.clone()
+    };
+    // Multisig details are only resolved in verbose mode.
+    let inspect_options = InspectOptions {
+        verbose: true,
+        ..options.inspect.clone()
+    };
+    let mut chains = Vec::with_capacity(chain_ids.len());
+    for chain_id in &chain_ids {
+        let inspection = inspect_bridgehub_chain(client, bridgehub, *chain_id, &inspect_options)?;
+        warnings.extend(
+            inspection
+                .warnings
+                .into_iter()
+                .map(|warning| match warning.chain_id {
+                    Some(_) => warning,
+                    None => warning.chain(*chain_id),
+                }),
+        );
+        chains.push(inspection.chain);
+    }
+
+    let facts = collect_facts(client, config, &chains, options.history, &mut warnings);
+    let findings = rules::evaluate_rules(config, &topology, &chains, &facts);
+
+    Ok(AuditReport {
+        bridgehub,
+        chains_audited: chain_ids,
+        findings,
+        warnings,
+    })
+}
+
+fn collect_facts(
+    client: &dyn RpcClient,
+    config: &AuditConfig,
+    chains: &[ChainSummary],
+    history: Option<HistoryOptions>,
+    warnings: &mut Vec<Diagnostic>,
+) -> AuditFacts {
+    let enabled = |id: &str| {
+        BUILTIN_RULES
+            .iter()
+            .any(|rule| rule.id == id && config.is_enabled(rule))
+    };
+    let mut facts = AuditFacts::default();
+
+    let mut code_addresses = Vec::new();
+    for chain in chains {
+        if enabled("admin-owner-eoa") {
+            code_addresses.extend(chain.admin_owner);
+        }
+        if enabled("timelock-owner-eoa") {
+            code_addresses.extend(chain.validator_timelock_owner);
+        }
+        for rule in config.custom.iter().filter(|rule| rule.contract.is_some()) {
+            code_addresses.extend(chain.address_field(&rule.field));
+        }
+    }
+    for address in code_addresses {
+        if facts.code.contains_key(&address) {
+            continue;
+        }
+        match client.eth_get_code(&format!("{address:#x}")) {
+            Ok(code) => {
+                let has_code = !code.trim_start_matches("0x").is_empty();
+                facts.code.insert(address, has_code);
+            }
+            Err(err) => warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ResolutionFailed,
+                    format!("failed to resolve code of {address}"),
+                )
+                .method("eth_getCode")
+                .contract(address)
+                .error(err),
+            ),
+        }
+    }
+
+    let pending: Vec<(Address, Address)> = chains
+        .iter()
+        .flat_map(|chain| {
+            [
+                (chain.admin, chain.admin_pending_owner),
+                (
+                    chain.validator_timelock,
+                    chain.validator_timelock_pending_owner,
+                ),
+            ]
+        })
+        .filter_map(|(contract, pending_owner)| Some((contract?, pending_owner?)))
+        .collect();
+    if !enabled("stale-pending-owner") || pending.is_empty() {
+        return facts;
+    }
+
+    let head = match client.eth_block_number() {
+        Ok(head) => head,
+        Err(err) => {
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ResolutionFailed,
+                    "failed to resolve block number for pending transfers",
+                )
+                .method("eth_blockNumber")
+                .error(err),
+            );
+            return facts;
+        }
+    };
+    match client.eth_get_block_timestamp(head) {
+        Ok(timestamp) => facts.now_unix = Some(timestamp),
+        Err(err) => {
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ResolutionFailed,
+                    format!("failed to resolve timestamp of block {head}"),
+                )
+                .method("eth_getBlockByNumber")
+                .error(err),
+            );
+            return facts;
+        }
+    }
+    let history = history.unwrap_or(HistoryOptions {
+        from_block: 0,
+        max_block_range: crate::scanner::logs::DEFAULT_LOG_BLOCK_RANGE,
+    });
+    for (contract, pending_owner) in pending {
+        let started = timeline::find_ownership_transfer_start(
+            client,
+            contract,
+            pending_owner,
+            history.from_block,
+            head,
+            history.max_block_range,
+        )
+        .map_err(|err| err.to_string())
+        .and_then(|block| match block {
+            Some(block) => client
+                .eth_get_block_timestamp(block)
+                .map(Some)
+                .map_err(|err| err.to_string()),
+            None => Ok(None),
+        });
+        match started {
+            Ok(Some(timestamp)) => {
+                facts.pending_since.insert(contract, timestamp);
+            }
+            Ok(None) => {
+                let message = format!(
+                    "no OwnershipTransferStarted log nominating {pending_owner} on {contract} since block {}",
+                    history.from_block
+                );
+                warnings.push(
+                    Diagnostic::new(DiagnosticCode::IncompleteLogs, message).contract(contract),
+                );
+            }
+            Err(err) => warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::IncompleteLogs,
+                    format!("failed to date pending ownership transfer on {contract}"),
+                )
+                .contract(contract)
+                .error(err),
+            ),
+        }
+    }
+    facts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::RpcError;
+    use alloy_primitives::address;
+
+    struct CodeOnly;
+
+    impl RpcClient for CodeOnly {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_code(&self, address: &str) -> Result<String, RpcError> {
+            match address {
+                "0x00000000000000000000000000000000000000c0" => Ok("0x6080".to_string()),
+                "0x00000000000000000000000000000000000000e0" => Ok("0x".to_string()),
+                _ => Err(RpcError::Transport("connection reset".to_string())),
+            }
+        }
+    }
+
+    fn chain(admin_owner: Address, timelock_owner: Address) -> ChainSummary {
+        ChainSummary {
+            chain_id: 324,
+            validator_timelock_owner: Some(timelock_owner),
+            admin: Some(address!("0x0000000000000000000000000000000000000004")),
+            admin_owner: Some(admin_owner),
+            admin_pending_owner: Some(address!("0x0000000000000000000000000000000000000005")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn collects_code_facts_and_reports_failed_lookups() {
+        let mut warnings = Vec::new();
+        let facts = collect_facts(
+            &CodeOnly,
+            &AuditConfig::default(),
+            &[
+                chain(
+                    address!("0x00000000000000000000000000000000000000c0"),
+                    address!("0x00000000000000000000000000000000000000e0"),
+                ),
+                chain(
+                    address!("0x00000000000000000000000000000000000000C0"),
+                    address!("0x00000000000000000000000000000000000000f0"),
+                ),
+            ],
+            None,
+            &mut warnings,
+        );
+
+        assert_eq!(
+            facts.has_code(address!("0x00000000000000000000000000000000000000c0")),
+            Some(true)
+        );
+        assert_eq!(
+            facts.has_code(address!("0x00000000000000000000000000000000000000e0")),
+            Some(false)
+        );
+        assert_eq!(
+            facts.has_code(address!("0x00000000000000000000000000000000000000f0")),
+            None
+        );
+        assert_eq!(
+            warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "failed to resolve code of 0x00000000000000000000000000000000000000F0: rpc transport error: connection reset".to_string(),
+                "failed to resolve block number for pending transfers: rpc method not supported by client: eth_blockNumber".to_string(),
+            ]
+        );
+        assert!(
+            warnings
+                .iter()
+                .all(|warning| warning.code == DiagnosticCode::ResolutionFailed)
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use crate::model::{AuditFinding, ChainSummary, Severity, TopologySnapshot};
+
+use super::AuditFacts;
+use super::config::{AuditConfig, CustomRule};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinRule {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: &'static str,
+}
+
+pub const BUILTIN_RULES: [BuiltinRule; 6] = [
+    BuiltinRule {
+        id: "admin-owner-eoa",
+        severity: Severity::High,
+        description: "chain admin owner must be a contract",
+    },
+    BuiltinRule {
+        id: "timelock-owner-eoa",
+        severity: Severity::High,
+        description: "no EOA may own a validator timelock",
+    },
+    BuiltinRule {
+        id: "multisig-threshold",
+        severity: Severity::High,
+        description: "multisig signing threshold must be at least 2/3 of validators",
+    },
+    BuiltinRule {
+        id: "stale-pending-owner",
+        severity: Severity::Medium,
+        description: "no pending ownership transfer older than 7 days",
+    },
+    BuiltinRule {
+        id: "protocol-version-skew",
+        severity: Severity::Medium,
+        description: "chains must be within one minor version of their CTM",
+    },
+    BuiltinRule {
+        id: "unresolved-ctm",
+        severity: Severity::Medium,
+        description: "every registered chain must resolve a CTM",
+    },
+];
+
+const DEFAULT_MAX_PENDING_DAYS: u64 = 7;
+const DEFAULT_MIN_THRESHOLD_RATIO: f64 = 2.0 / 3.0;
+const DEFAULT_MAX_MINOR_VERSIONS_BEHIND: u64 = 1;
+
+/// Evaluates every enabled built-in rule and all custom rules.
+pub fn evaluate_rules(
+    config: &AuditConfig,
+    topology: &TopologySnapshot,
+    chains: &[ChainSummary],
+    facts: &AuditFacts,
+) -> Vec<AuditFinding> {
+    let mut findings = Vec::new();
+    for rule in BUILTIN_RULES.iter().filter(|rule| config.is_enabled(rule)) {
+        let mut emit = |chain_id: Option<u64>, message: String| {
+            findings.push(AuditFinding {
+                rule: rule.id.to_string(),
+                severity: config.severity(rule),
+                chain_id,
+                message,
+            });
+        };
+        match rule.id {
+            "unresolved-ctm" => {
+                for chain_id in &topology.chain_ids {
+                    if !topology
+                        .chain_ctms
+                        .iter()
+                        .any(|entry| entry.chain_id == *chain_id)
+                    {
+                        emit(
+                            Some(*chain_id),
+                            "chain is registered but its CTM could not be resolved".to_string(),
+                        );
+                    }
+                }
+            }
+            _ => {
+                for chain in chains {
+                    if let Some(message) = evaluate_chain_rule(config, rule, topology, chain, facts)
+                    {
+                        emit(Some(chain.chain_id), message);
+                    }
+                }
+            }
+        }
+    }
+
+    for rule in &config.custom {
+        for chain in chains {
+            if !rule.chains.is_empty() && !rule.chains.contains(&chain.chain_id) {
+                continue;
+            }
+            if let Some(message) = evaluate_custom_rule(rule, chain, facts) {
+                findings.push(AuditFinding {
+                    rule: rule.id.clone(),
+                    severity: rule.severity,
+                    chain_id: Some(chain.chain_id),
+                    message,
+                });
+            }
+        }
+    }
+
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(a.chain_id.cmp(&b.chain_id))
+            .then(a.rule.cmp(&b.rule))
+    });
+    findings
+}
+
+fn evaluate_chain_rule(
+    config: &AuditConfig,
+    rule: &BuiltinRule,
+    topology: &TopologySnapshot,
+    chain: &ChainSummary,
+    facts: &AuditFacts,
+) -> Option<String> {
+    let settings = config.settings(rule);
+    match rule.id {
+        "admin-owner-eoa" => {
+            let owner = chain.admin_owner?;
+            (facts.has_code(owner) == Some(false))
+                .then(|| format!("chain admin owner {owner} is an EOA"))
+        }
+        "timelock-owner-eoa" => {
+            let owner = chain.validator_timelock_owner?;
+            (facts.has_code(owner) == Some(false))
+                .then(|| format!("validator timelock owner {owner} is an EOA"))
+        }
+        "multisig-threshold" => {
+            let threshold = chain.multisig_signing_threshold?;
+            let validators = chain.multisig_validators.as_ref()?.len();
+            let ratio = settings
+                .min_threshold_ratio
+                .unwrap_or(DEFAULT_MIN_THRESHOLD_RATIO);
+            let required = (ratio * validators as f64 - 1e-9).ceil() as u64;
+            (validators > 0 && threshold < required).then(|| {
+                format!(
+                    "multisig signing threshold {threshold} of {validators} validators is below the required {required}"
+                )
+            })
+        }
+        "stale-pending-owner" => {
+            let max_days = settings
+                .max_pending_days
+                .unwrap_or(DEFAULT_MAX_PENDING_DAYS);
+            let pending = [
+                ("chain admin", chain.admin, chain.admin_pending_owner),
+                (
+                    "validator timelock",
+                    chain.validator_timelock,
+                    chain.validator_timelock_pending_owner,
+                ),
+            ];
+            let messages: Vec<String> = pending
+                .into_iter()
+                .filter_map(|(label, contract, pending_owner)| {
+                    let (contract, pending_owner) = (contract?, pending_owner?);
+                    match (facts.pending_since.get(&contract), facts.now_unix) {
+                        (Some(since), Some(now)) => {
+                            let days = now.saturating_sub(*since) / SECONDS_PER_DAY;
+                            (days > max_days).then(|| {
+                                format!(
+                                    "{label} ownership transfer to {pending_owner} pending for {days} days"
+                                )
+                            })
+                        }
+                        _ => Some(format!(
+                            "{label} ownership transfer to {pending_owner} pending for an unknown time"
+                        )),
+                    }
+                })
+                .collect();
+            (!messages.is_empty()).then(|| messages.join("; "))
+        }
+        "protocol-version-skew" => {
+            let chain_version = chain.protocol_version?;
+            let ctm = chain.ctm?;
+            let ctm_version = topology
+                .ctms
+                .iter()
+                .find(|summary| summary.address == ctm)
+                .and_then(|summary| summary.protocol_version)?;
+            let max_behind = settings
+                .max_minor_versions_behind
+                .unwrap_or(DEFAULT_MAX_MINOR_VERSIONS_BEHIND);
+            let skewed = chain_version.major != ctm_version.major
+                || u64::from(ctm_version.minor.saturating_sub(chain_version.minor)) > max_behind;
+            skewed.then(|| {
+                format!(
+                    "chain protocol {chain_version} is more than {max_behind} minor version(s) behind CTM protocol {ctm_version}"
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+fn evaluate_custom_rule(
+    rule: &CustomRule,
+    chain: &ChainSummary,
+    facts: &AuditFacts,
+) -> Option<String> {
+    let value = chain.field(&rule.field);
+    let describe = |problem: String| match &rule.description {
+        Some(description) => format!("{description}: {problem}"),
+        None => problem,
+    };
+    let shown = value.as_deref().unwrap_or("unset");
+
+    if let Some(set) = rule.set
+        && value.is_some() != set
+    {
+        let expected = if set { "set" } else { "unset" };
+        return Some(describe(format!(
+            "{} is {shown}, expected {expected}",
+            rule.field
+        )));
+    }
+    let Some(value) = value.as_deref() else {
+        let has_value_condition = rule.equals.is_some()
+            || rule.one_of.is_some()
+            || rule.contract.is_some()
+            || rule.min.is_some()
+            || rule.max.is_some();
+        return has_value_condition
+            .then(|| describe(format!("{} could not be resolved", rule.field)));
+    };
+
+    if let Some(expected) = &rule.equals
+        && !value.eq_ignore_ascii_case(expected)
+    {
+        return Some(describe(format!(
+            "{} is {value}, expected {expected}",
+            rule.field
+        )));
+    }
+    if let Some(allowed) = &rule.one_of
+        && !allowed
+            .iter()
+            .any(|candidate| value.eq_ignore_ascii_case(candidate))
+    {
+        return Some(describe(format!(
+            "{} {value} is not in the allowed set",
+            rule.field
+        )));
+    }
+    if let Some(denied) = &rule.not_one_of
+        && denied
+            .iter()
+            .any(|candidate| value.eq_ignore_ascii_case(candidate))
+    {
+        return Some(describe(format!(
+            "{} {value} is in the denied set",
+            rule.field
+        )));
+    }
+    if let Some(contract) = rule.contract
+        && let Some(has_code) = chain
+            .address_field(&rule.field)
+            .and_then(|address| facts.has_code(address))
+        && has_code != contract
+    {
+        let kind = if has_code { "a contract" } else { "an EOA" };
+        return Some(describe(format!("{} {value} is {kind}", rule.field)));
+    }
+    if rule.min.is_some() || rule.max.is_some() {
+        let Ok(number) = value.parse::<u64>() else {
+            return Some(describe(format!("{} {value} is not numeric", rule.field)));
+        };
+        if let Some(min) = rule.min
+            && number < min
+        {
+            return Some(describe(format!("{} {number} is below {min}", rule.field)));
+        }
+        if let Some(max) = rule.max
+            && number > max
+        {
+            return Some(describe(format!("{} {number} is above {max}", rule.field)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, address};
+
+    use crate::model::{ChainCtm, CtmSummary, ProtocolSemver};
+
+    const CTM: Address = address!("0x000000000000000000000000000000000000000a");
+    const EOA: Address = address!("0x00000000000000000000000000000000000000e0");
+    const SAFE: Address = address!("0x00000000000000000000000000000000000000c0");
+
+    fn chain(chain_id: u64) -> ChainSummary {
+        ChainSummary {
+            chain_id,
+            ctm: Some(CTM),
+            validator_timelock: Some(address!("0x0000000000000000000000000000000000000007")),
+            validator_timelock_owner: Some(SAFE),
+            validator_timelock_execution_delay: Some(0),
+            admin: Some(address!("0x0000000000000000000000000000000000000004")),
+            admin_owner: Some(SAFE),
+            protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+            priority_tx_max_gas_limit: Some(4_700_000),
+            ..Default::default()
+        }
+    }
+
+    fn topology(chain_ids: &[u64]) -> TopologySnapshot {
+        TopologySnapshot {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_ids: chain_ids.to_vec(),
+            chain_ctms: vec![ChainCtm {
+                chain_id: 324,
+                ctm: CTM,
+            }],
+            ctms: vec![CtmSummary {
+                address: CTM,
+                protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+            }],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        }
+    }
+
+    fn facts() -> AuditFacts {
+        AuditFacts {
+            code: [(EOA, false), (SAFE, true)].into(),
+            pending_since: [(
+                address!("0x0000000000000000000000000000000000000004"),
+                1_000_000,
+            )]
+            .into(),
+            now_unix: Some(1_000_000 + 10 * SECONDS_PER_DAY),
+        }
+    }
+
+    #[test]
+    fn builtin_rules_flag_insecure_chain() {
+        let mut insecure = chain(324);
+        insecure.admin_owner = Some(EOA);
+        insecure.admin_pending_owner = Some(SAFE);
+        insecure.multisig_signing_threshold = Some(1);
+        insecure.multisig_validators = Some(vec![
+            address!("0x0000000000000000000000000000000000000011"),
+            address!("0x0000000000000000000000000000000000000012"),
+            address!("0x0000000000000000000000000000000000000013"),
+        ]);
+        insecure.protocol_version = Some(ProtocolSemver::new(0, 27, 0));
+
+        let findings = evaluate_rules(
+            &AuditConfig::default(),
+            &topology(&[324, 325]),
+            &[insecure],
+            &facts(),
+        );
+
+        assert_eq!(
+            findings
+                .iter()
+                .map(|finding| (finding.severity, finding.chain_id, finding.rule.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Severity::High, Some(324), "admin-owner-eoa"),
+                (Severity::High, Some(324), "multisig-threshold"),
+                (Severity::Medium, Some(324), "protocol-version-skew"),
+                (Severity::Medium, Some(324), "stale-pending-owner"),
+                (Severity::Medium, Some(325), "unresolved-ctm"),
+            ]
+        );
+        assert!(
+            findings[1]
+                .message
+                .contains("threshold 1 of 3 validators is below the required 2")
+        );
+        assert!(findings[3].message.contains("pending for 10 days"));
+    }
+
+    #[test]
+    fn secure_chain_has_no_findings() {
+        let mut secure = chain(324);
+        secure.multisig_signing_threshold = Some(2);
+        secure.multisig_validators = Some(vec![
+            address!("0x0000000000000000000000000000000000000011"),
+            address!("0x0000000000000000000000000000000000000012"),
+            address!("0x0000000000000000000000000000000000000013"),
+        ]);
+        secure.protocol_version = Some(ProtocolSemver::new(0, 28, 1));
+
+        let findings = evaluate_rules(
+            &AuditConfig::default(),
+            &topology(&[324]),
+            &[secure],
+            &facts(),
+        );
+        assert!(findings.is_empty(), "unexpected findings: {findings:?}");
+    }
+
+    #[test]
+    fn config_overrides_builtins_and_adds_custom_rules() {
+        let config: AuditConfig = toml::from_str(
+            r#"
+[rules.unresolved-ctm]
+enabled = false
+
+[rules.admin-owner-eoa]
+severity = "critical"
+
+[[rule]]
+id = "admin-owner-allowlist"
+severity = "high"
+field = "admin_owner"
+one_of = ["0x00000000000000000000000000000000000000C0"]
+description = "admin owner must be the security council"
+
+[[rule]]
+id = "priority-gas-cap"
+severity = "low"
+field = "priority_tx_max_gas_limit"
+max = 1000000
+chains = [325]
+"#,
+        )
+        .expect("config should parse");
+        let mut insecure = chain(324);
+        insecure.admin_owner = Some(EOA);
+
+        let findings = evaluate_rules(
+            &config,
+            &topology(&[324, 325]),
+            &[insecure, chain(325)],
+            &facts(),
+        );
+
+        assert_eq!(
+            findings
+                .iter()
+                .map(|finding| (finding.severity, finding.chain_id, finding.rule.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (Severity::Critical, Some(324), "admin-owner-eoa"),
+                (Severity::High, Some(324), "admin-owner-allowlist"),
+                (Severity::Low, Some(325), "priority-gas-cap"),
+            ]
+        );
+        assert_eq!(
+            findings[1].message,
+            format!(
+                "admin owner must be the security council: admin_owner {EOA} is not in the allowed set"
+            )
+        );
+        assert_eq!(
+            findings[2].message,
+            "priority_tx_max_gas_limit 4700000 is above 1000000"
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use alloy_primitives::keccak256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::rpc::{LogFilter, RpcClient, RpcError, RpcLog};
+
+#[derive(Debug, Clone, Default)]
+pub struct CacheOptions {
+    /// Directory persisting results across runs; memory only when unset.
+    pub dir: Option<PathBuf>,
+    /// How long a read against the latest block is reused; zero disables it.
+    /// Reads pinned to a block hash never expire.
+    pub latest_ttl: Duration,
+    /// Pins every read to the head block at the first request, so a run sees
+    /// one block and its persisted reads can be reused by later runs.
+    pub pin_head: bool,
+}
+
+/// Distinct blocks whose pinned reads are kept in memory; older blocks are
+/// evicted first and stay available on disk.
+const MEMORY_PINNED_BLOCKS: usize = 64;
+
+/// Block hashes and timestamps kept in memory, newest blocks first.
+const MEMORY_BLOCK_METADATA: usize = 4096;
+
+/// Block a cached read was made against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum BlockKey {
+    Latest,
+    /// In-memory only; a reorg within one run is not guarded against.
+    Number(u64),
+    Hash(u64, String),
+}
+
+impl BlockKey {
+    fn number(&self) -> Option<u64> {
+        match self {
+            Self::Latest => None,
+            Self::Number(number) | Self::Hash(number, _) => Some(*number),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    method: &'static str,
+    block: BlockKey,
+    target: String,
+    data: String,
+}
+
+impl CacheKey {
+    fn new(method: &'static str, block: BlockKey, target: &str, data: &str) -> Self {
+        Self {
+            method,
+            block,
+            target: target.to_ascii_lowercase(),
+            data: data.to_ascii_lowercase(),
+        }
+    }
+
+    /// File name of the entry under the chain's cache directory.
+    fn file_name(&self) -> String {
+        let block = match &self.block {
+            BlockKey::Latest => "latest".to_string(),
+            BlockKey::Number(number) => number.to_string(),
+            BlockKey::Hash(_, hash) => hash.clone(),
+        };
+        let key = format!("{}\n{block}\n{}\n{}", self.method, self.target, self.data);
+        format!("{:x}.json", keccak256(key.as_bytes()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    fetched_at_unix: u64,
+    result: String,
+}
+
+/// In-memory entries, bounded so a long-running `serve` does not grow them
+/// without limit.
+#[derive(Debug, Default)]
+struct Entries {
+    map: HashMap<CacheKey, CacheEntry>,
+    /// Blocks with pinned entries in `map`.
+    pinned_blocks: BTreeSet<u64>,
+    /// When expired latest-block entries are next dropped.
+    next_prune_unix: u64,
+}
+
+impl Entries {
+    fn insert(&mut self, key: CacheKey, entry: CacheEntry, latest_ttl_secs: u64) {
+        let now = entry.fetched_at_unix;
+        match key.block.number() {
+            Some(block) => {
+                if self.pinned_blocks.insert(block)
+                    && self.pinned_blocks.len() > MEMORY_PINNED_BLOCKS
+                    && let Some(oldest) = self.pinned_blocks.pop_first()
+                {
+                    self.map.retain(|key, _| key.block.number() != Some(oldest));
+                }
+            }
+            None if now >= self.next_prune_unix => {
+                self.map.retain(|key, entry| {
+                    key.block != BlockKey::Latest
+                        || now.saturating_sub(entry.fetched_at_unix) < latest_ttl_secs
+                });
+                self.next_prune_unix = now.saturating_add(latest_ttl_secs);
+            }
+            None => {}
+        }
+        self.map.insert(key, entry);
+    }
+}
+
+/// Serves repeated `eth_call`, `eth_getStorageAt` and `eth_getCode` reads from
+/// memory and, with `CacheOptions::dir`, from disk. Entries are keyed by chain
+/// ID, block hash, target and calldata; `eth_chainId` and the pinned head are
+/// memoized for the lifetime of the client, block hashes and timestamps for
+/// recent blocks. Errors are never cached and disk failures fall back to the
+/// RPC.
+pub struct CachedRpcClient<C> {
+    client: C,
+    options: CacheOptions,
+    chain_id: OnceLock<u64>,
+    head: OnceLock<u64>,
+    entries: Mutex<Entries>,
+    block_hashes: Mutex<BTreeMap<u64, String>>,
+    block_timestamps: Mutex<BTreeMap<u64, u64>>,
+}
+
+impl<C: RpcClient> CachedRpcClient<C> {
+    pub fn new(client: C, options: CacheOptions) -> Self {
+        Self {
+            client,
+            options,
+            chain_id: OnceLock::new(),
+            head: OnceLock::new(),
+            entries: Mutex::new(Entries::default()),
+            block_hashes: Mutex::new(BTreeMap::new()),
+            block_timestamps: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn cached(
+        &self,
+        key: CacheKey,
+        fetch: impl FnOnce(&C) -> Result<String, RpcError>,
+    ) -> Result<String, RpcError> {
+        let now = unix_now();
+        let fresh = |entry: &CacheEntry| match key.block {
+            BlockKey::Latest => {
+                now.saturating_sub(entry.fetched_at_unix) < self.options.latest_ttl.as_secs()
+            }
+            BlockKey::Number(_) | BlockKey::Hash(..) => true,
+        };
+        if key.block == BlockKey::Latest && self.options.latest_ttl.is_zero() {
+            return fetch(&self.client);
+        }
+
+        if let Some(entry) = lock(&self.entries)
+            .map
+            .get(&key)
+            .filter(|entry| fresh(entry))
+        {
+            return Ok(entry.result.clone());
+        }
+        let path = self.entry_path(&key);
+        if let Some(entry) = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str::<CacheEntry>(&json).ok())
+            .filter(|entry| fresh(entry))
+        {
+            let result = entry.result.clone();
+            self.remember(key, entry);
+            return Ok(result);
+        }
+
+        let entry = CacheEntry {
+            fetched_at_unix: now,
+            result: fetch(&self.client)?,
+        };
+        if let Some(path) = path {
+            write_entry(&path, &entry);
+        }
+        let result = entry.result.clone();
+        self.remember(key, entry);
+        Ok(result)
+    }
+
+    /// The block reads are pinned to with `CacheOptions::pin_head`, read once.
+    fn head(&self) -> Result<u64, RpcError> {
+        if let Some(head) = self.head.get() {
+            return Ok(*head);
+        }
+        let head = self.client.eth_block_number()?;
+        Ok(*self.head.get_or_init(|| head))
+    }
+
+    /// The pinned head, when reads are pinned and it can be read; otherwise
+    /// reads go to the latest block.
+    fn pinned_block(&self) -> Option<u64> {
+        self.options.pin_head.then(|| self.head().ok()).flatten()
+    }
+
+    /// Key of a read at `block`. Persisted entries outlive reorgs, so they are
+    /// keyed by block hash; without one the read is not cached.
+    fn block_key(&self, block: u64) -> Option<BlockKey> {
+        match &self.options.dir {
+            None => Some(BlockKey::Number(block)),
+            Some(_) => self
+                .eth_get_block_hash(block)
+                .ok()
+                .map(|hash| BlockKey::Hash(block, hash)),
+        }
+    }
+
+    fn remember(&self, key: CacheKey, entry: CacheEntry) {
+        lock(&self.entries).insert(key, entry, self.options.latest_ttl.as_secs());
+    }
+
+    /// On-disk location of `key`, when a cache directory is configured and
+    /// the chain ID is known.
+    fn entry_path(&self, key: &CacheKey) -> Option<PathBuf> {
+        let dir = self.options.dir.as_ref()?;
+        let chain_id = self.eth_chain_id().ok()?;
+        Some(dir.join(chain_id.to_string()).join(key.file_name()))
+    }
+}
+
+/// Best effort: a failed write only costs a refetch on the next run. The entry
+/// is renamed into place so concurrent runs never read a partial file.
+fn write_entry(path: &Path, entry: &CacheEntry) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    let Ok(json) = serde_json::to_string(entry) else {
+        return;
+    };
+    let temporary = path.with_extension(format!("tmp{}", std::process::id()));
+    if fs::create_dir_all(dir).is_ok() && fs::write(&temporary, json).is_ok() {
+        let _ = fs::rename(&temporary, path);
+    }
+}
+
+/// Records per-block metadata, dropping the oldest blocks past
+/// `MEMORY_BLOCK_METADATA`.
+fn remember_block<V>(map: &Mutex<BTreeMap<u64, V>>, block: u64, value: V) {
+    let mut map = lock(map);
+    map.insert(block, value);
+    while map.len() > MEMORY_BLOCK_METADATA {
+        map.pop_first();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+impl<C: RpcClient> RpcClient for CachedRpcClient<C> {
+    fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+        if let Some(block) = self.pinned_block() {
+            return self.eth_call_at_block(to, data, block);
+        }
+        self.cached(
+            CacheKey::new("eth_call", BlockKey::Latest, to, data),
+            |client| client.eth_call(to, data),
+        )
+    }
+
+    fn eth_call_at_block(&self, to: &str, data: &str, block: u64) -> Result<String, RpcError> {
+        let Some(block_key) = self.block_key(block) else {
+            return self.client.eth_call_at_block(to, data, block);
+        };
+        self.cached(CacheKey::new("eth_call", block_key, to, data), |client| {
+            client.eth_call_at_block(to, data, block)
+        })
+    }
+
+    fn eth_chain_id(&self) -> Result<u64, RpcError> {
+        if let Some(chain_id) = self.chain_id.get() {
+            return Ok(*chain_id);
+        }
+        let chain_id = self.client.eth_chain_id()?;
+        Ok(*self.chain_id.get_or_init(|| chain_id))
+    }
+
+    fn eth_get_storage_at(&self, address: &str, slot: &str) -> Result<String, RpcError> {
+        if let Some(block) = self.pinned_block() {
+            return self.eth_get_storage_at_block(address, slot, block);
+        }
+        self.cached(
+            CacheKey::new("eth_getStorageAt", BlockKey::Latest, address, slot),
+            |client| client.eth_get_storage_at(address, slot),
+        )
+    }
+
+    fn eth_get_storage_at_block(
+        &self,
+        address: &str,
+        slot: &str,
+        block: u64,
+    ) -> Result<String, RpcError> {
+        let Some(block_key) = self.block_key(block) else {
+            return self.client.eth_get_storage_at_block(address, slot, block);
+        };
+        self.cached(
+            CacheKey::new("eth_getStorageAt", block_key, address, slot),
+            |client| client.eth_get_storage_at_block(address, slot, block),
+        )
+    }
+
+    fn eth_block_number(&self) -> Result<u64, RpcError> {
+        if self.options.pin_head {
+            return self.head();
+        }
+        self.client.eth_block_number()
+    }
+
+    fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+        self.client.eth_get_logs(filter)
+    }
+
+    fn eth_get_code(&self, address: &str) -> Result<String, RpcError> {
+        if let Some(block) = self.pinned_block() {
+            return self.eth_get_code_at_block(address, block);
+        }
+        self.cached(
+            CacheKey::new("eth_getCode", BlockKey::Latest, address, ""),
+            |client| client.eth_get_code(address),
+        )
+    }
+
+    fn eth_get_code_at_block(&self, address: &str, block: u64) -> Result<String, RpcError> {
+        let Some(block_key) = self.block_key(block) else {
+            return self.client.eth_get_code_at_block(address, block);
+        };
+        self.cached(
+            CacheKey::new("eth_getCode", block_key, address, ""),
+            |client| client.eth_get_code_at_block(address, block),
+        )
+    }
+
+    fn eth_get_block_timestamp(&self, block: u64) -> Result<u64, RpcError> {
+        if let Some(timestamp) = lock(&self.block_timestamps).get(&block) {
+            return Ok(*timestamp);
+        }
+        let timestamp = self.client.eth_get_block_timestamp(block)?;
+        remember_block(&self.block_timestamps, block, timestamp);
+        Ok(timestamp)
+    }
+
+    fn eth_get_block_hash(&self, block: u64) -> Result<String, RpcError> {
+        if let Some(hash) = lock(&self.block_hashes).get(&block) {
+            return Ok(hash.clone());
+        }
+        let hash = self.client.eth_get_block_hash(block)?;
+        remember_block(&self.block_hashes, block, hash.clone());
+        Ok(hash)
+    }
+
+    fn raw_request(&self, method: &'static str, params: Value) -> Result<Value, RpcError> {
+        self.client.raw_request(method, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct Counting {
+        calls: AtomicU64,
+        fail: bool,
+    }
+
+    impl RpcClient for &Counting {
+        fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(RpcError::Transport("connection refused".to_string()));
+            }
+            Ok(format!("{to}:{data}:{call}"))
+        }
+
+        fn eth_call_at_block(&self, to: &str, data: &str, block: u64) -> Result<String, RpcError> {
+            Ok(format!("{}@{block}", self.eth_call(to, data)?))
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(324)
+        }
+
+        fn eth_block_number(&self) -> Result<u64, RpcError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(7)
+        }
+
+        fn eth_get_block_hash(&self, block: u64) -> Result<String, RpcError> {
+            Ok(format!("0x{block:064x}"))
+        }
+    }
+
+    fn options(dir: Option<PathBuf>, latest_ttl: Duration) -> CacheOptions {
+        CacheOptions {
+            dir,
+            latest_ttl,
+            pin_head: false,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mercator-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn memoizes_latest_reads_within_the_ttl() {
+        let rpc = Counting::default();
+        let client = CachedRpcClient::new(&rpc, options(None, Duration::from_secs(60)));
+
+        let first = client.eth_call("0xAB", "0x8da5cb5b").unwrap();
+        assert_eq!(client.eth_call("0xab", "0x8DA5CB5B").unwrap(), first);
+        assert_ne!(client.eth_call("0xab", "0xe30c3978").unwrap(), first);
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 2);
+
+        client.eth_chain_id().unwrap();
+        client.eth_chain_id().unwrap();
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn zero_ttl_disables_latest_caching_but_not_pinned_reads() {
+        let rpc = Counting::default();
+        let client = CachedRpcClient::new(&rpc, options(None, Duration::ZERO));
+
+        client.eth_call("0xab", "0x01").unwrap();
+        client.eth_call("0xab", "0x01").unwrap();
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 2);
+
+        let pinned = client.eth_call_at_block("0xab", "0x01", 7).unwrap();
+        assert_eq!(client.eth_call_at_block("0xab", "0x01", 7).unwrap(), pinned);
+        assert_ne!(client.eth_call_at_block("0xab", "0x01", 8).unwrap(), pinned);
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let rpc = Counting {
+            fail: true,
+            ..Counting::default()
+        };
+        let client = CachedRpcClient::new(&rpc, options(None, Duration::from_secs(60)));
+
+        assert!(client.eth_call("0xab", "0x01").is_err());
+        assert!(client.eth_call("0xab", "0x01").is_err());
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn persists_pinned_reads_across_clients() {
+        let dir = temp_dir("pinned");
+        let rpc = Counting::default();
+
+        let first = CachedRpcClient::new(&rpc, options(Some(dir.clone()), Duration::ZERO));
+        let result = first.eth_call_at_block("0xab", "0x01", 7).unwrap();
+        let calls = rpc.calls.load(Ordering::SeqCst);
+
+        let second = CachedRpcClient::new(&rpc, options(Some(dir.clone()), Duration::ZERO));
+        assert_eq!(second.eth_call_at_block("0xab", "0x01", 7).unwrap(), result);
+        // Only `eth_chainId` is fetched again to locate the chain's directory.
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), calls + 1);
+        assert_eq!(fs::read_dir(dir.join("324")).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn expires_persisted_latest_reads() {
+        let dir = temp_dir("latest");
+        let rpc = Counting::default();
+        let key = CacheKey::new("eth_call", BlockKey::Latest, "0xab", "0x01");
+        write_entry(
+            &dir.join("324").join(key.file_name()),
+            &CacheEntry {
+                fetched_at_unix: unix_now() - 120,
+                result: "stale".to_string(),
+            },
+        );
+
+        let client =
+            CachedRpcClient::new(&rpc, options(Some(dir.clone()), Duration::from_secs(600)));
+        assert_eq!(client.eth_call("0xab", "0x01").unwrap(), "stale");
+
+        let client =
+            CachedRpcClient::new(&rpc, options(Some(dir.clone()), Duration::from_secs(60)));
+        assert_ne!(client.eth_call("0xab", "0x01").unwrap(), "stale");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn drops_expired_latest_reads_from_memory() {
+        let rpc = Counting::default();
+        let client = CachedRpcClient::new(&rpc, options(None, Duration::from_secs(60)));
+        let stale = CacheKey::new("eth_call", BlockKey::Latest, "0xab", "0x01");
+        lock(&client.entries).map.insert(
+            stale.clone(),
+            CacheEntry {
+                fetched_at_unix: unix_now() - 120,
+                result: "stale".to_string(),
+            },
+        );
+
+        client.eth_call("0xab", "0x02").unwrap();
+        assert!(!lock(&client.entries).map.contains_key(&stale));
+    }
+
+    #[test]
+    fn keeps_pinned_reads_for_recent_blocks_only() {
+        let rpc = Counting::default();
+        let client = CachedRpcClient::new(&rpc, options(None, Duration::ZERO));
+
+        let oldest = client.eth_call_at_block("0xab", "0x01", 0).unwrap();
+        for block in 1..=MEMORY_PINNED_BLOCKS as u64 {
+            client.eth_call_at_block("0xab", "0x01", block).unwrap();
+        }
+        assert_eq!(lock(&client.entries).map.len(), MEMORY_PINNED_BLOCKS);
+        assert_ne!(client.eth_call_at_block("0xab", "0x01", 0).unwrap(), oldest);
+    }
+
+    #[test]
+    fn pins_reads_to_the_head_block_across_runs() {
+        let dir = temp_dir("head");
+        let rpc = Counting::default();
+        let pinned = CacheOptions {
+            pin_head: true,
+            ..options(Some(dir.clone()), Duration::ZERO)
+        };
+
+        let first = CachedRpcClient::new(&rpc, pinned.clone());
+        let result = first.eth_call("0xab", "0x01").unwrap();
+        assert!(result.ends_with("@7"));
+        assert_eq!(first.eth_call("0xab", "0x01").unwrap(), result);
+        assert_eq!(first.eth_block_number().unwrap(), 7);
+        // `eth_blockNumber`, `eth_chainId` and one `eth_call`.
+        let calls = rpc.calls.load(Ordering::SeqCst);
+        assert_eq!(calls, 3);
+
+        let second = CachedRpcClient::new(&rpc, pinned);
+        assert_eq!(second.eth_call("0xab", "0x01").unwrap(), result);
+        // Only the head and `eth_chainId` are fetched again.
+        assert_eq!(rpc.calls.load(Ordering::SeqCst), calls + 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,8 +1,22 @@
-use clap::{Args, Parser, Subcommand};
-use std::str::FromStr;
+use clap::{Args, Parser, Subcommand, builder::PossibleValuesParser};
+use std::{net::SocketAddr, path::PathBuf, str::FromStr};
 
 use alloy_primitives::Address;
 
+use crate::config::DEFAULT_CONFIG_PATH;
+use crate::diagnostic::DiagnosticCode;
+use crate::model::{CHAIN_FIELDS, Severity};
+use crate::render::{ChainColumn, OutputFormat};
+use crate::scanner::{
+    bisect::BisectField,
+    bridgehub::L2_BRIDGEHUB_ADDRESS,
+    data_availability::{DaLayer, KnownDaValidator},
+    logs::DEFAULT_LOG_BLOCK_RANGE,
+};
+
+/// `--latest-ttl-secs` of one-shot commands.
+pub const DEFAULT_LATEST_TTL_SECS: u64 = 30;
+
 #[derive(Debug, Parser)]
 #[command(
     name = "mercator",
@@ -21,28 +35,106 @@ pub enum Command {
     Scan(ScanArgs),
     /// Inspect a single chain deeply using bridgehub + chain ID.
     Inspect(InspectArgs),
+    /// Reconstruct a chain's protocol upgrade history from diamond and CTM logs.
+    Upgrades(UpgradesArgs),
+    /// Binary-search archive state for the block where a chain field changed.
+    Bisect(BisectArgs),
+    /// Compare two `scan --save` snapshots; exits with 2 on security-relevant changes.
+    Diff(DiffArgs),
+    /// Re-scan periodically and print changes as NDJSON events.
+    Watch(WatchArgs),
+    /// Check chains against an expected-state manifest; exits with 2 on any mismatch.
+    Verify(VerifyArgs),
+    /// Evaluate security rules over every chain; exits with 2 on findings at or above `--fail-on`.
+    Audit(AuditArgs),
+    /// Re-scan periodically and serve Prometheus metrics on `/metrics`.
+    Serve(ServeArgs),
+    /// Show how a chain field evolved across snapshots stored with `scan --db`.
+    History(HistoryArgs),
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct CommonArgs {
-    /// Ethereum JSON-RPC URL.
-    #[arg(long, env = "MERCATOR_RPC_URL", value_parser = parse_rpc_url)]
-    pub rpc_url: String,
-    /// Bridgehub contract address.
-    #[arg(long, value_parser = parse_address)]
-    pub bridgehub: String,
+    /// Ethereum JSON-RPC URL; overrides the profile's `rpc_urls`.
+    #[arg(
+        long,
+        env = "MERCATOR_RPC_URL",
+        value_parser = parse_rpc_url,
+        required_unless_present = "profile"
+    )]
+    pub rpc_url: Option<String>,
+    /// Bridgehub contract address; overrides the profile's `bridgehub`.
+    #[arg(
+        long,
+        env = "MERCATOR_BRIDGEHUB",
+        value_parser = parse_address,
+        required_unless_present = "profile"
+    )]
+    pub bridgehub: Option<Address>,
+    /// Named profile of the config file supplying the RPC URL and Bridgehub.
+    #[arg(long, env = "MERCATOR_PROFILE")]
+    pub profile: Option<String>,
+    /// Config file with `[profile.NAME]` tables, read when a profile is selected.
+    #[arg(long, env = "MERCATOR_CONFIG", default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
     /// HTTP timeout for RPC calls.
     #[arg(long, default_value_t = 15)]
     pub timeout_secs: u64,
     /// Print additional diagnostics.
     #[arg(long, default_value_t = false)]
     pub verbose: bool,
+    /// Known L1 DA validator as `ADDRESS=LAYER` (rollup, avail, celestia, eigenda, no-da);
+    /// extends the profile's `da_validators`.
+    #[arg(long = "da-validator", value_parser = parse_known_da_validator)]
+    pub da_validators: Vec<KnownDaValidator>,
+    /// Suppress warnings with this code, e.g. `W003`; repeatable.
+    #[arg(long = "allow")]
+    pub allow: Vec<DiagnosticCode>,
+    /// Directory persisting RPC results across runs; results are only reused
+    /// within the run when unset.
+    #[arg(long, env = "MERCATOR_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+    /// Seconds a read of the latest block is reused; 0 always re-reads.
+    /// Other commands pin their reads to the head block they start at, which
+    /// are reused indefinitely, and only fall back to this when the head
+    /// cannot be read. Defaults to `DEFAULT_LATEST_TTL_SECS`, or 0 for `watch`
+    /// and `serve`, whose rounds must see the state at their own block.
+    #[arg(long)]
+    pub latest_ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct OutputArgs {
+    /// Output format: text, a contract graph (dot, mermaid), a report
+    /// (markdown, html), one row per chain (csv, tsv) or json.
+    #[arg(long, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// Comma-separated `csv`/`tsv` columns, in output order; all when omitted.
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Vec<ChainColumn>,
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct ScanArgs {
     #[command(flatten)]
     pub common: CommonArgs,
+    /// List chain registrations and CTM add/remove events from Bridgehub logs.
+    #[arg(long, default_value_t = false)]
+    pub history: bool,
+    /// First block indexed by `--history`.
+    #[arg(long, default_value_t = 0, requires = "history")]
+    pub from_block: u64,
+    /// Maximum block span per `eth_getLogs` request; shrunk automatically on provider limits.
+    #[arg(long, default_value_t = DEFAULT_LOG_BLOCK_RANGE, requires = "history")]
+    pub log_block_range: u64,
+    /// Inspect every chain and write the full snapshot as JSON to this path.
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+    /// Inspect every chain and append the snapshot to this SQLite database.
+    #[arg(long)]
+    pub db: Option<PathBuf>,
+    #[command(flatten)]
+    pub output: OutputArgs,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -50,14 +142,191 @@ pub struct InspectArgs {
     #[command(flatten)]
     pub common: CommonArgs,
     /// Chain ID to inspect.
+    #[arg(long, required_unless_present = "all")]
+    pub chain_id: Option<u64>,
+    /// Inspect every registered chain; `--gateway-rpc-url` and `--l2-rpc-url`
+    /// only apply to a single chain.
+    #[arg(long, default_value_t = false, conflicts_with = "chain_id")]
+    pub all: bool,
+    /// Address probed on legacy validator timelocks (repeatable).
+    #[arg(long = "validator-candidate", value_parser = parse_address)]
+    pub validator_candidates: Vec<Address>,
+    /// JSON-RPC URL of the gateway the chain settles on, to follow migrated chains.
+    #[arg(long, env = "MERCATOR_GATEWAY_RPC_URL", value_parser = parse_rpc_url)]
+    pub gateway_rpc_url: Option<String>,
+    /// Bridgehub address on the gateway.
+    #[arg(long, default_value_t = L2_BRIDGEHUB_ADDRESS, value_parser = parse_address)]
+    pub gateway_bridgehub: Address,
+    /// JSON-RPC URL of the chain's own L2 node, to cross-check it against L1.
+    #[arg(long, env = "MERCATOR_L2_RPC_URL", value_parser = parse_rpc_url)]
+    pub l2_rpc_url: Option<String>,
+    /// Render ownership, admin, upgrade and role changes of the discovered contracts.
+    #[arg(long, default_value_t = false)]
+    pub timeline: bool,
+    /// First block indexed by `--timeline`.
+    #[arg(long, default_value_t = 0, requires = "timeline")]
+    pub from_block: u64,
+    /// Maximum block span per `eth_getLogs` request; shrunk automatically on provider limits.
+    #[arg(long, default_value_t = DEFAULT_LOG_BLOCK_RANGE, requires = "timeline")]
+    pub log_block_range: u64,
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct UpgradesArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Chain ID whose upgrades are listed.
+    #[arg(long)]
+    pub chain_id: u64,
+    /// First block searched for upgrade events.
+    #[arg(long, default_value_t = 0)]
+    pub from_block: u64,
+    /// Maximum block span per `eth_getLogs` request; shrunk automatically on provider limits.
+    #[arg(long, default_value_t = DEFAULT_LOG_BLOCK_RANGE)]
+    pub log_block_range: u64,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BisectArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Chain ID whose field is bisected.
+    #[arg(long)]
+    pub chain_id: u64,
+    /// Field to track, named as in `inspect` (e.g. admin_owner, validator_timelock).
+    #[arg(long)]
+    pub field: BisectField,
+    /// Lower bound of the search range.
+    #[arg(long = "from")]
+    pub from_block: u64,
+    /// Upper bound of the search range; defaults to the latest block.
+    #[arg(long = "to")]
+    pub to_block: Option<u64>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct DiffArgs {
+    /// Snapshot taken first.
+    pub old: PathBuf,
+    /// Snapshot taken later.
+    pub new: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct HistoryArgs {
+    /// SQLite database written by `scan --db`.
+    #[arg(long)]
+    pub db: PathBuf,
+    /// Chain ID whose field is listed.
     #[arg(long)]
     pub chain_id: u64,
+    /// Field to list, named as in audit rules (e.g. protocol_version, admin_owner).
+    #[arg(long, value_parser = PossibleValuesParser::new(CHAIN_FIELDS))]
+    pub field: String,
+    /// Only snapshots of this Bridgehub; overrides the profile's `bridgehub`.
+    #[arg(
+        long,
+        env = "MERCATOR_BRIDGEHUB",
+        value_parser = parse_address,
+        required_unless_present = "profile"
+    )]
+    pub bridgehub: Option<Address>,
+    /// Only snapshots taken on this L1 chain ID; defaults to the profile's `l1_chain_id`.
+    #[arg(long)]
+    pub network_chain_id: Option<u64>,
+    /// Named profile of the config file supplying the Bridgehub and L1 chain ID.
+    #[arg(long, env = "MERCATOR_PROFILE")]
+    pub profile: Option<String>,
+    /// Config file with `[profile.NAME]` tables, read when a profile is selected.
+    #[arg(long, env = "MERCATOR_CONFIG", default_value = DEFAULT_CONFIG_PATH)]
+    pub config: PathBuf,
 }
 
-pub fn parse_address(value: &str) -> Result<String, String> {
-    let address = Address::from_str(value)
-        .map_err(|_| "address must be 0x-prefixed and 20 bytes long".to_string())?;
-    Ok(format!("{address:#x}"))
+#[derive(Debug, Clone, Args)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Re-scan once the head advanced by N blocks instead of every `--interval-secs`.
+    #[arg(long, conflicts_with = "interval_secs")]
+    pub interval_blocks: Option<u64>,
+    /// Seconds between scans.
+    #[arg(long, default_value_t = 60)]
+    pub interval_secs: u64,
+    /// Seconds between `eth_blockNumber` polls with `--interval-blocks`.
+    #[arg(long, default_value_t = 12)]
+    pub poll_secs: u64,
+    /// URL receiving each change event as a JSON POST.
+    #[arg(long, env = "MERCATOR_WEBHOOK_URL", value_parser = parse_webhook_url)]
+    pub webhook_url: Option<String>,
+    /// Stop after N scan rounds, including the baseline; runs until interrupted by default.
+    #[arg(long)]
+    pub rounds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// TOML manifest with one `[[chain]]` table of expected values per chain.
+    #[arg(long)]
+    pub manifest: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Address the metrics endpoint listens on.
+    #[arg(long, default_value = "127.0.0.1:9100")]
+    pub listen: SocketAddr,
+    /// Seconds between scan rounds.
+    #[arg(long, default_value_t = 300)]
+    pub interval_secs: u64,
+    /// Stop after N scan rounds; runs until interrupted by default.
+    #[arg(long)]
+    pub rounds: Option<u64>,
+    /// Also serve the read-only JSON API over the latest round:
+    /// `/topology`, `/chains/{id}`, `/whois/{address}` and `/health`.
+    #[arg(long)]
+    pub api: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct AuditArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// TOML rules file tuning built-in rules and adding custom `[[rule]]` checks.
+    #[arg(long)]
+    pub rules: Option<PathBuf>,
+    /// Chain ID to audit (repeatable); all registered chains by default.
+    #[arg(long = "chain-id")]
+    pub chain_ids: Vec<u64>,
+    /// Lowest finding severity that makes the command fail.
+    #[arg(long, default_value_t = Severity::High)]
+    pub fail_on: Severity,
+    /// First block searched for the start of pending ownership transfers.
+    #[arg(long, default_value_t = 0)]
+    pub from_block: u64,
+    /// Maximum block span per `eth_getLogs` request; shrunk automatically on provider limits.
+    #[arg(long, default_value_t = DEFAULT_LOG_BLOCK_RANGE)]
+    pub log_block_range: u64,
+}
+
+pub fn parse_address(value: &str) -> Result<Address, String> {
+    Address::from_str(value)
+        .map_err(|_| "address must be 0x-prefixed and 20 bytes long".to_string())
+}
+
+pub fn parse_known_da_validator(value: &str) -> Result<KnownDaValidator, String> {
+    let (address, layer) = value
+        .split_once('=')
+        .ok_or_else(|| "da validator must be formatted as ADDRESS=LAYER".to_string())?;
+    Ok(KnownDaValidator {
+        address: parse_address(address)?,
+        layer: DaLayer::from_str(layer)?,
+    })
 }
 
 pub fn parse_rpc_url(value: &str) -> Result<String, String> {
@@ -66,9 +335,16 @@ pub fn parse_rpc_url(value: &str) -> Result<String, String> {
         .map_err(|err| format!("invalid rpc url: {err}"))
 }
 
+pub fn parse_webhook_url(value: &str) -> Result<String, String> {
+    reqwest::Url::parse(value)
+        .map(|url| url.to_string())
+        .map_err(|err| format!("invalid webhook url: {err}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::address;
     use clap::Parser;
 
     #[test]
@@ -77,6 +353,18 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn cli_accepts_a_profile_instead_of_connection_flags() {
+        let cli = Cli::try_parse_from(["mercator", "scan", "--profile", "mainnet"])
+            .expect("scan --profile should parse");
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert_eq!(args.common.profile.as_deref(), Some("mainnet"));
+        assert_eq!(args.common.rpc_url, None);
+        assert_eq!(args.common.config, PathBuf::from("mercator.toml"));
+    }
+
     #[test]
     fn cli_requires_inspect_chain_id() {
         let result = Cli::try_parse_from([
@@ -105,13 +393,81 @@ mod tests {
         let Command::Scan(args) = cli.command else {
             panic!("expected scan command");
         };
-        assert_eq!(args.common.rpc_url, "https://example.com/");
+        assert_eq!(args.common.rpc_url.as_deref(), Some("https://example.com/"));
         assert_eq!(
             args.common.bridgehub,
-            "0x0000000000000000000000000000000000000001"
+            Some(address!("0x0000000000000000000000000000000000000001"))
         );
         assert_eq!(args.common.timeout_secs, 15);
         assert!(!args.common.verbose);
+        assert_eq!(args.output.format, OutputFormat::Text);
+    }
+
+    #[test]
+    fn cli_parses_scan_format() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--format",
+            "mermaid",
+        ])
+        .expect("scan format should parse");
+
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert_eq!(args.output.format, OutputFormat::Mermaid);
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--format",
+            "svg",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_scan_history_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--history",
+            "--from-block",
+            "19000000",
+        ])
+        .expect("scan history flags should parse");
+
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert!(args.history);
+        assert_eq!(args.from_block, 19_000_000);
+        assert_eq!(args.log_block_range, DEFAULT_LOG_BLOCK_RANGE);
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--from-block",
+            "1",
+        ]);
+        assert!(result.is_err());
     }
 
     #[test]
@@ -131,14 +487,503 @@ mod tests {
         let Command::Inspect(args) = cli.command else {
             panic!("expected inspect command");
         };
-        assert_eq!(args.common.rpc_url, "https://example.com/");
+        assert_eq!(args.common.rpc_url.as_deref(), Some("https://example.com/"));
         assert_eq!(
             args.common.bridgehub,
-            "0x0000000000000000000000000000000000000001"
+            Some(address!("0x0000000000000000000000000000000000000001"))
         );
-        assert_eq!(args.chain_id, 324);
+        assert_eq!(args.chain_id, Some(324));
+        assert!(!args.all);
         assert_eq!(args.common.timeout_secs, 15);
         assert!(!args.common.verbose);
+        assert!(args.validator_candidates.is_empty());
+        assert_eq!(args.gateway_rpc_url, None);
+        assert_eq!(
+            args.gateway_bridgehub,
+            address!("0x0000000000000000000000000000000000010002")
+        );
+        assert_eq!(args.l2_rpc_url, None);
+        assert!(!args.timeline);
+    }
+
+    #[test]
+    fn cli_parses_inspect_all_as_csv_with_columns() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "inspect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--all",
+            "--format",
+            "csv",
+            "--columns",
+            "chain_id,admin_owner,threshold",
+        ])
+        .expect("inspect --all should parse");
+
+        let Command::Inspect(args) = cli.command else {
+            panic!("expected inspect command");
+        };
+        assert!(args.all);
+        assert_eq!(args.chain_id, None);
+        assert_eq!(args.output.format, OutputFormat::Csv);
+        assert_eq!(
+            args.output.columns,
+            vec![
+                ChainColumn::ChainId,
+                ChainColumn::AdminOwner,
+                ChainColumn::Threshold
+            ]
+        );
+
+        for extra in [
+            &["--chain-id", "324"][..],
+            &["--columns", "chain_id,owner"][..],
+        ] {
+            let result = Cli::try_parse_from(
+                [
+                    "mercator",
+                    "inspect",
+                    "--rpc-url",
+                    "https://example.com",
+                    "--bridgehub",
+                    "0x0000000000000000000000000000000000000001",
+                    "--all",
+                ]
+                .into_iter()
+                .chain(extra.iter().copied()),
+            );
+            assert!(result.is_err());
+        }
+        let result = Cli::try_parse_from([
+            "mercator",
+            "inspect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_inspect_l2_rpc_url() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "inspect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--l2-rpc-url",
+            "https://l2.example.com",
+        ])
+        .expect("inspect l2 rpc url should parse");
+
+        let Command::Inspect(args) = cli.command else {
+            panic!("expected inspect command");
+        };
+        assert_eq!(args.l2_rpc_url.as_deref(), Some("https://l2.example.com/"));
+    }
+
+    #[test]
+    fn cli_parses_inspect_timeline_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "inspect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--timeline",
+            "--from-block",
+            "18000000",
+            "--log-block-range",
+            "2000",
+        ])
+        .expect("inspect timeline flags should parse");
+
+        let Command::Inspect(args) = cli.command else {
+            panic!("expected inspect command");
+        };
+        assert!(args.timeline);
+        assert_eq!(args.from_block, 18_000_000);
+        assert_eq!(args.log_block_range, 2_000);
+    }
+
+    #[test]
+    fn cli_parses_upgrades_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "upgrades",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--from-block",
+            "17000000",
+        ])
+        .expect("upgrades command should parse");
+
+        let Command::Upgrades(args) = cli.command else {
+            panic!("expected upgrades command");
+        };
+        assert_eq!(args.chain_id, 324);
+        assert_eq!(args.from_block, 17_000_000);
+        assert_eq!(args.log_block_range, DEFAULT_LOG_BLOCK_RANGE);
+    }
+
+    #[test]
+    fn cli_parses_bisect_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "bisect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--field",
+            "admin_owner",
+            "--from",
+            "100",
+            "--to",
+            "200",
+        ])
+        .expect("bisect command should parse");
+
+        let Command::Bisect(args) = cli.command else {
+            panic!("expected bisect command");
+        };
+        assert_eq!(args.field, BisectField::AdminOwner);
+        assert_eq!(args.from_block, 100);
+        assert_eq!(args.to_block, Some(200));
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "bisect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--field",
+            "owner",
+            "--from",
+            "100",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_scan_save_and_diff() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--save",
+            "before.json",
+        ])
+        .expect("scan command should parse");
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert_eq!(args.save, Some(PathBuf::from("before.json")));
+
+        let cli = Cli::try_parse_from(["mercator", "diff", "before.json", "after.json"])
+            .expect("diff command should parse");
+        let Command::Diff(args) = cli.command else {
+            panic!("expected diff command");
+        };
+        assert_eq!(args.old, PathBuf::from("before.json"));
+        assert_eq!(args.new, PathBuf::from("after.json"));
+    }
+
+    #[test]
+    fn cli_parses_watch_intervals() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "watch",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--interval-blocks",
+            "25",
+            "--webhook-url",
+            "http://127.0.0.1:9000/hook",
+        ])
+        .expect("watch command should parse");
+        let Command::Watch(args) = cli.command else {
+            panic!("expected watch command");
+        };
+        assert_eq!(args.interval_blocks, Some(25));
+        assert_eq!(args.interval_secs, 60);
+        assert_eq!(args.common.latest_ttl_secs, None);
+        assert_eq!(
+            args.webhook_url.as_deref(),
+            Some("http://127.0.0.1:9000/hook")
+        );
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "watch",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--interval-blocks",
+            "25",
+            "--interval-secs",
+            "30",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_scan_db_and_history() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--db",
+            "mercator.db",
+        ])
+        .expect("scan --db should parse");
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert_eq!(args.db, Some(PathBuf::from("mercator.db")));
+
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "history",
+            "--db",
+            "mercator.db",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--network-chain-id",
+            "1",
+            "--chain-id",
+            "324",
+            "--field",
+            "protocol_version",
+        ])
+        .expect("history should parse");
+        let Command::History(args) = cli.command else {
+            panic!("expected history command");
+        };
+        assert_eq!(args.chain_id, 324);
+        assert_eq!(args.field, "protocol_version");
+        assert_eq!(args.network_chain_id, Some(1));
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "history",
+            "--db",
+            "mercator.db",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--field",
+            "owner",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_serve_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "serve",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+        ])
+        .expect("serve command should parse");
+        let Command::Serve(args) = cli.command else {
+            panic!("expected serve command");
+        };
+        assert_eq!(args.listen, "127.0.0.1:9100".parse().unwrap());
+        assert_eq!(args.interval_secs, 300);
+        assert_eq!(args.rounds, None);
+        assert!(!args.api);
+
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "serve",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--api",
+        ])
+        .expect("serve --api should parse");
+        let Command::Serve(args) = cli.command else {
+            panic!("expected serve command");
+        };
+        assert!(args.api);
+
+        let result = Cli::try_parse_from([
+            "mercator",
+            "serve",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--listen",
+            "localhost",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_requires_verify_manifest() {
+        let args = [
+            "mercator",
+            "verify",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+
+        let cli = Cli::try_parse_from(args.into_iter().chain(["--manifest", "expected.toml"]))
+            .expect("verify command should parse");
+        let Command::Verify(args) = cli.command else {
+            panic!("expected verify command");
+        };
+        assert_eq!(args.manifest, PathBuf::from("expected.toml"));
+    }
+
+    #[test]
+    fn cli_parses_audit_flags() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "audit",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--chain-id",
+            "325",
+            "--fail-on",
+            "medium",
+        ])
+        .expect("audit command should parse");
+        let Command::Audit(args) = cli.command else {
+            panic!("expected audit command");
+        };
+        assert_eq!(args.chain_ids, vec![324, 325]);
+        assert_eq!(args.fail_on, Severity::Medium);
+        assert_eq!(args.rules, None);
+    }
+
+    #[test]
+    fn cli_parses_allowed_warning_codes() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "scan",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--allow",
+            "W003",
+            "--allow",
+            "w010",
+        ])
+        .expect("scan command should parse");
+        let Command::Scan(args) = cli.command else {
+            panic!("expected scan command");
+        };
+        assert_eq!(
+            args.common.allow,
+            vec![
+                DiagnosticCode::LegacyValidatorTimelock,
+                DiagnosticCode::Skipped
+            ]
+        );
+
+        assert!(
+            Cli::try_parse_from([
+                "mercator",
+                "scan",
+                "--rpc-url",
+                "https://example.com",
+                "--bridgehub",
+                "0x0000000000000000000000000000000000000001",
+                "--allow",
+                "W999",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn cli_parses_repeated_validator_candidates() {
+        let cli = Cli::try_parse_from([
+            "mercator",
+            "inspect",
+            "--rpc-url",
+            "https://example.com",
+            "--bridgehub",
+            "0x0000000000000000000000000000000000000001",
+            "--chain-id",
+            "324",
+            "--validator-candidate",
+            "0x0000000000000000000000000000000000000011",
+            "--validator-candidate",
+            "0x0000000000000000000000000000000000000012",
+        ])
+        .expect("inspect command should parse");
+
+        let Command::Inspect(args) = cli.command else {
+            panic!("expected inspect command");
+        };
+        assert_eq!(
+            args.validator_candidates,
+            vec![
+                address!("0x0000000000000000000000000000000000000011"),
+                address!("0x0000000000000000000000000000000000000012"),
+            ]
+        );
+    }
+
+    #[test]
+    fn da_validator_parser_reads_address_and_layer() {
+        let known = parse_known_da_validator("0x00000000000000000000000000000000000000A1=celestia")
+            .expect("da validator should parse");
+        assert_eq!(
+            known.address,
+            address!("0x00000000000000000000000000000000000000a1")
+        );
+        assert_eq!(known.layer, DaLayer::Celestia);
+        assert!(parse_known_da_validator("0x00000000000000000000000000000000000000a1").is_err());
+        assert!(
+            parse_known_da_validator("0x00000000000000000000000000000000000000a1=blobs").is_err()
+        );
     }
 
     #[test]
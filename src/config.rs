@@ -0,0 +1,427 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    render::AddressLabels,
+    rpc::{RpcClient, RpcError},
+    scanner::data_availability::{DaLayer, KnownDaValidator},
+};
+
+/// Default config file, looked up in the working directory.
+pub const DEFAULT_CONFIG_PATH: &str = "mercator.toml";
+
+/// `mercator.toml`: named network profiles selected with `--profile`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default, rename = "profile")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// L1 RPC endpoints, tried in order until one answers `eth_chainId`.
+    #[serde(default)]
+    pub rpc_urls: Vec<String>,
+    pub bridgehub: Option<Address>,
+    /// Expected `eth_chainId` of the L1 RPC, checked at startup.
+    pub l1_chain_id: Option<u64>,
+    /// L2 RPC endpoint per chain ID, used by `inspect` without `--l2-rpc-url`.
+    #[serde(default)]
+    pub l2_rpc_urls: BTreeMap<String, String>,
+    /// Known L1 DA validators on this network (address to DA layer); `--da-validator`
+    /// entries extend and override them.
+    #[serde(default)]
+    pub da_validators: BTreeMap<String, String>,
+    /// Address labels file; relative paths resolve against the config file.
+    pub labels_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid config {path}: {source}")]
+    Toml {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error("profile `{profile}` has invalid l2_rpc_urls key `{key}` (expected a chain ID)")]
+    InvalidL2ChainId { profile: String, key: String },
+    #[error("profile `{profile}` has invalid da_validators entry `{key}`: {reason}")]
+    InvalidDaValidator {
+        profile: String,
+        key: String,
+        reason: String,
+    },
+    #[error("labels file {path} has invalid address `{key}`")]
+    InvalidLabel { path: String, key: String },
+    #[error("profile `{profile}` is not defined in {path} (available: {available})")]
+    UnknownProfile {
+        profile: String,
+        path: String,
+        available: String,
+    },
+    #[error("no {0} given: pass --{0} or select a profile that sets it")]
+    Missing(&'static str),
+    #[error("rpc {url} reports chain ID {found}, but the profile expects L1 chain ID {expected}")]
+    ChainIdMismatch {
+        url: String,
+        expected: u64,
+        found: u64,
+    },
+    #[error("no rpc url answered eth_chainId: {0}")]
+    Unreachable(RpcError),
+}
+
+/// Connection settings once CLI flags and env vars are layered over the
+/// selected profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Network {
+    /// Candidate L1 RPC URLs, in preference order.
+    pub rpc_urls: Vec<String>,
+    pub bridgehub: Address,
+    pub l1_chain_id: Option<u64>,
+    pub l2_rpc_urls: BTreeMap<u64, String>,
+    pub da_validators: Vec<KnownDaValidator>,
+    pub labels_file: Option<PathBuf>,
+}
+
+/// Reads a config file, resolving each profile's `labels_file` against it.
+pub fn load_config(path: &Path) -> Result<Config, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let mut config: Config = toml::from_str(&contents).map_err(|source| ConfigError::Toml {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    for profile in config.profiles.values_mut() {
+        if let Some(labels_file) = &mut profile.labels_file
+            && labels_file.is_relative()
+        {
+            *labels_file = base.join(&*labels_file);
+        }
+    }
+    Ok(config)
+}
+
+/// Reads a labels file: a TOML table mapping addresses to names.
+pub fn load_labels(path: &Path) -> Result<AddressLabels, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let labels: BTreeMap<String, String> =
+        toml::from_str(&contents).map_err(|source| ConfigError::Toml {
+            path: path.display().to_string(),
+            source,
+        })?;
+    labels
+        .into_iter()
+        .map(|(key, label)| match Address::from_str(&key) {
+            Ok(address) => Ok((address, label)),
+            Err(_) => Err(ConfigError::InvalidLabel {
+                path: path.display().to_string(),
+                key,
+            }),
+        })
+        .collect::<Result<_, _>>()
+        .map(AddressLabels::new)
+}
+
+impl Config {
+    pub fn profile(&self, name: &str, path: &Path) -> Result<&Profile, ConfigError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                profile: name.to_string(),
+                path: path.display().to_string(),
+                available: self.profiles.keys().cloned().collect::<Vec<_>>().join(", "),
+            })
+    }
+}
+
+/// Layers `rpc_url` and `bridgehub` (from CLI flags or env vars) over
+/// `profile`; explicit values always win.
+pub fn resolve_network(
+    rpc_url: Option<&str>,
+    bridgehub: Option<Address>,
+    profile: Option<(&str, &Profile)>,
+) -> Result<Network, ConfigError> {
+    let (name, profile) = match profile {
+        Some((name, profile)) => (name, profile.clone()),
+        None => ("", Profile::default()),
+    };
+    let rpc_urls = match rpc_url {
+        Some(url) => vec![url.to_string()],
+        None if profile.rpc_urls.is_empty() => return Err(ConfigError::Missing("rpc-url")),
+        None => profile.rpc_urls,
+    };
+    let l2_rpc_urls = profile
+        .l2_rpc_urls
+        .into_iter()
+        .map(|(key, url)| match key.parse() {
+            Ok(chain_id) => Ok((chain_id, url)),
+            Err(_) => Err(ConfigError::InvalidL2ChainId {
+                profile: name.to_string(),
+                key,
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+    let da_validators = profile
+        .da_validators
+        .into_iter()
+        .map(|(key, layer)| {
+            let invalid = |reason: String| ConfigError::InvalidDaValidator {
+                profile: name.to_string(),
+                key: key.clone(),
+                reason,
+            };
+            Ok(KnownDaValidator {
+                address: Address::from_str(&key)
+                    .map_err(|_| invalid("expected a 0x-prefixed address".to_string()))?,
+                layer: DaLayer::from_str(&layer).map_err(invalid)?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Network {
+        rpc_urls,
+        bridgehub: bridgehub
+            .or(profile.bridgehub)
+            .ok_or(ConfigError::Missing("bridgehub"))?,
+        l1_chain_id: profile.l1_chain_id,
+        l2_rpc_urls,
+        da_validators,
+        labels_file: profile.labels_file,
+    })
+}
+
+/// Picks the first of `network.rpc_urls` that answers `eth_chainId` and checks
+/// it against `network.l1_chain_id`. A single URL without an expected chain ID
+/// is returned without a request.
+pub fn select_rpc_url<C: RpcClient>(
+    network: &Network,
+    connect: impl Fn(&str) -> Result<C, RpcError>,
+) -> Result<String, ConfigError> {
+    if let ([url], None) = (network.rpc_urls.as_slice(), network.l1_chain_id) {
+        return Ok(url.clone());
+    }
+    let mut last_error = RpcError::InvalidResponse("no rpc url configured".to_string());
+    for url in &network.rpc_urls {
+        match connect(url).and_then(|client| client.eth_chain_id()) {
+            Ok(found) => {
+                return match network.l1_chain_id {
+                    Some(expected) if expected != found => Err(ConfigError::ChainIdMismatch {
+                        url: url.clone(),
+                        expected,
+                        found,
+                    }),
+                    _ => Ok(url.clone()),
+                };
+            }
+            Err(err) => last_error = err,
+        }
+    }
+    Err(ConfigError::Unreachable(last_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    struct ChainIdOnly(Result<u64, RpcError>);
+
+    impl RpcClient for ChainIdOnly {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            self.0.clone()
+        }
+    }
+
+    /// `down.example` is unreachable, `sepolia.example` reports 11155111 and
+    /// every other URL reports mainnet.
+    fn connect(url: &str) -> Result<ChainIdOnly, RpcError> {
+        Ok(ChainIdOnly(match url {
+            "https://down.example" => Err(RpcError::Transport("connection refused".to_string())),
+            "https://sepolia.example" => Ok(11_155_111),
+            _ => Ok(1),
+        }))
+    }
+
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mercator-config-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(DEFAULT_CONFIG_PATH);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_profiles_and_resolves_labels_next_to_the_config() {
+        let path = write_config(
+            "load",
+            r#"
+[profile.mainnet]
+rpc_urls = ["https://down.example", "https://mainnet.example"]
+bridgehub = "0x0000000000000000000000000000000000000001"
+l1_chain_id = 1
+l2_rpc_urls = { 324 = "https://l2.example" }
+da_validators = { "0x00000000000000000000000000000000000000a1" = "celestia" }
+labels_file = "labels.toml"
+
+[profile.sepolia]
+rpc_urls = ["https://sepolia.example"]
+"#,
+        );
+
+        let config = load_config(&path).expect("config should load");
+        let mainnet = config.profile("mainnet", &path).unwrap();
+        assert_eq!(mainnet.l1_chain_id, Some(1));
+        assert_eq!(
+            mainnet.labels_file,
+            Some(path.parent().unwrap().join("labels.toml"))
+        );
+        let err = config.profile("devnet", &path).unwrap_err();
+        assert!(err.to_string().contains("(available: mainnet, sepolia)"));
+
+        let network = resolve_network(None, None, Some(("mainnet", mainnet))).unwrap();
+        assert_eq!(
+            network.bridgehub,
+            address!("0x0000000000000000000000000000000000000001")
+        );
+        assert_eq!(network.l2_rpc_urls[&324], "https://l2.example");
+        assert_eq!(
+            network.da_validators,
+            vec![KnownDaValidator {
+                address: address!("0x00000000000000000000000000000000000000a1"),
+                layer: DaLayer::Celestia,
+            }]
+        );
+        assert_eq!(
+            select_rpc_url(&network, connect).unwrap(),
+            "https://mainnet.example"
+        );
+
+        let labels_file = network.labels_file.as_deref().unwrap();
+        fs::write(
+            labels_file,
+            "\"0x0000000000000000000000000000000000000001\" = \"Bridgehub\"\n",
+        )
+        .unwrap();
+        let labels = load_labels(labels_file).expect("labels should load");
+        assert_eq!(
+            labels.get(address!("0x0000000000000000000000000000000000000001")),
+            Some("Bridgehub")
+        );
+        fs::write(labels_file, "\"0x01\" = \"Bridgehub\"\n").unwrap();
+        assert!(matches!(
+            load_labels(labels_file),
+            Err(ConfigError::InvalidLabel { .. })
+        ));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn flags_override_the_profile() {
+        let profile = Profile {
+            rpc_urls: vec!["https://mainnet.example".to_string()],
+            bridgehub: Some(address!("0x0000000000000000000000000000000000000001")),
+            ..Profile::default()
+        };
+        let bridgehub = address!("0x0000000000000000000000000000000000000002");
+
+        let network = resolve_network(
+            Some("https://other.example"),
+            Some(bridgehub),
+            Some(("mainnet", &profile)),
+        )
+        .unwrap();
+        assert_eq!(network.rpc_urls, vec!["https://other.example".to_string()]);
+        assert_eq!(network.bridgehub, bridgehub);
+
+        assert!(matches!(
+            resolve_network(None, Some(bridgehub), None),
+            Err(ConfigError::Missing("rpc-url"))
+        ));
+        assert!(matches!(
+            resolve_network(Some("https://other.example"), None, None),
+            Err(ConfigError::Missing("bridgehub"))
+        ));
+    }
+
+    #[test]
+    fn rejects_an_rpc_on_the_wrong_network() {
+        let network = Network {
+            rpc_urls: vec!["https://sepolia.example".to_string()],
+            l1_chain_id: Some(1),
+            ..Network::default()
+        };
+        let err = select_rpc_url(&network, connect).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "rpc https://sepolia.example reports chain ID 11155111, but the profile expects L1 chain ID 1"
+        );
+
+        let network = Network {
+            rpc_urls: vec!["https://down.example".to_string()],
+            l1_chain_id: Some(1),
+            ..Network::default()
+        };
+        assert!(matches!(
+            select_rpc_url(&network, connect),
+            Err(ConfigError::Unreachable(RpcError::Transport(_)))
+        ));
+    }
+
+    #[test]
+    fn rejects_non_numeric_l2_chain_ids() {
+        let profile = Profile {
+            rpc_urls: vec!["https://mainnet.example".to_string()],
+            bridgehub: Some(address!("0x0000000000000000000000000000000000000001")),
+            l2_rpc_urls: BTreeMap::from([("era".to_string(), "https://l2.example".to_string())]),
+            ..Profile::default()
+        };
+        assert!(matches!(
+            resolve_network(None, None, Some(("mainnet", &profile))),
+            Err(ConfigError::InvalidL2ChainId { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_da_validators() {
+        let profile = Profile {
+            rpc_urls: vec!["https://mainnet.example".to_string()],
+            bridgehub: Some(address!("0x0000000000000000000000000000000000000001")),
+            da_validators: BTreeMap::from([(
+                "0x00000000000000000000000000000000000000a1".to_string(),
+                "blobs".to_string(),
+            )]),
+            ..Profile::default()
+        };
+        assert!(matches!(
+            resolve_network(None, None, Some(("mainnet", &profile))),
+            Err(ConfigError::InvalidDaValidator { .. })
+        ));
+    }
+}
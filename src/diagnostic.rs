@@ -0,0 +1,266 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::model::Severity;
+
+/// Stable identifier of a diagnostic category; the `W0xx` codes never change
+/// meaning once released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DiagnosticCode {
+    /// An on-chain read (call, storage, code, logs) failed.
+    #[serde(rename = "W001")]
+    ResolutionFailed,
+    /// A registry getter returned the zero address.
+    #[serde(rename = "W002")]
+    ZeroAddress,
+    /// A legacy validator timelock could not enumerate its validators.
+    #[serde(rename = "W003")]
+    LegacyValidatorTimelock,
+    /// An `Ownable2Step` transfer awaits acceptance.
+    #[serde(rename = "W004")]
+    PendingOwner,
+    /// A transaction filterer can censor priority transactions.
+    #[serde(rename = "W005")]
+    TransactionFilterer,
+    /// Bridgehub, diamond or gateway disagree about the settlement layer.
+    #[serde(rename = "W006")]
+    SettlementLayerMismatch,
+    /// DA validators disagree with the pubdata pricing mode.
+    #[serde(rename = "W007")]
+    DataAvailabilityMismatch,
+    /// Base token sources disagree.
+    #[serde(rename = "W008")]
+    BaseTokenMismatch,
+    /// A log-derived view (history, timeline, upgrades) is incomplete.
+    #[serde(rename = "W009")]
+    IncompleteLogs,
+    /// An optional follow-up step was skipped.
+    #[serde(rename = "W010")]
+    Skipped,
+    /// A whole chain inspection or scan round failed.
+    #[serde(rename = "W011")]
+    ScanFailed,
+    /// The chain's L2 node disagrees with what L1 reports about it.
+    #[serde(rename = "W012")]
+    L2NodeMismatch,
+}
+
+impl DiagnosticCode {
+    pub const ALL: [Self; 12] = [
+        Self::ResolutionFailed,
+        Self::ZeroAddress,
+        Self::LegacyValidatorTimelock,
+        Self::PendingOwner,
+        Self::TransactionFilterer,
+        Self::SettlementLayerMismatch,
+        Self::DataAvailabilityMismatch,
+        Self::BaseTokenMismatch,
+        Self::IncompleteLogs,
+        Self::Skipped,
+        Self::ScanFailed,
+        Self::L2NodeMismatch,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ResolutionFailed => "W001",
+            Self::ZeroAddress => "W002",
+            Self::LegacyValidatorTimelock => "W003",
+            Self::PendingOwner => "W004",
+            Self::TransactionFilterer => "W005",
+            Self::SettlementLayerMismatch => "W006",
+            Self::DataAvailabilityMismatch => "W007",
+            Self::BaseTokenMismatch => "W008",
+            Self::IncompleteLogs => "W009",
+            Self::Skipped => "W010",
+            Self::ScanFailed => "W011",
+            Self::L2NodeMismatch => "W012",
+        }
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::Skipped => Severity::Info,
+            Self::ResolutionFailed | Self::LegacyValidatorTimelock | Self::IncompleteLogs => {
+                Severity::Low
+            }
+            Self::ZeroAddress
+            | Self::PendingOwner
+            | Self::TransactionFilterer
+            | Self::SettlementLayerMismatch
+            | Self::DataAvailabilityMismatch
+            | Self::BaseTokenMismatch
+            | Self::ScanFailed
+            | Self::L2NodeMismatch => Severity::Medium,
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for DiagnosticCode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|code| code.code().eq_ignore_ascii_case(value))
+            .ok_or_else(|| {
+                let codes = Self::ALL.map(Self::code).join(", ");
+                format!("unknown warning code `{value}` (expected one of: {codes})")
+            })
+    }
+}
+
+/// A non-fatal problem found while scanning. `Display` renders the prose
+/// message followed by the underlying error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub severity: Severity,
+    pub chain_id: Option<u64>,
+    /// Address of the contract involved, when there is one.
+    pub contract: Option<Address>,
+    /// Contract method or RPC method involved, when there is one.
+    pub method: Option<String>,
+    pub message: String,
+    pub error: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(code: DiagnosticCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            chain_id: None,
+            contract: None,
+            method: None,
+            message: message.into(),
+            error: None,
+        }
+    }
+
+    /// A failed on-chain read of `method`.
+    pub fn resolution_failed(
+        method: &str,
+        message: impl Into<String>,
+        error: impl fmt::Display,
+    ) -> Self {
+        Self::new(DiagnosticCode::ResolutionFailed, message)
+            .method(method)
+            .error(error)
+    }
+
+    pub fn chain(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn contract(mut self, contract: Address) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    pub fn error(mut self, error: impl fmt::Display) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            Some(error) => write!(f, "{}: {error}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Drops diagnostics whose code is in `allow`.
+pub fn retain_unsuppressed(
+    diagnostics: &[Diagnostic],
+    allow: &[DiagnosticCode],
+) -> Vec<Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| !allow.contains(&diagnostic.code))
+        .cloned()
+        .collect()
+}
+
+pub fn count_by_code(diagnostics: &[Diagnostic]) -> BTreeMap<DiagnosticCode, usize> {
+    let mut counts = BTreeMap::new();
+    for diagnostic in diagnostics {
+        *counts.entry(diagnostic.code).or_default() += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_message_with_underlying_error() {
+        let diagnostic = Diagnostic::resolution_failed(
+            "getZKChain",
+            "failed to resolve getZKChain for chain 324",
+            "rpc transport error: timeout",
+        )
+        .chain(324);
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "failed to resolve getZKChain for chain 324: rpc transport error: timeout"
+        );
+        assert_eq!(diagnostic.code, DiagnosticCode::ResolutionFailed);
+        assert_eq!(diagnostic.severity, Severity::Low);
+        assert_eq!(diagnostic.method.as_deref(), Some("getZKChain"));
+    }
+
+    #[test]
+    fn codes_round_trip_through_text_and_json() {
+        assert_eq!(
+            "w003".parse::<DiagnosticCode>(),
+            Ok(DiagnosticCode::LegacyValidatorTimelock)
+        );
+        assert!("W999".parse::<DiagnosticCode>().is_err());
+        assert_eq!(
+            serde_json::to_string(&DiagnosticCode::TransactionFilterer).unwrap(),
+            "\"W005\""
+        );
+        for code in DiagnosticCode::ALL {
+            assert_eq!(code.code().parse::<DiagnosticCode>(), Ok(code));
+        }
+    }
+
+    #[test]
+    fn suppresses_and_counts_by_code() {
+        let diagnostics = vec![
+            Diagnostic::new(DiagnosticCode::ZeroAddress, "a"),
+            Diagnostic::new(DiagnosticCode::ZeroAddress, "b"),
+            Diagnostic::new(DiagnosticCode::Skipped, "c"),
+        ];
+
+        let kept = retain_unsuppressed(&diagnostics, &[DiagnosticCode::Skipped]);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(
+            count_by_code(&diagnostics),
+            BTreeMap::from([
+                (DiagnosticCode::ZeroAddress, 2),
+                (DiagnosticCode::Skipped, 1)
+            ])
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy_primitives::Address;
+
+use crate::diagnostic::DiagnosticCode;
+use crate::model::{
+    ChainInspection, ChainSummary, ProtocolSemver, SavedSnapshot, SnapshotChange, SnapshotDiff,
+};
+
+/// Compares two saved snapshots.
+///
+/// Chain removal, CTM reassignment and any owner/admin/validator/filterer
+/// change are flagged as security-relevant; new chains and protocol-version
+/// bumps are informational.
+pub fn diff_snapshots(old: &SavedSnapshot, new: &SavedSnapshot) -> SnapshotDiff {
+    let mut changes = Vec::new();
+
+    let old_chains: BTreeSet<u64> = old.topology.chain_ids.iter().copied().collect();
+    let new_chains: BTreeSet<u64> = new.topology.chain_ids.iter().copied().collect();
+    for chain_id in new_chains.difference(&old_chains) {
+        changes.push(change(
+            Some(*chain_id),
+            "chain",
+            None,
+            Some("registered".to_string()),
+            false,
+        ));
+    }
+    for chain_id in old_chains.difference(&new_chains) {
+        changes.push(change(
+            Some(*chain_id),
+            "chain",
+            Some("registered".to_string()),
+            None,
+            true,
+        ));
+    }
+
+    let old_ctms = chain_ctms(old);
+    let new_ctms = chain_ctms(new);
+    for chain_id in old_chains.intersection(&new_chains) {
+        let (before, after) = (old_ctms.get(chain_id), new_ctms.get(chain_id));
+        if before != after && !ctm_unresolved(old, *chain_id) && !ctm_unresolved(new, *chain_id) {
+            changes.push(change(
+                Some(*chain_id),
+                "ctm",
+                before.map(|ctm| ctm.to_string()),
+                after.map(|ctm| ctm.to_string()),
+                true,
+            ));
+        }
+    }
+
+    let old_ctm_versions: BTreeMap<Address, Option<ProtocolSemver>> = old
+        .topology
+        .ctms
+        .iter()
+        .map(|ctm| (ctm.address, ctm.protocol_version))
+        .collect();
+    // A CTM version is only `None` when its read failed, so it is skipped.
+    for ctm in &new.topology.ctms {
+        if let Some(Some(before)) = old_ctm_versions.get(&ctm.address)
+            && let Some(after) = ctm.protocol_version
+            && *before != after
+        {
+            changes.push(change(
+                None,
+                &format!("ctm {} protocol_version", ctm.address),
+                Some(before.to_string()),
+                Some(after.to_string()),
+                false,
+            ));
+        }
+    }
+
+    let old_inspections = chain_inspections(old);
+    for (chain_id, after) in chain_inspections(new) {
+        if let Some(before) = old_inspections.get(&chain_id) {
+            diff_chain_summary(before, after, &mut changes);
+        }
+    }
+
+    SnapshotDiff {
+        old_block_number: old.block_number,
+        new_block_number: new.block_number,
+        changes,
+    }
+}
+
+/// Compares the summaries of one chain, skipping fields that either side
+/// failed to resolve: a failed read is not evidence of a change.
+fn diff_chain_summary(
+    old: &ChainInspection,
+    new: &ChainInspection,
+    changes: &mut Vec<SnapshotChange>,
+) {
+    let unresolved = |field: &str| old.unresolved.contains(field) || new.unresolved.contains(field);
+    let (old, new) = (&old.chain, &new.chain);
+    let chain_id = Some(new.chain_id);
+    let mut compare = |field: &str, before: Option<String>, after: Option<String>, security| {
+        if before != after && !unresolved(field) {
+            changes.push(change(chain_id, field, before, after, security));
+        }
+    };
+
+    compare(
+        "protocol_version",
+        display(old.protocol_version),
+        display(new.protocol_version),
+        false,
+    );
+    compare(
+        "chain_contract",
+        display(old.chain_contract),
+        display(new.chain_contract),
+        true,
+    );
+    compare("admin", display(old.admin), display(new.admin), true);
+    compare(
+        "admin_owner",
+        display(old.admin_owner),
+        display(new.admin_owner),
+        true,
+    );
+    compare(
+        "admin_pending_owner",
+        display(old.admin_pending_owner),
+        display(new.admin_pending_owner),
+        true,
+    );
+    compare(
+        "validator_timelock",
+        display(old.validator_timelock),
+        display(new.validator_timelock),
+        true,
+    );
+    compare(
+        "validator_timelock_owner",
+        display(old.validator_timelock_owner),
+        display(new.validator_timelock_owner),
+        true,
+    );
+    compare(
+        "validator_timelock_pending_owner",
+        display(old.validator_timelock_pending_owner),
+        display(new.validator_timelock_pending_owner),
+        true,
+    );
+    compare(
+        "validator_timelock_execution_delay",
+        old.validator_timelock_execution_delay
+            .map(|delay| delay.to_string()),
+        new.validator_timelock_execution_delay
+            .map(|delay| delay.to_string()),
+        true,
+    );
+    compare(
+        "timelock_validators",
+        format_timelock_validators(old),
+        format_timelock_validators(new),
+        true,
+    );
+    compare(
+        "multisig_validators",
+        old.multisig_validators
+            .as_deref()
+            .map(join_sorted_addresses),
+        new.multisig_validators
+            .as_deref()
+            .map(join_sorted_addresses),
+        true,
+    );
+    compare(
+        "multisig_signing_threshold",
+        old.multisig_signing_threshold
+            .map(|threshold| threshold.to_string()),
+        new.multisig_signing_threshold
+            .map(|threshold| threshold.to_string()),
+        true,
+    );
+    compare(
+        "transaction_filterer",
+        display(old.transaction_filterer),
+        display(new.transaction_filterer),
+        true,
+    );
+    compare(
+        "l1_da_validator",
+        display(old.data_availability.l1_validator),
+        display(new.data_availability.l1_validator),
+        true,
+    );
+    compare(
+        "l2_da_validator",
+        display(old.data_availability.l2_validator),
+        display(new.data_availability.l2_validator),
+        true,
+    );
+    compare(
+        "settlement_chain_id",
+        old.settlement.settlement_chain_id.map(|id| id.to_string()),
+        new.settlement.settlement_chain_id.map(|id| id.to_string()),
+        true,
+    );
+}
+
+fn chain_ctms(snapshot: &SavedSnapshot) -> BTreeMap<u64, Address> {
+    snapshot
+        .topology
+        .chain_ctms
+        .iter()
+        .map(|chain| (chain.chain_id, chain.ctm))
+        .collect()
+}
+
+/// Whether `snapshot` failed to read the CTM of `chain_id`, either in the
+/// topology scan or in the chain's inspection.
+pub(crate) fn ctm_unresolved(snapshot: &SavedSnapshot, chain_id: u64) -> bool {
+    snapshot.inspections.iter().any(|inspection| {
+        inspection.chain.chain_id == chain_id && inspection.unresolved.contains("ctm")
+    }) || snapshot.topology.warnings.iter().any(|warning| {
+        warning.code == DiagnosticCode::ResolutionFailed
+            && warning.chain_id == Some(chain_id)
+            && warning.method.as_deref() == Some("chainTypeManager")
+    })
+}
+
+fn chain_inspections(snapshot: &SavedSnapshot) -> BTreeMap<u64, &ChainInspection> {
+    snapshot
+        .inspections
+        .iter()
+        .map(|inspection| (inspection.chain.chain_id, inspection))
+        .collect()
+}
+
+fn format_timelock_validators(summary: &ChainSummary) -> Option<String> {
+    summary.timelock_validators.as_ref().map(|validators| {
+        validators
+            .iter()
+            .map(|validator| format!("{} ({})", validator.address, validator.roles.join("/")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
+
+fn display<T: ToString>(value: Option<T>) -> Option<String> {
+    value.map(|value| value.to_string())
+}
+
+/// Joins `addresses` in sorted order, so a reordered set compares equal.
+fn join_sorted_addresses(addresses: &[Address]) -> String {
+    addresses
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn change(
+    chain_id: Option<u64>,
+    field: &str,
+    old: Option<String>,
+    new: Option<String>,
+    security_relevant: bool,
+) -> SnapshotChange {
+    SnapshotChange {
+        chain_id,
+        field: field.to_string(),
+        old,
+        new,
+        security_relevant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::diagnostic::Diagnostic;
+    use crate::model::{ChainCtm, ChainInspection, CtmSummary, TopologySnapshot};
+
+    const CTM_A: Address = address!("0x000000000000000000000000000000000000000a");
+    const CTM_B: Address = address!("0x000000000000000000000000000000000000000b");
+
+    fn summary(chain_id: u64, admin: Address, protocol_version: ProtocolSemver) -> ChainSummary {
+        ChainSummary {
+            chain_id,
+            ctm: Some(CTM_A),
+            admin: Some(admin),
+            protocol_version: Some(protocol_version),
+            ..Default::default()
+        }
+    }
+
+    fn saved(
+        block_number: u64,
+        chains: &[(u64, Address)],
+        summaries: Vec<ChainSummary>,
+    ) -> SavedSnapshot {
+        SavedSnapshot {
+            format_version: 1,
+            network_chain_id: Some(1),
+            block_number: Some(block_number),
+            saved_at_unix: 0,
+            topology: TopologySnapshot {
+                bridgehub: address!("0x0000000000000000000000000000000000000001"),
+                chain_ids: chains.iter().map(|(chain_id, _)| *chain_id).collect(),
+                chain_ctms: chains
+                    .iter()
+                    .map(|(chain_id, ctm)| ChainCtm {
+                        chain_id: *chain_id,
+                        ctm: *ctm,
+                    })
+                    .collect(),
+                ctms: vec![CtmSummary {
+                    address: CTM_A,
+                    protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+                }],
+                chain_data_availability: vec![],
+                history: None,
+                warnings: vec![],
+            },
+            inspections: summaries
+                .into_iter()
+                .map(|chain| ChainInspection {
+                    bridgehub: address!("0x0000000000000000000000000000000000000001"),
+                    chain,
+                    gateway: None,
+                    timeline: None,
+                    l2: None,
+                    warnings: vec![],
+                    unresolved: Default::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let snapshot = saved(
+            10,
+            &[(324, CTM_A)],
+            vec![summary(
+                324,
+                address!("0x0000000000000000000000000000000000000002"),
+                ProtocolSemver::new(0, 29, 0),
+            )],
+        );
+        let diff = diff_snapshots(&snapshot, &snapshot);
+        assert!(diff.changes.is_empty());
+        assert!(!diff.has_security_changes());
+    }
+
+    #[test]
+    fn protocol_bumps_and_new_chains_are_informational() {
+        let old = saved(
+            10,
+            &[(324, CTM_A)],
+            vec![summary(
+                324,
+                address!("0x0000000000000000000000000000000000000002"),
+                ProtocolSemver::new(0, 28, 0),
+            )],
+        );
+        let new = saved(
+            20,
+            &[(324, CTM_A), (325, CTM_A)],
+            vec![summary(
+                324,
+                address!("0x0000000000000000000000000000000000000002"),
+                ProtocolSemver::new(0, 29, 0),
+            )],
+        );
+
+        let diff = diff_snapshots(&old, &new);
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| (change.chain_id, change.field.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(Some(325), "chain"), (Some(324), "protocol_version")]
+        );
+        assert!(!diff.has_security_changes());
+        assert_eq!(diff.old_block_number, Some(10));
+        assert_eq!(diff.new_block_number, Some(20));
+    }
+
+    #[test]
+    fn flags_ctm_reassignment_admin_change_and_removal() {
+        let old = saved(
+            10,
+            &[(324, CTM_A), (325, CTM_A)],
+            vec![summary(
+                324,
+                address!("0x0000000000000000000000000000000000000002"),
+                ProtocolSemver::new(0, 29, 0),
+            )],
+        );
+        let new = saved(
+            20,
+            &[(324, CTM_B)],
+            vec![summary(
+                324,
+                address!("0x0000000000000000000000000000000000000003"),
+                ProtocolSemver::new(0, 29, 0),
+            )],
+        );
+
+        let diff = diff_snapshots(&old, &new);
+        let security: Vec<_> = diff
+            .changes
+            .iter()
+            .filter(|change| change.security_relevant)
+            .map(|change| (change.chain_id, change.field.as_str()))
+            .collect();
+        assert_eq!(
+            security,
+            vec![
+                (Some(325), "chain"),
+                (Some(324), "ctm"),
+                (Some(324), "admin")
+            ]
+        );
+        assert!(diff.has_security_changes());
+    }
+
+    #[test]
+    fn skips_fields_that_failed_to_resolve() {
+        let admin = address!("0x0000000000000000000000000000000000000002");
+        let old = saved(
+            10,
+            &[(324, CTM_A)],
+            vec![summary(324, admin, ProtocolSemver::new(0, 29, 0))],
+        );
+        let mut new = saved(
+            20,
+            &[(324, CTM_A)],
+            vec![ChainSummary {
+                admin: None,
+                ..summary(324, admin, ProtocolSemver::new(0, 29, 0))
+            }],
+        );
+        new.inspections[0].unresolved = BTreeSet::from(["admin".to_string()]);
+
+        let diff = diff_snapshots(&old, &new);
+        assert!(diff.changes.is_empty());
+        // Either side being unresolved is enough.
+        assert!(diff_snapshots(&new, &old).changes.is_empty());
+    }
+
+    #[test]
+    fn skips_unresolved_ctm_reads() {
+        let admin = address!("0x0000000000000000000000000000000000000002");
+        let old = saved(
+            10,
+            &[(324, CTM_A), (325, CTM_A)],
+            vec![summary(324, admin, ProtocolSemver::new(0, 29, 0))],
+        );
+        let mut new = saved(
+            20,
+            &[(325, CTM_A)],
+            vec![summary(324, admin, ProtocolSemver::new(0, 29, 0))],
+        );
+        new.topology.chain_ids = vec![324, 325];
+        new.topology.ctms[0].protocol_version = None;
+        new.topology.warnings.push(
+            Diagnostic::resolution_failed("chainTypeManager", "failed", "timeout").chain(324),
+        );
+        new.inspections[0].unresolved = BTreeSet::from(["ctm".to_string()]);
+
+        assert!(diff_snapshots(&old, &new).changes.is_empty());
+        assert!(diff_snapshots(&new, &old).changes.is_empty());
+
+        // The topology warning alone is enough, without an inspection.
+        new.inspections.clear();
+        assert!(diff_snapshots(&old, &new).changes.is_empty());
+    }
+
+    #[test]
+    fn compares_multisig_validators_as_a_set() {
+        let admin = address!("0x0000000000000000000000000000000000000002");
+        let first = address!("0x0000000000000000000000000000000000000021");
+        let second = address!("0x0000000000000000000000000000000000000022");
+        let with_validators = |validators: Vec<Address>| ChainSummary {
+            multisig_validators: Some(validators),
+            ..summary(324, admin, ProtocolSemver::new(0, 29, 0))
+        };
+        let old = saved(
+            10,
+            &[(324, CTM_A)],
+            vec![with_validators(vec![first, second])],
+        );
+        let new = saved(
+            20,
+            &[(324, CTM_A)],
+            vec![with_validators(vec![second, first])],
+        );
+        assert!(diff_snapshots(&old, &new).changes.is_empty());
+
+        let new = saved(20, &[(324, CTM_A)], vec![with_validators(vec![second])]);
+        let diff = diff_snapshots(&old, &new);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].field, "multisig_validators");
+    }
+}
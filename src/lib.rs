@@ -1,5 +1,16 @@
+pub mod api;
+pub mod audit;
+pub mod cache;
 pub mod cli;
+pub mod config;
+pub mod diagnostic;
+pub mod diff;
+pub mod manifest;
 pub mod model;
 pub mod render;
 pub mod rpc;
 pub mod scanner;
+pub mod serve;
+pub mod snapshot;
+pub mod store;
+pub mod watch;
@@ -1,49 +1,491 @@
 use clap::Parser;
 use mercator::{
-    cli::{Cli, Command},
-    render::{render_chain_inspection, render_topology},
-    rpc::HttpRpcClient,
-    scanner::{inspect_bridgehub_chain, scan_bridgehub_topology},
+    audit::{
+        AuditOptions, audit_bridgehub,
+        config::{AuditConfig, load_audit_config},
+    },
+    cache::{CacheOptions, CachedRpcClient},
+    cli::{Cli, Command, CommonArgs, DEFAULT_LATEST_TTL_SECS},
+    config::{ConfigError, Network, load_config, load_labels, resolve_network, select_rpc_url},
+    diagnostic::{Diagnostic, DiagnosticCode, count_by_code, retain_unsuppressed},
+    diff::diff_snapshots,
+    manifest::{load_manifest, verify_manifest},
+    render::{
+        AddressLabels, OutputFormat, render_audit_report, render_chain_inspection,
+        render_field_bisection, render_field_history, render_formatted, render_snapshot_diff,
+        render_topology, render_upgrade_history, render_verification_report,
+    },
+    rpc::{HttpRpcClient, RpcClient},
+    scanner::{
+        HistoryOptions, InspectOptions, ScanOptions, bisect_chain_field, capture_snapshot,
+        chain_topology, inspect_batch_progress, inspect_bridgehub_chain,
+        inspect_gateway_settlement, inspect_l2_node, scan_bridgehub_topology, scan_chain_upgrades,
+    },
+    serve::{ExporterState, RpcLatency, ServeOptions, TimedRpcClient, run_exporter, serve_http},
+    snapshot::{load_snapshot, save_snapshot},
+    store::SnapshotStore,
+    watch::{WatchInterval, WatchOptions, Webhook, watch},
 };
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Exit code of `diff` when security-relevant fields changed.
+const EXIT_SECURITY_CHANGES: i32 = 2;
+/// Exit code of `verify` when a chain deviates from the manifest.
+const EXIT_MANIFEST_VIOLATIONS: i32 = 2;
+/// Exit code of `audit` when a finding reaches `--fail-on`.
+const EXIT_AUDIT_FINDINGS: i32 = 2;
 
 fn main() {
-    if let Err(err) = run() {
-        eprintln!("error: {err}");
-        std::process::exit(1);
+    match run() {
+        Ok(0) => {}
+        Ok(code) => std::process::exit(code),
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
     }
 }
 
-fn run() -> Result<(), Box<dyn std::error::Error>> {
+fn run() -> Result<i32, Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match cli.command {
         Command::Scan(args) => {
-            let client = HttpRpcClient::new(args.common.rpc_url, args.common.timeout_secs)?;
-            let snapshot = scan_bridgehub_topology(&client, &args.common.bridgehub)?;
-            println!("{}", render_topology(&snapshot, args.common.verbose));
-            emit_warnings(&snapshot.warnings);
+            let (network, client) = rpc_client(&args.common, true)?;
+            let labels = network_labels(&network)?;
+            let options = ScanOptions {
+                // Only the text output renders the per-CTM DA breakdown.
+                data_availability: args.output.format == OutputFormat::Text,
+                da_validators: network.da_validators.clone(),
+                history: args.history.then_some(HistoryOptions {
+                    from_block: args.from_block,
+                    max_block_range: args.log_block_range,
+                }),
+            };
+            let (snapshot, inspections) = if args.save.is_some()
+                || args.db.is_some()
+                || args.output.format.needs_inspections()
+            {
+                // Saved snapshots always carry multisig details, so a diff
+                // never compares a verbose capture against a plain one.
+                let inspect_options = InspectOptions {
+                    verbose: args.common.verbose
+                        || args.save.is_some()
+                        || args.db.is_some()
+                        || args.output.format.needs_multisig_details(),
+                    da_validators: options.da_validators.clone(),
+                    ..InspectOptions::default()
+                };
+                let saved =
+                    capture_snapshot(&client, network.bridgehub, &options, &inspect_options)?;
+                if let Some(path) = &args.save {
+                    save_snapshot(path, &saved)?;
+                }
+                if let Some(path) = &args.db {
+                    SnapshotStore::open(path)?.insert_snapshot(&saved)?;
+                }
+                (saved.topology, saved.inspections)
+            } else {
+                (
+                    scan_bridgehub_topology(&client, network.bridgehub, &options)?,
+                    Vec::new(),
+                )
+            };
+            let output = match args.output.format {
+                OutputFormat::Text => render_topology(&snapshot, &labels),
+                format => render_formatted(
+                    format,
+                    &snapshot,
+                    &inspections,
+                    &args.output.columns,
+                    &labels,
+                ),
+            };
+            println!("{output}");
+            emit_warnings(&snapshot.warnings, &args.common.allow, args.common.verbose);
         }
         Command::Inspect(args) => {
-            let client = HttpRpcClient::new(args.common.rpc_url, args.common.timeout_secs)?;
-            let inspection = inspect_bridgehub_chain(
+            let (network, client) = rpc_client(&args.common, true)?;
+            let labels = network_labels(&network)?;
+            let options = InspectOptions {
+                verbose: args.common.verbose || args.output.format.needs_multisig_details(),
+                validator_candidates: args.validator_candidates,
+                da_validators: network.da_validators.clone(),
+                timeline: args.timeline.then_some(HistoryOptions {
+                    from_block: args.from_block,
+                    max_block_range: args.log_block_range,
+                }),
+            };
+            let Some(chain_id) = args.chain_id else {
+                let scan_options = ScanOptions {
+                    data_availability: false,
+                    da_validators: options.da_validators.clone(),
+                    history: None,
+                };
+                let saved = capture_snapshot(&client, network.bridgehub, &scan_options, &options)?;
+                let output = match args.output.format {
+                    OutputFormat::Text => saved
+                        .inspections
+                        .iter()
+                        .map(|inspection| {
+                            render_chain_inspection(inspection, args.common.verbose, &labels)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n\n"),
+                    format => render_formatted(
+                        format,
+                        &saved.topology,
+                        &saved.inspections,
+                        &args.output.columns,
+                        &labels,
+                    ),
+                };
+                println!("{output}");
+                let warnings = saved
+                    .topology
+                    .warnings
+                    .iter()
+                    .chain(
+                        saved
+                            .inspections
+                            .iter()
+                            .flat_map(|inspection| &inspection.warnings),
+                    )
+                    .cloned()
+                    .collect::<Vec<_>>();
+                emit_warnings(&warnings, &args.common.allow, args.common.verbose);
+                return Ok(0);
+            };
+            let mut inspection =
+                inspect_bridgehub_chain(&client, network.bridgehub, chain_id, &options)?;
+            let gateway_client = args
+                .gateway_rpc_url
+                .map(|url| HttpRpcClient::new(url, args.common.timeout_secs))
+                .transpose()?;
+            if let Some(gateway_client) = gateway_client.as_ref() {
+                inspect_gateway_settlement(
+                    gateway_client,
+                    args.gateway_bridgehub,
+                    &mut inspection,
+                    &options,
+                )?;
+            }
+            if let Some(l2_rpc_url) = args
+                .l2_rpc_url
+                .or_else(|| network.l2_rpc_urls.get(&chain_id).cloned())
+            {
+                let l2_client = HttpRpcClient::new(l2_rpc_url, args.common.timeout_secs)?;
+                inspect_l2_node(&l2_client, &mut inspection);
+                let settlement_client: &dyn RpcClient = match gateway_client.as_ref() {
+                    Some(gateway_client) if inspection.gateway.is_some() => gateway_client,
+                    _ => &client,
+                };
+                inspect_batch_progress(settlement_client, &l2_client, &mut inspection);
+            }
+            match args.output.format {
+                OutputFormat::Text => println!(
+                    "{}",
+                    render_chain_inspection(&inspection, args.common.verbose, &labels)
+                ),
+                format => {
+                    let mut topology = chain_topology(&client, &inspection);
+                    println!(
+                        "{}",
+                        render_formatted(
+                            format,
+                            &topology,
+                            std::slice::from_ref(&inspection),
+                            &args.output.columns,
+                            &labels,
+                        )
+                    );
+                    inspection.warnings.append(&mut topology.warnings);
+                }
+            }
+            emit_warnings(
+                &inspection.warnings,
+                &args.common.allow,
+                args.common.verbose,
+            );
+        }
+        Command::Upgrades(args) => {
+            let (network, client) = rpc_client(&args.common, true)?;
+            let history = scan_chain_upgrades(
                 &client,
-                &args.common.bridgehub,
+                network.bridgehub,
                 args.chain_id,
-                args.common.verbose,
+                HistoryOptions {
+                    from_block: args.from_block,
+                    max_block_range: args.log_block_range,
+                },
+            )?;
+            println!("{}", render_upgrade_history(&history, args.common.verbose));
+            emit_warnings(&history.warnings, &args.common.allow, args.common.verbose);
+        }
+        Command::Bisect(args) => {
+            let (network, client) = rpc_client(&args.common, true)?;
+            let bisection = bisect_chain_field(
+                &client,
+                network.bridgehub,
+                args.chain_id,
+                args.field,
+                args.from_block,
+                args.to_block,
+            )?;
+            println!("{}", render_field_bisection(&bisection));
+        }
+        Command::History(args) => {
+            let profile = match &args.profile {
+                Some(name) => Some(
+                    load_config(&args.config)?
+                        .profile(name, &args.config)?
+                        .clone(),
+                ),
+                None => None,
+            };
+            let bridgehub = args
+                .bridgehub
+                .or(profile.as_ref().and_then(|profile| profile.bridgehub))
+                .ok_or(ConfigError::Missing("bridgehub"))?;
+            let network_chain_id = args
+                .network_chain_id
+                .or(profile.as_ref().and_then(|profile| profile.l1_chain_id));
+            let store = SnapshotStore::open(&args.db)?;
+            let history =
+                store.field_history(bridgehub, network_chain_id, args.chain_id, &args.field)?;
+            println!("{}", render_field_history(&history));
+        }
+        Command::Diff(args) => {
+            let old = load_snapshot(&args.old)?;
+            let new = load_snapshot(&args.new)?;
+            let diff = diff_snapshots(&old, &new);
+            println!("{}", render_snapshot_diff(&diff));
+            if diff.has_security_changes() {
+                return Ok(EXIT_SECURITY_CHANGES);
+            }
+        }
+        Command::Watch(mut args) => {
+            // Each round must see the state at its own block.
+            args.common.latest_ttl_secs.get_or_insert(0);
+            let (network, client) = rpc_client(&args.common, false)?;
+            let webhook = args
+                .webhook_url
+                .as_deref()
+                .map(|url| Webhook::new(url, args.common.timeout_secs))
+                .transpose()?;
+            let scan_options = ScanOptions {
+                data_availability: false,
+                da_validators: network.da_validators.clone(),
+                history: None,
+            };
+            let inspect_options = InspectOptions {
+                verbose: args.common.verbose,
+                da_validators: scan_options.da_validators.clone(),
+                ..InspectOptions::default()
+            };
+            let options = WatchOptions {
+                interval: match args.interval_blocks {
+                    Some(blocks) => WatchInterval::Blocks(blocks),
+                    None => WatchInterval::Seconds(args.interval_secs),
+                },
+                poll_interval: Duration::from_secs(args.poll_secs),
+                max_rounds: args.rounds,
+            };
+            watch(
+                &client,
+                network.bridgehub,
+                &scan_options,
+                &inspect_options,
+                &options,
+                &mut |event| {
+                    match serde_json::to_string(event) {
+                        Ok(line) => println!("{line}"),
+                        Err(err) => eprintln!("warning: failed to encode watch event: {err}"),
+                    }
+                    if let Some(webhook) = &webhook
+                        && let Err(err) = webhook.post(event)
+                    {
+                        eprintln!("warning: {err}");
+                    }
+                },
+                &mut |warning| {
+                    if !args.common.allow.contains(&warning.code) {
+                        eprintln!("warning[{}]: {warning}", warning.code);
+                    }
+                },
             )?;
-            println!(
-                "{}",
-                render_chain_inspection(&inspection, args.common.verbose)
+        }
+        Command::Serve(mut args) => {
+            args.common.latest_ttl_secs.get_or_insert(0);
+            let latency = RpcLatency::default();
+            let (network, rpc_url) = resolve_network_args(&args.common)?;
+            // Only requests that reach the RPC are timed.
+            let client = CachedRpcClient::new(
+                TimedRpcClient::new(
+                    HttpRpcClient::new(rpc_url, args.common.timeout_secs)?,
+                    latency.clone(),
+                ),
+                cache_options(&args.common, false),
             );
-            emit_warnings(&inspection.warnings);
+            let scan_options = ScanOptions {
+                data_availability: false,
+                da_validators: network.da_validators.clone(),
+                history: None,
+            };
+            // Multisig thresholds are only resolved in verbose mode.
+            let inspect_options = InspectOptions {
+                verbose: true,
+                da_validators: scan_options.da_validators.clone(),
+                ..InspectOptions::default()
+            };
+            let listener = TcpListener::bind(args.listen)?;
+            let state = Arc::new(Mutex::new(ExporterState::default()));
+            {
+                let state = Arc::clone(&state);
+                let latency = latency.clone();
+                let api = args.api;
+                thread::spawn(move || serve_http(listener, state, latency, api));
+            }
+            eprintln!("serving metrics on http://{}/metrics", args.listen);
+            if args.api {
+                eprintln!("serving the json api on http://{}/", args.listen);
+            }
+
+            run_exporter(
+                &client,
+                network.bridgehub,
+                &scan_options,
+                &inspect_options,
+                &ServeOptions {
+                    interval: Duration::from_secs(args.interval_secs),
+                    max_rounds: args.rounds,
+                },
+                &state,
+                &mut |warning| {
+                    if !args.common.allow.contains(&warning.code) {
+                        eprintln!("warning[{}]: {warning}", warning.code);
+                    }
+                },
+            );
+        }
+        Command::Verify(args) => {
+            let manifest = load_manifest(&args.manifest)?;
+            let (network, client) = rpc_client(&args.common, true)?;
+            let options = InspectOptions {
+                verbose: args.common.verbose,
+                da_validators: network.da_validators.clone(),
+                ..InspectOptions::default()
+            };
+            let report = verify_manifest(&client, network.bridgehub, &manifest, &options)?;
+            println!("{}", render_verification_report(&report));
+            emit_warnings(&report.warnings, &args.common.allow, args.common.verbose);
+            if !report.violations.is_empty() {
+                return Ok(EXIT_MANIFEST_VIOLATIONS);
+            }
+        }
+        Command::Audit(args) => {
+            let config = match &args.rules {
+                Some(path) => load_audit_config(path)?,
+                None => AuditConfig::default(),
+            };
+            let (network, client) = rpc_client(&args.common, true)?;
+            let options = AuditOptions {
+                inspect: InspectOptions {
+                    verbose: args.common.verbose,
+                    da_validators: network.da_validators.clone(),
+                    ..InspectOptions::default()
+                },
+                chain_ids: args.chain_ids,
+                history: Some(HistoryOptions {
+                    from_block: args.from_block,
+                    max_block_range: args.log_block_range,
+                }),
+            };
+            let report = audit_bridgehub(&client, network.bridgehub, &config, &options)?;
+            println!("{}", render_audit_report(&report));
+            emit_warnings(&report.warnings, &args.common.allow, args.common.verbose);
+            if report
+                .findings
+                .iter()
+                .any(|finding| finding.severity >= args.fail_on)
+            {
+                return Ok(EXIT_AUDIT_FINDINGS);
+            }
         }
     }
 
-    Ok(())
+    Ok(0)
+}
+
+/// Layers `--rpc-url`, `--bridgehub` (or their env vars) and `--da-validator`
+/// over the selected profile and picks the L1 RPC URL, checking its chain ID against the profile.
+fn resolve_network_args(
+    common: &CommonArgs,
+) -> Result<(Network, String), Box<dyn std::error::Error>> {
+    let config;
+    let profile = match &common.profile {
+        Some(name) => {
+            config = load_config(&common.config)?;
+            Some((name.as_str(), config.profile(name, &common.config)?))
+        }
+        None => None,
+    };
+    let mut network = resolve_network(common.rpc_url.as_deref(), common.bridgehub, profile)?;
+    // `--da-validator` entries come first so they win over the profile's.
+    network
+        .da_validators
+        .splice(0..0, common.da_validators.iter().cloned());
+    let rpc_url = select_rpc_url(&network, |url| {
+        HttpRpcClient::new(url.to_string(), common.timeout_secs)
+    })?;
+    Ok((network, rpc_url))
+}
+
+/// Labels from the profile's `labels_file`, if it sets one.
+fn network_labels(network: &Network) -> Result<AddressLabels, ConfigError> {
+    Ok(match network.labels_file.as_deref() {
+        Some(path) => load_labels(path)?,
+        None => AddressLabels::default(),
+    })
+}
+
+/// The L1 client, with RPC results cached per `--cache-dir` and
+/// `--latest-ttl-secs`. With `pin_head` the run reads the head block it
+/// starts at; `watch` and `serve` follow the latest block instead.
+fn rpc_client(
+    common: &CommonArgs,
+    pin_head: bool,
+) -> Result<(Network, CachedRpcClient<HttpRpcClient>), Box<dyn std::error::Error>> {
+    let (network, rpc_url) = resolve_network_args(common)?;
+    let client = HttpRpcClient::new(rpc_url, common.timeout_secs)?;
+    Ok((
+        network,
+        CachedRpcClient::new(client, cache_options(common, pin_head)),
+    ))
 }
 
-fn emit_warnings(warnings: &[String]) {
-    for warning in warnings {
-        eprintln!("warning: {warning}");
+fn cache_options(common: &CommonArgs, pin_head: bool) -> CacheOptions {
+    CacheOptions {
+        dir: common.cache_dir.clone(),
+        latest_ttl: Duration::from_secs(common.latest_ttl_secs.unwrap_or(DEFAULT_LATEST_TTL_SECS)),
+        pin_head,
+    }
+}
+
+fn emit_warnings(warnings: &[Diagnostic], allow: &[DiagnosticCode], verbose: bool) {
+    let warnings = retain_unsuppressed(warnings, allow);
+    for warning in &warnings {
+        eprintln!("warning[{}]: {warning}", warning.code);
+    }
+    if verbose && !warnings.is_empty() {
+        let counts = count_by_code(&warnings)
+            .into_iter()
+            .map(|(code, count)| format!("{code}={count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("warnings by code: {counts}");
     }
 }
@@ -0,0 +1,358 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use alloy_primitives::Address;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::model::{ChainSummary, ManifestViolation, ProtocolSemver, VerificationReport};
+use crate::rpc::RpcClient;
+use crate::scanner::{InspectOptions, ScanError, inspect_bridgehub_chain};
+
+/// Expected chain configuration maintained outside of mercator.
+///
+/// Only fields present in the manifest are verified.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedManifest {
+    #[serde(default, rename = "chain")]
+    pub chains: Vec<ExpectedChain>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedChain {
+    pub chain_id: u64,
+    pub ctm: Option<Address>,
+    pub chain_contract: Option<Address>,
+    pub admin: Option<Address>,
+    pub admin_owner: Option<Address>,
+    pub validator_timelock: Option<Address>,
+    pub validator_timelock_owner: Option<Address>,
+    pub protocol_version: Option<ProtocolSemver>,
+    /// Timelock operator addresses, compared as a set regardless of roles.
+    pub timelock_validators: Option<Vec<Address>>,
+    pub multisig_signing_threshold: Option<u64>,
+    pub multisig_validators: Option<Vec<Address>>,
+}
+
+impl ExpectedChain {
+    fn needs_multisig_details(&self) -> bool {
+        self.multisig_signing_threshold.is_some() || self.multisig_validators.is_some()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("failed to read manifest {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid manifest {path}: {source}")]
+    Toml {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error("manifest lists chain {0} more than once")]
+    DuplicateChain(u64),
+}
+
+/// Reads a TOML manifest; addresses and protocol versions are parsed while
+/// deserializing, so malformed values are reported as TOML errors.
+pub fn load_manifest(path: &Path) -> Result<ExpectedManifest, ManifestError> {
+    let contents = fs::read_to_string(path).map_err(|source| ManifestError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let manifest: ExpectedManifest =
+        toml::from_str(&contents).map_err(|source| ManifestError::Toml {
+            path: path.display().to_string(),
+            source,
+        })?;
+    let mut seen = BTreeSet::new();
+    if let Some(chain) = manifest
+        .chains
+        .iter()
+        .find(|chain| !seen.insert(chain.chain_id))
+    {
+        return Err(ManifestError::DuplicateChain(chain.chain_id));
+    }
+    Ok(manifest)
+}
+
+/// Inspects every manifest chain and compares it against the expected
+/// values. Inspection warnings are tagged with the chain ID.
+pub fn verify_manifest(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    manifest: &ExpectedManifest,
+    options: &InspectOptions,
+) -> Result<VerificationReport, ScanError> {
+    let mut report = VerificationReport {
+        bridgehub,
+        chains_checked: Vec::with_capacity(manifest.chains.len()),
+        fields_checked: 0,
+        violations: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    for expected in &manifest.chains {
+        let chain_options = InspectOptions {
+            verbose: options.verbose || expected.needs_multisig_details(),
+            // Legacy timelocks cannot enumerate operators; probe the expected ones.
+            validator_candidates: match &expected.timelock_validators {
+                Some(validators) => validators.clone(),
+                None => options.validator_candidates.clone(),
+            },
+            ..options.clone()
+        };
+        let inspection =
+            inspect_bridgehub_chain(client, bridgehub, expected.chain_id, &chain_options)?;
+        report.warnings.extend(inspection.warnings.into_iter().map(
+            |warning| match warning.chain_id {
+                Some(_) => warning,
+                None => warning.chain(expected.chain_id),
+            },
+        ));
+
+        let (fields_checked, violations) = verify_chain(expected, &inspection.chain);
+        report.chains_checked.push(expected.chain_id);
+        report.fields_checked += fields_checked;
+        report.violations.extend(violations);
+    }
+
+    Ok(report)
+}
+
+/// Returns the number of fields compared and the mismatches among them.
+pub fn verify_chain(
+    expected: &ExpectedChain,
+    actual: &ChainSummary,
+) -> (usize, Vec<ManifestViolation>) {
+    let mut checked = 0;
+    let mut violations = Vec::new();
+    let chain_id = actual.chain_id;
+    let mut check = |field: &str, want: Option<String>, got: Option<String>| {
+        let Some(want) = want else {
+            return;
+        };
+        checked += 1;
+        if got.as_deref() != Some(want.as_str()) {
+            violations.push(ManifestViolation {
+                chain_id,
+                field: field.to_string(),
+                expected: want,
+                actual: got,
+            });
+        }
+    };
+
+    let text = |address: Option<Address>| address.map(|address| address.to_string());
+    check("ctm", text(expected.ctm), text(actual.ctm));
+    check(
+        "chain_contract",
+        text(expected.chain_contract),
+        text(actual.chain_contract),
+    );
+    check("admin", text(expected.admin), text(actual.admin));
+    check(
+        "admin_owner",
+        text(expected.admin_owner),
+        text(actual.admin_owner),
+    );
+    check(
+        "validator_timelock",
+        text(expected.validator_timelock),
+        text(actual.validator_timelock),
+    );
+    check(
+        "validator_timelock_owner",
+        text(expected.validator_timelock_owner),
+        text(actual.validator_timelock_owner),
+    );
+    check(
+        "protocol_version",
+        expected.protocol_version.map(|version| version.to_string()),
+        actual.protocol_version.map(|version| version.to_string()),
+    );
+    check(
+        "timelock_validators",
+        expected
+            .timelock_validators
+            .as_ref()
+            .map(|validators| format_address_set(validators.iter().copied())),
+        actual.timelock_validators.as_ref().map(|validators| {
+            format_address_set(validators.iter().map(|validator| validator.address))
+        }),
+    );
+    check(
+        "multisig_signing_threshold",
+        expected
+            .multisig_signing_threshold
+            .map(|threshold| threshold.to_string()),
+        actual
+            .multisig_signing_threshold
+            .map(|threshold| threshold.to_string()),
+    );
+    check(
+        "multisig_validators",
+        expected
+            .multisig_validators
+            .as_ref()
+            .map(|validators| format_address_set(validators.iter().copied())),
+        actual
+            .multisig_validators
+            .as_ref()
+            .map(|validators| format_address_set(validators.iter().copied())),
+    );
+
+    (checked, violations)
+}
+
+/// Sorted, deduplicated and comma-joined so order does not matter.
+fn format_address_set(addresses: impl IntoIterator<Item = Address>) -> String {
+    addresses
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|address| address.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::model::{ProtocolSemver, TimelockValidator};
+
+    fn write_manifest(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "mercator-manifest-{name}-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, contents).expect("manifest should be written");
+        path
+    }
+
+    fn summary() -> ChainSummary {
+        ChainSummary {
+            chain_id: 324,
+            ctm: Some(address!("0x000000000000000000000000000000000000000a")),
+            validator_timelock: Some(address!("0x0000000000000000000000000000000000000007")),
+            validator_timelock_owner: Some(address!("0x0000000000000000000000000000000000000004")),
+            validator_timelock_execution_delay: Some(0),
+            timelock_validators: Some(vec![
+                TimelockValidator {
+                    address: address!("0x0000000000000000000000000000000000000011"),
+                    roles: vec!["committer".to_string()],
+                },
+                TimelockValidator {
+                    address: address!("0x0000000000000000000000000000000000000012"),
+                    roles: vec!["prover".to_string()],
+                },
+            ]),
+            admin_owner: Some(address!("0x0000000000000000000000000000000000000004")),
+            protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn loads_typed_manifest_values() {
+        let path = write_manifest(
+            "load",
+            r#"
+[[chain]]
+chain_id = 324
+ctm = "0x000000000000000000000000000000000000000A"
+timelock_validators = ["0x0000000000000000000000000000000000000012"]
+protocol_version = "v0.29.0"
+"#,
+        );
+        let manifest = load_manifest(&path);
+        fs::remove_file(&path).ok();
+        let manifest = manifest.expect("manifest should load");
+
+        assert_eq!(manifest.chains.len(), 1);
+        assert_eq!(
+            manifest.chains[0].ctm,
+            Some(address!("0x000000000000000000000000000000000000000a"))
+        );
+        assert_eq!(manifest.chains[0].admin_owner, None);
+        assert_eq!(
+            manifest.chains[0].protocol_version,
+            Some(ProtocolSemver::new(0, 29, 0))
+        );
+        assert_eq!(
+            manifest.chains[0].timelock_validators,
+            Some(vec![address!("0x0000000000000000000000000000000000000012")])
+        );
+    }
+
+    #[test]
+    fn rejects_typos_and_invalid_addresses() {
+        let path = write_manifest("typo", "[[chain]]\nchain_id = 324\nadmin_ownr = \"0x01\"\n");
+        let typo = load_manifest(&path);
+        fs::remove_file(&path).ok();
+        assert!(matches!(typo, Err(ManifestError::Toml { .. })));
+
+        let path = write_manifest("address", "[[chain]]\nchain_id = 324\nadmin = \"0x01\"\n");
+        let invalid = load_manifest(&path);
+        fs::remove_file(&path).ok();
+        assert!(matches!(invalid, Err(ManifestError::Toml { .. })));
+
+        let path = write_manifest(
+            "version",
+            "[[chain]]\nchain_id = 324\nprotocol_version = \"0.29\"\n",
+        );
+        let invalid = load_manifest(&path);
+        fs::remove_file(&path).ok();
+        assert!(matches!(invalid, Err(ManifestError::Toml { .. })));
+
+        let path = write_manifest(
+            "duplicate",
+            "[[chain]]\nchain_id = 324\n[[chain]]\nchain_id = 324\n",
+        );
+        let duplicate = load_manifest(&path);
+        fs::remove_file(&path).ok();
+        assert!(matches!(duplicate, Err(ManifestError::DuplicateChain(324))));
+    }
+
+    #[test]
+    fn reports_only_mismatched_listed_fields() {
+        let expected = ExpectedChain {
+            chain_id: 324,
+            ctm: Some(address!("0x000000000000000000000000000000000000000a")),
+            admin_owner: Some(address!("0x0000000000000000000000000000000000000005")),
+            timelock_validators: Some(vec![
+                address!("0x0000000000000000000000000000000000000012"),
+                address!("0x0000000000000000000000000000000000000011"),
+            ]),
+            multisig_signing_threshold: Some(2),
+            ..ExpectedChain::default()
+        };
+
+        let (checked, violations) = verify_chain(&expected, &summary());
+
+        assert_eq!(checked, 4);
+        assert_eq!(
+            violations,
+            vec![
+                ManifestViolation {
+                    chain_id: 324,
+                    field: "admin_owner".to_string(),
+                    expected: "0x0000000000000000000000000000000000000005".to_string(),
+                    actual: Some("0x0000000000000000000000000000000000000004".to_string()),
+                },
+                ManifestViolation {
+                    chain_id: 324,
+                    field: "multisig_signing_threshold".to_string(),
+                    expected: "2".to_string(),
+                    actual: None,
+                },
+            ]
+        );
+    }
+}
@@ -1,42 +1,583 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::Diagnostic;
+
+/// Protocol version as `major.minor.patch`; orders numerically and
+/// serializes as the dotted string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct ProtocolSemver {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ProtocolSemver {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for ProtocolSemver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for ProtocolSemver {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid protocol version `{value}` (expected major.minor.patch)");
+        let mut parts = value.trim().trim_start_matches('v').split('.');
+        let mut next = || {
+            parts
+                .next()
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or_else(invalid)
+        };
+        let version = Self::new(next()?, next()?, next()?);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(version)
+    }
+}
+
+impl From<ProtocolSemver> for String {
+    fn from(version: ProtocolSemver) -> Self {
+        version.to_string()
+    }
+}
+
+impl TryFrom<String> for ProtocolSemver {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChainCtm {
     pub chain_id: u64,
-    pub ctm: String,
+    pub ctm: Address,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CtmSummary {
-    pub address: String,
-    pub protocol_version: Option<String>,
+    pub address: Address,
+    pub protocol_version: Option<ProtocolSemver>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelockValidator {
+    pub address: Address,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaseToken {
+    pub asset_id: Option<String>,
+    pub address: Option<Address>,
+    pub origin_chain_id: Option<u64>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub gas_price_multiplier_nominator: Option<u128>,
+    pub gas_price_multiplier_denominator: Option<u128>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataAvailability {
+    pub pricing_mode: Option<String>,
+    pub l1_validator: Option<Address>,
+    pub l2_validator: Option<Address>,
+    pub classification: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainDataAvailability {
+    pub chain_id: u64,
+    pub data_availability: DataAvailability,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementLayer {
+    pub l1_chain_id: Option<u64>,
+    pub settlement_chain_id: Option<u64>,
+    pub settlement_layer_whitelisted: Option<bool>,
+    pub diamond_settlement_layer: Option<Address>,
+    pub is_settlement_layer: Option<bool>,
+    pub migrated: Option<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeParams {
+    pub pubdata_pricing_mode: String,
+    pub batch_overhead_l1_gas: u32,
+    pub max_pubdata_per_batch: u32,
+    pub max_l2_gas_per_batch: u32,
+    pub priority_tx_max_pubdata: u32,
+    pub minimal_l2_gas_price: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChainSummary {
     pub chain_id: u64,
-    pub ctm: Option<String>,
-    pub validator_timelock: Option<String>,
-    pub validator_timelock_owner: Option<String>,
-    pub chain_contract: Option<String>,
-    pub admin: Option<String>,
-    pub admin_owner: Option<String>,
-    pub protocol_version: Option<String>,
+    pub ctm: Option<Address>,
+    pub validator_timelock: Option<Address>,
+    pub validator_timelock_owner: Option<Address>,
+    /// Pending `Ownable2Step` owner awaiting `acceptOwnership()`.
+    pub validator_timelock_pending_owner: Option<Address>,
+    pub validator_timelock_execution_delay: Option<u64>,
+    pub timelock_validators: Option<Vec<TimelockValidator>>,
+    pub chain_contract: Option<Address>,
+    pub admin: Option<Address>,
+    pub admin_owner: Option<Address>,
+    pub admin_pending_owner: Option<Address>,
+    pub protocol_version: Option<ProtocolSemver>,
+    pub base_token: BaseToken,
+    pub data_availability: DataAvailability,
+    pub settlement: SettlementLayer,
+    pub fee_params: Option<FeeParams>,
+    pub priority_tx_max_gas_limit: Option<u64>,
+    /// Raw `getTransactionFilterer()` result; the zero address means none is set.
+    pub transaction_filterer: Option<Address>,
     pub multisig_signing_set_mode: Option<String>,
     pub multisig_signing_threshold: Option<u64>,
-    pub multisig_validators: Option<Vec<String>>,
+    pub multisig_validators: Option<Vec<Address>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// `ChainSummary` fields addressable by name, from audit rules and `history`.
+pub const CHAIN_FIELDS: [&str; 19] = [
+    "ctm",
+    "chain_contract",
+    "validator_timelock",
+    "validator_timelock_owner",
+    "validator_timelock_pending_owner",
+    "validator_timelock_execution_delay",
+    "timelock_validator_count",
+    "admin",
+    "admin_owner",
+    "admin_pending_owner",
+    "protocol_version",
+    "base_token",
+    "l1_da_validator",
+    "l2_da_validator",
+    "settlement_chain_id",
+    "transaction_filterer",
+    "priority_tx_max_gas_limit",
+    "multisig_signing_threshold",
+    "multisig_validator_count",
+];
+
+impl ChainSummary {
+    /// `field` rendered as text, or `None` when unset or not a field name.
+    pub fn field(&self, field: &str) -> Option<String> {
+        if let Some(address) = self.address_field(field) {
+            return Some(address.to_string());
+        }
+        match field {
+            "validator_timelock_execution_delay" => self
+                .validator_timelock_execution_delay
+                .map(|delay| delay.to_string()),
+            "timelock_validator_count" => self
+                .timelock_validators
+                .as_ref()
+                .map(|validators| validators.len().to_string()),
+            "protocol_version" => self.protocol_version.map(|version| version.to_string()),
+            "settlement_chain_id" => self.settlement.settlement_chain_id.map(|id| id.to_string()),
+            "priority_tx_max_gas_limit" => self
+                .priority_tx_max_gas_limit
+                .map(|limit| limit.to_string()),
+            "multisig_signing_threshold" => self
+                .multisig_signing_threshold
+                .map(|threshold| threshold.to_string()),
+            "multisig_validator_count" => self
+                .multisig_validators
+                .as_ref()
+                .map(|validators| validators.len().to_string()),
+            _ => None,
+        }
+    }
+
+    /// The address held by an address-valued field, if `field` is one.
+    pub fn address_field(&self, field: &str) -> Option<Address> {
+        match field {
+            "ctm" => self.ctm,
+            "chain_contract" => self.chain_contract,
+            "validator_timelock" => self.validator_timelock,
+            "validator_timelock_owner" => self.validator_timelock_owner,
+            "validator_timelock_pending_owner" => self.validator_timelock_pending_owner,
+            "admin" => self.admin,
+            "admin_owner" => self.admin_owner,
+            "admin_pending_owner" => self.admin_pending_owner,
+            "base_token" => self.base_token.address,
+            "l1_da_validator" => self.data_availability.l1_validator,
+            "l2_da_validator" => self.data_availability.l2_validator,
+            "transaction_filterer" => self.transaction_filterer,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TopologySnapshot {
-    pub bridgehub: String,
+    pub bridgehub: Address,
     pub chain_ids: Vec<u64>,
     pub chain_ctms: Vec<ChainCtm>,
     pub ctms: Vec<CtmSummary>,
-    pub warnings: Vec<String>,
+    pub chain_data_availability: Vec<ChainDataAvailability>,
+    pub history: Option<BridgehubHistory>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainRegistration {
+    pub chain_id: u64,
+    pub ctm: Address,
+    pub chain_governance: Address,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    /// Registered at some point but absent from the current chain set.
+    pub removed: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CtmRegistryEvent {
+    pub ctm: Address,
+    pub added: bool,
+    pub block_number: u64,
+    pub transaction_hash: String,
+}
+
+/// Bridgehub registry events indexed over `from_block..=to_block`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgehubHistory {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub chain_registrations: Vec<ChainRegistration>,
+    pub ctm_events: Vec<CtmRegistryEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChainInspection {
-    pub bridgehub: String,
+    pub bridgehub: Address,
     pub chain: ChainSummary,
-    pub warnings: Vec<String>,
+    /// The same chain as seen from its gateway's Bridgehub, when followed.
+    pub gateway: Option<Box<ChainInspection>>,
+    /// Control-change events across the discovered contracts, oldest first.
+    pub timeline: Option<Vec<TimelineEntry>>,
+    /// What the chain's own node reports, when an L2 RPC was given.
+    pub l2: Option<L2Inspection>,
+    pub warnings: Vec<Diagnostic>,
+    /// Summary fields whose reads failed, as opposed to being unset on chain.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub unresolved: BTreeSet<String>,
+}
+
+/// Chain state read from the chain's L2 node (`eth_chainId` and `zks_*`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L2Inspection {
+    pub chain_id: Option<u64>,
+    pub l1_chain_id: Option<u64>,
+    pub bridgehub: Option<Address>,
+    /// The chain diamond proxy on L1 (`zks_getMainContract`).
+    pub main_contract: Option<Address>,
+    pub base_token_l1_address: Option<Address>,
+    /// Bridge addresses keyed by the field names of `zks_getBridgeContracts`.
+    pub bridge_contracts: Option<BTreeMap<String, Address>>,
+    pub latest_l1_batch: Option<u64>,
+    /// How far the settlement layer trails the latest sealed batch.
+    pub batch_progress: Option<BatchProgress>,
+}
+
+/// Batch counters of the chain diamond on its settlement layer, compared
+/// with the latest batch sealed by the L2.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchProgress {
+    pub latest_sealed: u64,
+    pub committed: u64,
+    pub proven: u64,
+    pub executed: u64,
+    pub commit_lag: BatchLag,
+    pub proof_lag: BatchLag,
+    pub execution_lag: BatchLag,
+}
+
+/// Distance from the latest sealed batch to a pipeline stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchLag {
+    pub batches: u64,
+    /// Seconds between the two batches' timestamps, when both were resolved.
+    pub seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub block_number: u64,
+    pub log_index: u64,
+    pub transaction_hash: String,
+    /// Role of the emitting contract in the chain, e.g. `chain admin`.
+    pub contract: String,
+    pub address: Address,
+    pub event: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetChange {
+    pub facet: Address,
+    /// `add`, `replace` or `remove`.
+    pub action: String,
+    pub selectors: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeEvent {
+    pub block_number: u64,
+    pub log_index: u64,
+    pub transaction_hash: String,
+    /// `chain diamond` for applied upgrades, `ctm` for published ones.
+    pub source: String,
+    pub event: String,
+    pub previous_protocol_version: Option<ProtocolSemver>,
+    pub protocol_version: Option<ProtocolSemver>,
+    pub facet_changes: Vec<FacetChange>,
+    pub init_address: Option<Address>,
+    /// keccak256 of the initializer calldata.
+    pub init_calldata_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeHistory {
+    pub bridgehub: Address,
+    pub chain_id: u64,
+    pub ctm: Option<Address>,
+    pub chain_contract: Option<Address>,
+    pub current_protocol_version: Option<ProtocolSemver>,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub events: Vec<UpgradeEvent>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldBisection {
+    pub bridgehub: Address,
+    pub chain_id: u64,
+    pub field: String,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// `None` means the field could not be read at that block (not set yet).
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+    pub changed_at_block: Option<u64>,
+    /// Value one block before `changed_at_block`.
+    pub previous_value: Option<String>,
+    pub probes: usize,
+}
+
+/// How a chain field evolved across the snapshots of a `--db` store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldHistory {
+    pub chain_id: u64,
+    pub field: String,
+    /// Runs of consecutive snapshots holding the same value, oldest first.
+    pub entries: Vec<FieldHistoryEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldHistoryEntry {
+    /// `None` means the field could not be resolved in those snapshots.
+    pub value: Option<String>,
+    /// Block of the first and last snapshot of the run, when recorded.
+    pub first_block: Option<u64>,
+    pub last_block: Option<u64>,
+    pub snapshots: usize,
+}
+
+/// A `scan --save` artifact: topology plus per-chain inspections.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSnapshot {
+    pub format_version: u32,
+    /// `eth_chainId` of the network the Bridgehub lives on.
+    pub network_chain_id: Option<u64>,
+    /// Latest block when the scan started.
+    pub block_number: Option<u64>,
+    pub saved_at_unix: u64,
+    pub topology: TopologySnapshot,
+    pub inspections: Vec<ChainInspection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotChange {
+    /// `None` for topology-level changes such as CTM protocol bumps.
+    pub chain_id: Option<u64>,
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub security_relevant: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub old_block_number: Option<u64>,
+    pub new_block_number: Option<u64>,
+    pub changes: Vec<SnapshotChange>,
+}
+
+impl SnapshotDiff {
+    pub fn has_security_changes(&self) -> bool {
+        self.changes.iter().any(|change| change.security_relevant)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchEventKind {
+    NewChain,
+    ChainRemoved,
+    CtmChanged,
+    AdminChanged,
+    OwnerChanged,
+    PendingOwnerAppeared,
+    PendingOwnerCleared,
+    ValidatorSetChanged,
+    ProtocolBumped,
+    ConfigChanged,
+}
+
+/// One change observed by `watch`, emitted as an NDJSON line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub kind: WatchEventKind,
+    /// Latest block when the round that observed the change started.
+    pub block_number: Option<u64>,
+    pub chain_id: Option<u64>,
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub security_relevant: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestViolation {
+    pub chain_id: u64,
+    pub field: String,
+    pub expected: String,
+    /// `None` when the field could not be resolved on-chain.
+    pub actual: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub bridgehub: Address,
+    pub chains_checked: Vec<u64>,
+    pub fields_checked: usize,
+    pub violations: Vec<ManifestViolation>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: [Self; 5] = [
+        Self::Info,
+        Self::Low,
+        Self::Medium,
+        Self::High,
+        Self::Critical,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.name() == value)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown severity `{value}` (expected one of: {names})")
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub rule: String,
+    pub severity: Severity,
+    /// `None` for Bridgehub-wide findings.
+    pub chain_id: Option<u64>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub bridgehub: Address,
+    pub chains_audited: Vec<u64>,
+    /// Most severe first.
+    pub findings: Vec<AuditFinding>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_semver_parses_orders_and_round_trips() {
+        let v28: ProtocolSemver = "0.28.1".parse().expect("version should parse");
+        let v29: ProtocolSemver = "v0.29.0".parse().expect("version should parse");
+        assert!(v28 < v29);
+        assert!(ProtocolSemver::new(0, 9, 0) < ProtocolSemver::new(0, 10, 0));
+        assert_eq!(v29.to_string(), "0.29.0");
+        assert!("29".parse::<ProtocolSemver>().is_err());
+        assert!("0.29.0.1".parse::<ProtocolSemver>().is_err());
+
+        let json = serde_json::to_string(&v29).expect("version should serialize");
+        assert_eq!(json, "\"0.29.0\"");
+        assert_eq!(
+            serde_json::from_str::<ProtocolSemver>(&json).expect("version should deserialize"),
+            v29
+        );
+    }
 }
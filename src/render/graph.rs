@@ -0,0 +1,380 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy_primitives::Address;
+
+use crate::model::{ChainInspection, TopologySnapshot};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKind {
+    Bridgehub,
+    Ctm,
+    Chain,
+    Diamond,
+    Admin,
+    Timelock,
+    Owner,
+}
+
+impl NodeKind {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Bridgehub => "Bridgehub",
+            Self::Ctm => "CTM",
+            Self::Chain => "chain",
+            Self::Diamond => "diamond",
+            Self::Admin => "chain admin",
+            Self::Timelock => "validator timelock",
+            Self::Owner => "owner",
+        }
+    }
+
+    fn dot_shape(self) -> &'static str {
+        match self {
+            Self::Bridgehub => "doubleoctagon",
+            Self::Ctm => "box3d",
+            Self::Chain => "ellipse",
+            Self::Diamond => "diamond",
+            Self::Admin => "box",
+            Self::Timelock => "hexagon",
+            Self::Owner => "oval",
+        }
+    }
+
+    fn mermaid_brackets(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Bridgehub => ("{{", "}}"),
+            Self::Ctm => ("[[", "]]"),
+            Self::Chain => ("([", "])"),
+            Self::Diamond => ("{", "}"),
+            Self::Admin => ("[", "]"),
+            Self::Timelock => ("[/", "/]"),
+            Self::Owner => ("((", "))"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeId {
+    Chain(u64),
+    Address(Address),
+}
+
+impl NodeId {
+    fn key(&self) -> String {
+        match self {
+            Self::Chain(chain_id) => format!("chain_{chain_id}"),
+            Self::Address(address) => format!("addr_{}", hex::encode(address)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    lines: Vec<String>,
+    warned: bool,
+}
+
+/// Contract graph from the Bridgehub down to chain owners. Contracts shared
+/// between chains (a common timelock or owner) become a single node.
+#[derive(Debug, Default)]
+struct Graph {
+    order: Vec<NodeId>,
+    nodes: BTreeMap<NodeId, Node>,
+    edges: BTreeSet<(NodeId, NodeId, &'static str)>,
+}
+
+impl Graph {
+    fn build(snapshot: &TopologySnapshot, inspections: &[ChainInspection]) -> Self {
+        let mut graph = Self::default();
+        let bridgehub = NodeId::Address(snapshot.bridgehub);
+        graph.node(bridgehub.clone(), NodeKind::Bridgehub);
+
+        for ctm in &snapshot.ctms {
+            let id = NodeId::Address(ctm.address);
+            graph.node(id.clone(), NodeKind::Ctm);
+            if let Some(version) = ctm.protocol_version {
+                graph.annotate(&id, format!("v{version}"));
+            }
+            graph.edge(&bridgehub, &id, "chainTypeManager");
+        }
+        for entry in &snapshot.chain_ctms {
+            let chain = NodeId::Chain(entry.chain_id);
+            graph.node(chain.clone(), NodeKind::Chain);
+            graph.node(NodeId::Address(entry.ctm), NodeKind::Ctm);
+            graph.edge(&NodeId::Address(entry.ctm), &chain, "chainTypeManager");
+        }
+
+        for inspection in inspections {
+            let summary = &inspection.chain;
+            let chain = NodeId::Chain(summary.chain_id);
+            graph.node(chain.clone(), NodeKind::Chain);
+            if let Some(ctm) = summary.ctm {
+                graph.node(NodeId::Address(ctm), NodeKind::Ctm);
+                graph.edge(&NodeId::Address(ctm), &chain, "chainTypeManager");
+            }
+            if let Some(version) = summary.protocol_version {
+                graph.annotate(&chain, format!("v{version}"));
+            }
+
+            for (contract, kind, label, owner, pending_owner) in [
+                (
+                    summary.chain_contract,
+                    NodeKind::Diamond,
+                    "getZKChain",
+                    None,
+                    None,
+                ),
+                (
+                    summary.admin,
+                    NodeKind::Admin,
+                    "getChainAdmin",
+                    summary.admin_owner,
+                    summary.admin_pending_owner,
+                ),
+                (
+                    summary.validator_timelock,
+                    NodeKind::Timelock,
+                    "validatorTimelock",
+                    summary.validator_timelock_owner,
+                    summary.validator_timelock_pending_owner,
+                ),
+            ] {
+                let Some(contract) = contract.filter(|address| !address.is_zero()) else {
+                    continue;
+                };
+                let contract = NodeId::Address(contract);
+                graph.node(contract.clone(), kind);
+                graph.edge(&chain, &contract, label);
+                for (owner, label) in [(owner, "owner()"), (pending_owner, "pendingOwner()")] {
+                    if let Some(owner) = owner.filter(|address| !address.is_zero()) {
+                        let owner = NodeId::Address(owner);
+                        graph.node(owner.clone(), NodeKind::Owner);
+                        graph.edge(&contract, &owner, label);
+                    }
+                }
+            }
+        }
+
+        let warnings = snapshot.warnings.iter().chain(
+            inspections
+                .iter()
+                .flat_map(|inspection| &inspection.warnings),
+        );
+        for warning in warnings {
+            let id = match (warning.contract, warning.chain_id) {
+                (Some(contract), _) => NodeId::Address(contract),
+                (None, Some(chain_id)) => NodeId::Chain(chain_id),
+                (None, None) => continue,
+            };
+            if let Some(node) = graph.nodes.get_mut(&id) {
+                node.warned = true;
+            }
+        }
+        graph
+    }
+
+    /// Adds a node unless the address is already present under another role.
+    fn node(&mut self, id: NodeId, kind: NodeKind) {
+        if self.nodes.contains_key(&id) {
+            return;
+        }
+        let name = match &id {
+            NodeId::Chain(chain_id) => format!("chain {chain_id}"),
+            NodeId::Address(address) => format!("{}\n{address}", kind.title()),
+        };
+        self.order.push(id.clone());
+        self.nodes.insert(
+            id,
+            Node {
+                kind,
+                lines: vec![name],
+                warned: false,
+            },
+        );
+    }
+
+    fn annotate(&mut self, id: &NodeId, line: String) {
+        if let Some(node) = self.nodes.get_mut(id)
+            && !node.lines.contains(&line)
+        {
+            node.lines.push(line);
+        }
+    }
+
+    fn edge(&mut self, from: &NodeId, to: &NodeId, label: &'static str) {
+        self.edges.insert((from.clone(), to.clone(), label));
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (&NodeId, &Node)> {
+        self.order.iter().map(|id| (id, &self.nodes[id]))
+    }
+}
+
+/// Renders the topology and chain inspections as a Graphviz digraph.
+pub fn render_dot(snapshot: &TopologySnapshot, inspections: &[ChainInspection]) -> String {
+    let graph = Graph::build(snapshot, inspections);
+    let mut lines = vec![
+        "digraph mercator {".to_string(),
+        "  rankdir=LR;".to_string(),
+        "  node [fontname=\"monospace\"];".to_string(),
+    ];
+    for (id, node) in graph.nodes() {
+        let label = node
+            .lines
+            .iter()
+            .map(|line| line.replace('\n', "\\n"))
+            .collect::<Vec<_>>()
+            .join("\\n");
+        let style = if node.warned {
+            ", color=red, fontcolor=red"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "  {} [label=\"{label}\", shape={}{style}];",
+            id.key(),
+            node.kind.dot_shape()
+        ));
+    }
+    for (from, to, label) in &graph.edges {
+        lines.push(format!(
+            "  {} -> {} [label=\"{label}\"];",
+            from.key(),
+            to.key()
+        ));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+/// Renders the topology and chain inspections as a Mermaid flowchart.
+pub fn render_mermaid(snapshot: &TopologySnapshot, inspections: &[ChainInspection]) -> String {
+    let graph = Graph::build(snapshot, inspections);
+    let mut lines = vec!["flowchart LR".to_string()];
+    for (id, node) in graph.nodes() {
+        let label = node
+            .lines
+            .iter()
+            .map(|line| line.replace('\n', "<br/>"))
+            .collect::<Vec<_>>()
+            .join("<br/>");
+        let (open, close) = node.kind.mermaid_brackets();
+        lines.push(format!("  {}{open}\"{label}\"{close}", id.key()));
+    }
+    for (from, to, label) in &graph.edges {
+        lines.push(format!("  {} -->|\"{label}\"| {}", from.key(), to.key()));
+    }
+    let warned = graph
+        .nodes()
+        .filter(|(_, node)| node.warned)
+        .map(|(id, _)| id.key())
+        .collect::<Vec<_>>();
+    if !warned.is_empty() {
+        lines.push("  classDef warning stroke:#d00,stroke-width:2px,color:#d00;".to_string());
+        lines.push(format!("  class {} warning;", warned.join(",")));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::diagnostic::{Diagnostic, DiagnosticCode};
+    use crate::model::{ChainCtm, ChainSummary, CtmSummary, ProtocolSemver};
+
+    fn fixture() -> (TopologySnapshot, Vec<ChainInspection>) {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let ctm = address!("0x000000000000000000000000000000000000000c");
+        let snapshot = TopologySnapshot {
+            bridgehub,
+            chain_ids: vec![324],
+            chain_ctms: vec![ChainCtm { chain_id: 324, ctm }],
+            ctms: vec![CtmSummary {
+                address: ctm,
+                protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+            }],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        };
+        let inspection = ChainInspection {
+            bridgehub,
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(ctm),
+                validator_timelock: Some(address!("0x00000000000000000000000000000000000000e1")),
+                validator_timelock_owner: Some(address!(
+                    "0x00000000000000000000000000000000000000f0"
+                )),
+                chain_contract: Some(address!("0x00000000000000000000000000000000000000d1")),
+                admin: Some(address!("0x00000000000000000000000000000000000000a1")),
+                admin_owner: Some(address!("0x00000000000000000000000000000000000000f0")),
+                protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![
+                Diagnostic::new(DiagnosticCode::PendingOwner, "pending owner")
+                    .chain(324)
+                    .contract(address!("0x00000000000000000000000000000000000000a1")),
+            ],
+            unresolved: Default::default(),
+        };
+        (snapshot, vec![inspection])
+    }
+
+    #[test]
+    fn renders_dot_graph_with_shared_owner_and_warning_styling() {
+        let (snapshot, inspections) = fixture();
+        let output = render_dot(&snapshot, &inspections);
+
+        assert!(output.starts_with("digraph mercator {"));
+        assert!(output.contains(
+            "  addr_0000000000000000000000000000000000000001 [label=\"Bridgehub\\n0x0000000000000000000000000000000000000001\", shape=doubleoctagon];"
+        ));
+        assert!(output.contains("  chain_324 [label=\"chain 324\\nv0.28.0\", shape=ellipse];"));
+        assert!(output.contains(
+            "  addr_00000000000000000000000000000000000000a1 [label=\"chain admin\\n0x00000000000000000000000000000000000000A1\", shape=box, color=red, fontcolor=red];"
+        ));
+        assert!(output.contains(
+            "  addr_000000000000000000000000000000000000000c -> chain_324 [label=\"chainTypeManager\"];"
+        ));
+        assert!(output.contains(
+            "  chain_324 -> addr_00000000000000000000000000000000000000a1 [label=\"getChainAdmin\"];"
+        ));
+        assert_eq!(
+            output
+                .matches("-> addr_00000000000000000000000000000000000000f0 [label=\"owner()\"]")
+                .count(),
+            2
+        );
+        assert_eq!(
+            output
+                .matches("\n  addr_00000000000000000000000000000000000000f0 [label=")
+                .count(),
+            1
+        );
+        assert!(output.ends_with('}'));
+    }
+
+    #[test]
+    fn renders_mermaid_flowchart_with_warning_class() {
+        let (snapshot, inspections) = fixture();
+        let output = render_mermaid(&snapshot, &inspections);
+
+        assert!(output.starts_with("flowchart LR\n"));
+        assert!(output.contains(
+            "  addr_00000000000000000000000000000000000000d1{\"diamond<br/>0x00000000000000000000000000000000000000D1\"}"
+        ));
+        assert!(output.contains("  chain_324([\"chain 324<br/>v0.28.0\"])"));
+        assert!(output.contains(
+            "  addr_00000000000000000000000000000000000000a1 -->|\"owner()\"| addr_00000000000000000000000000000000000000f0"
+        ));
+        assert!(output.ends_with("  class addr_00000000000000000000000000000000000000a1 warning;"));
+    }
+}
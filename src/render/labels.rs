@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use alloy_primitives::Address;
+
+/// Operator-facing names for addresses, read from a profile's `labels_file`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressLabels(BTreeMap<Address, String>);
+
+impl AddressLabels {
+    pub fn new(labels: BTreeMap<Address, String>) -> Self {
+        Self(labels)
+    }
+
+    pub fn get(&self, address: Address) -> Option<&str> {
+        self.0.get(&address).map(String::as_str)
+    }
+
+    /// Appends ` (label)` after every labeled address in `text`, whatever its
+    /// case. Hex runs longer than an address, such as hashes, are left alone.
+    pub fn annotate(&self, text: &str) -> String {
+        if self.0.is_empty() {
+            return text.to_string();
+        }
+        let bytes = text.as_bytes();
+        let mut annotated = String::with_capacity(text.len());
+        let mut copied = 0;
+        let mut index = 0;
+        while let Some(offset) = text[index..].find("0x") {
+            let start = index + offset;
+            let end = start + 42;
+            let hex_run = bytes[start + 2..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_hexdigit())
+                .count();
+            let preceded_by_word = start > 0 && bytes[start - 1].is_ascii_alphanumeric();
+            if hex_run == 40
+                && !preceded_by_word
+                && let Some(label) = Address::from_str(&text[start..end])
+                    .ok()
+                    .and_then(|address| self.get(address))
+            {
+                annotated.push_str(&text[copied..end]);
+                annotated.push_str(&format!(" ({label})"));
+                copied = end;
+            }
+            index = start + 2 + hex_run;
+        }
+        annotated.push_str(&text[copied..]);
+        annotated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn annotates_labeled_addresses_only() {
+        let labels = AddressLabels::new(BTreeMap::from([(
+            address!("0x00000000000000000000000000000000000000a1"),
+            "Era admin".to_string(),
+        )]));
+
+        assert_eq!(
+            labels.annotate(
+                "owner 0x00000000000000000000000000000000000000A1, other 0x00000000000000000000000000000000000000a2"
+            ),
+            "owner 0x00000000000000000000000000000000000000A1 (Era admin), other 0x00000000000000000000000000000000000000a2"
+        );
+        let hash = format!(
+            "0x{}00000000000000000000000000000000000000a1",
+            "0".repeat(24)
+        );
+        assert_eq!(labels.annotate(&hash), hash);
+        assert_eq!(
+            labels.annotate("0x00000000000000000000000000000000000000a1"),
+            "0x00000000000000000000000000000000000000a1 (Era admin)"
+        );
+    }
+}
@@ -0,0 +1,1582 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use alloy_primitives::Address;
+
+use crate::model::{
+    AuditReport, ChainInspection, FieldBisection, FieldHistory, Severity, SnapshotDiff,
+    TopologySnapshot, UpgradeHistory, VerificationReport,
+};
+use crate::scanner::token::is_eth_token;
+
+pub mod graph;
+pub mod labels;
+pub mod report;
+pub mod table;
+
+pub use graph::{render_dot, render_mermaid};
+pub use labels::AddressLabels;
+pub use report::{render_html, render_markdown};
+pub use table::{ChainColumn, render_delimited};
+
+/// Output format of `scan` and `inspect`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Dot,
+    Mermaid,
+    Markdown,
+    Html,
+    Csv,
+    Tsv,
+    Json,
+}
+
+impl OutputFormat {
+    pub const ALL: [Self; 8] = [
+        Self::Text,
+        Self::Dot,
+        Self::Mermaid,
+        Self::Markdown,
+        Self::Html,
+        Self::Csv,
+        Self::Tsv,
+        Self::Json,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Dot => "dot",
+            Self::Mermaid => "mermaid",
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+        }
+    }
+
+    /// Whether the format needs every chain inspected, not just the topology.
+    pub fn needs_inspections(self) -> bool {
+        !matches!(self, Self::Text)
+    }
+
+    /// Whether the format shows multisig committer details, which only a
+    /// verbose inspection resolves.
+    pub fn needs_multisig_details(self) -> bool {
+        matches!(self, Self::Markdown | Self::Html | Self::Csv | Self::Tsv)
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == value)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown format `{value}` (expected one of: {names})")
+            })
+    }
+}
+
+/// Renders a topology and its chain inspections in `format`; `Text` gives the
+/// topology summary. `columns` selects the CSV/TSV columns, and `labels`
+/// annotates addresses in the text, Markdown and HTML formats.
+pub fn render_formatted(
+    format: OutputFormat,
+    snapshot: &TopologySnapshot,
+    inspections: &[ChainInspection],
+    columns: &[ChainColumn],
+    labels: &AddressLabels,
+) -> String {
+    match format {
+        OutputFormat::Text => render_topology(snapshot, labels),
+        OutputFormat::Dot => render_dot(snapshot, inspections),
+        OutputFormat::Mermaid => render_mermaid(snapshot, inspections),
+        OutputFormat::Markdown => render_markdown(snapshot, inspections, labels),
+        OutputFormat::Html => render_html(snapshot, inspections, labels),
+        OutputFormat::Csv => render_delimited(snapshot, inspections, columns, ','),
+        OutputFormat::Tsv => render_delimited(snapshot, inspections, columns, '\t'),
+        OutputFormat::Json => render_json(snapshot, inspections),
+    }
+}
+
+/// Renders `{"topology": ..., "chains": [...]}`, the schema shared by
+/// `--format json` and the `serve --api` endpoints.
+pub fn render_json(snapshot: &TopologySnapshot, inspections: &[ChainInspection]) -> String {
+    serde_json::json!({
+        "topology": snapshot,
+        "chains": inspections,
+    })
+    .to_string()
+}
+
+pub fn render_topology(snapshot: &TopologySnapshot, labels: &AddressLabels) -> String {
+    let mut ctm_chain_ids: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+    for entry in &snapshot.chain_ctms {
+        ctm_chain_ids
+            .entry(entry.ctm)
+            .or_default()
+            .push(entry.chain_id);
+    }
+    for chain_ids in ctm_chain_ids.values_mut() {
+        chain_ids.sort_unstable();
+    }
+
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - BridgeHub: {}", snapshot.bridgehub),
+        format!("  - Chains discovered: {}", snapshot.chain_ids.len()),
+        format!("  - CTMs discovered: {}", snapshot.ctms.len()),
+        String::new(),
+        "CTMs".to_string(),
+    ];
+
+    if snapshot.ctms.is_empty() {
+        lines.push("  - none resolved".to_string());
+    } else {
+        for ctm in &snapshot.ctms {
+            let chain_ids = ctm_chain_ids.get(&ctm.address).cloned().unwrap_or_default();
+            let chain_count = chain_ids.len();
+            let chain_ids_text = if chain_ids.is_empty() {
+                "none".to_string()
+            } else {
+                chain_ids
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            let protocol_version = format_optional(ctm.protocol_version);
+            lines.push(format!(
+                "  - {} (protocol version: {protocol_version}, chain count: {chain_count}, chains: {chain_ids_text})",
+                ctm.address
+            ));
+        }
+    }
+
+    let chain_ctm: BTreeMap<u64, Address> = snapshot
+        .chain_ctms
+        .iter()
+        .map(|entry| (entry.chain_id, entry.ctm))
+        .collect();
+    let mut ctm_da_counts: BTreeMap<String, BTreeMap<&str, usize>> = BTreeMap::new();
+    for entry in &snapshot.chain_data_availability {
+        let ctm = format_optional(chain_ctm.get(&entry.chain_id));
+        let classification = entry
+            .data_availability
+            .classification
+            .as_deref()
+            .unwrap_or("unknown");
+        *ctm_da_counts
+            .entry(ctm)
+            .or_default()
+            .entry(classification)
+            .or_default() += 1;
+    }
+
+    // Snapshots saved before the breakdown existed carry no per-chain DA.
+    if !ctm_da_counts.is_empty() {
+        lines.push(String::new());
+        lines.push("Data Availability".to_string());
+        for (ctm, counts) in ctm_da_counts {
+            let breakdown = counts
+                .iter()
+                .map(|(classification, count)| format!("{classification}: {count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("  - {ctm} ({breakdown})"));
+        }
+    }
+
+    if let Some(history) = snapshot.history.as_ref() {
+        lines.push(String::new());
+        lines.push(format!(
+            "History (blocks {}..={})",
+            history.from_block, history.to_block
+        ));
+        lines.push("  - Chain Registrations:".to_string());
+        if history.chain_registrations.is_empty() {
+            lines.push("    - none".to_string());
+        }
+        for registration in &history.chain_registrations {
+            lines.push(format!(
+                "    - chain {} at block {} (tx {}, CTM {}){}",
+                registration.chain_id,
+                registration.block_number,
+                registration.transaction_hash,
+                registration.ctm,
+                if registration.removed {
+                    " [removed]"
+                } else {
+                    ""
+                }
+            ));
+        }
+        lines.push("  - CTM Registry Events:".to_string());
+        if history.ctm_events.is_empty() {
+            lines.push("    - none".to_string());
+        }
+        for event in &history.ctm_events {
+            lines.push(format!(
+                "    - {} {} at block {} (tx {})",
+                if event.added { "added" } else { "removed" },
+                event.ctm,
+                event.block_number,
+                event.transaction_hash
+            ));
+        }
+        let removed_chain_ids = history
+            .chain_registrations
+            .iter()
+            .filter(|registration| registration.removed)
+            .map(|registration| registration.chain_id.to_string())
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "  - Removed Chains: {}",
+            if removed_chain_ids.is_empty() {
+                "none".to_string()
+            } else {
+                removed_chain_ids.join(",")
+            }
+        ));
+    }
+
+    labels.annotate(&lines.join("\n"))
+}
+
+pub fn render_chain_inspection(
+    inspection: &ChainInspection,
+    verbose: bool,
+    labels: &AddressLabels,
+) -> String {
+    let chain = &inspection.chain;
+    let ctm = format_optional(chain.ctm);
+    let validator_timelock = format_optional(chain.validator_timelock);
+    let validator_timelock_owner = format_optional(chain.validator_timelock_owner);
+    let diamond = format_optional(chain.chain_contract);
+    let admin = format_optional(chain.admin);
+    let admin_owner = format_optional(chain.admin_owner);
+    let protocol = format_optional(chain.protocol_version);
+
+    let mut lines = vec![
+        "Details".to_string(),
+        format!("  - BridgeHub: {}", inspection.bridgehub),
+        format!("  - Chain ID: {}", chain.chain_id),
+        format!("  - CTM: {ctm}"),
+        format!("  - Validator Timelock Ownable: {validator_timelock}"),
+        format!("  - Validator Timelock Owner: {validator_timelock_owner}"),
+        format!("  - Chain Diamond Proxy: {diamond}"),
+        format!("  - Protocol: {protocol}"),
+        format!("  - Chain Admin Ownable: {admin}"),
+        format!("  - Chain Admin Owner: {admin_owner}"),
+    ];
+    if let Some(pending_owner) = chain.validator_timelock_pending_owner {
+        lines.insert(
+            6,
+            format!("  - Validator Timelock Pending Owner: {pending_owner}"),
+        );
+    }
+    if let Some(pending_owner) = chain.admin_pending_owner {
+        lines.push(format!("  - Chain Admin Pending Owner: {pending_owner}"));
+    }
+
+    let execution_delay = chain
+        .validator_timelock_execution_delay
+        .map(|value| format!("{value}s"))
+        .unwrap_or_else(|| "unknown".to_string());
+    lines.push(String::new());
+    lines.push("Validators".to_string());
+    lines.push(format!("  - Execution Delay: {execution_delay}"));
+    match chain.timelock_validators.as_ref() {
+        Some(validators) => {
+            lines.push("  - Operators:".to_string());
+            if validators.is_empty() {
+                lines.push("    - none".to_string());
+            } else {
+                for validator in validators {
+                    lines.push(format!(
+                        "    - {} ({})",
+                        validator.address,
+                        validator.roles.join(", ")
+                    ));
+                }
+            }
+        }
+        None => {
+            lines.push("  - Operators: unknown".to_string());
+        }
+    }
+
+    let base_token = &chain.base_token;
+    let base_token_kind = match base_token.address {
+        Some(address) if is_eth_token(address) => "ETH",
+        Some(_) => "custom token",
+        None => "unknown",
+    };
+    let gas_price_multiplier = match (
+        base_token.gas_price_multiplier_nominator,
+        base_token.gas_price_multiplier_denominator,
+    ) {
+        (Some(nominator), Some(denominator)) => format!("{nominator}/{denominator}"),
+        _ => "unknown".to_string(),
+    };
+    lines.push(String::new());
+    lines.push("Base Token".to_string());
+    lines.push(format!("  - Kind: {base_token_kind}"));
+    lines.push(format!(
+        "  - Token: {}",
+        format_optional(base_token.address)
+    ));
+    lines.push(format!(
+        "  - Asset ID: {}",
+        base_token.asset_id.as_deref().unwrap_or("unknown")
+    ));
+    lines.push(format!(
+        "  - Name: {}",
+        base_token.name.as_deref().unwrap_or("unknown")
+    ));
+    lines.push(format!(
+        "  - Symbol: {}",
+        base_token.symbol.as_deref().unwrap_or("unknown")
+    ));
+    lines.push(format!(
+        "  - Decimals: {}",
+        base_token
+            .decimals
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ));
+    lines.push(format!(
+        "  - Origin Chain ID: {}",
+        base_token
+            .origin_chain_id
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ));
+    lines.push(format!("  - Gas Price Multiplier: {gas_price_multiplier}"));
+
+    let data_availability = &chain.data_availability;
+    lines.push(String::new());
+    lines.push("Data Availability".to_string());
+    lines.push(format!(
+        "  - Mode: {}",
+        data_availability
+            .classification
+            .as_deref()
+            .unwrap_or("unknown")
+    ));
+    lines.push(format!(
+        "  - Pubdata Pricing Mode: {}",
+        data_availability
+            .pricing_mode
+            .as_deref()
+            .unwrap_or("unknown")
+    ));
+    lines.push(format!(
+        "  - L1 DA Validator: {}",
+        format_optional(data_availability.l1_validator)
+    ));
+    lines.push(format!(
+        "  - L2 DA Validator: {}",
+        format_optional(data_availability.l2_validator)
+    ));
+
+    let settlement = &chain.settlement;
+    let settles_on = match (settlement.migrated, settlement.settlement_chain_id) {
+        (Some(false), _) => "L1".to_string(),
+        (Some(true), Some(settlement_chain_id)) => format!("gateway (chain {settlement_chain_id})"),
+        (Some(true), None) => "gateway".to_string(),
+        (None, _) => "unknown".to_string(),
+    };
+    lines.push(String::new());
+    lines.push("Settlement Layer".to_string());
+    lines.push(format!("  - Settles On: {settles_on}"));
+    lines.push(format!(
+        "  - L1 Chain ID: {}",
+        format_optional(settlement.l1_chain_id)
+    ));
+    lines.push(format!(
+        "  - Settlement Chain ID: {}",
+        format_optional(settlement.settlement_chain_id)
+    ));
+    if settlement.migrated == Some(true) {
+        lines.push(format!(
+            "  - Settlement Layer Whitelisted: {}",
+            format_yes_no(settlement.settlement_layer_whitelisted)
+        ));
+    }
+    lines.push(format!(
+        "  - Diamond Settlement Layer: {}",
+        match (settlement.diamond_settlement_layer, settlement.migrated) {
+            (Some(address), _) => address.to_string(),
+            (None, Some(_)) => "none".to_string(),
+            (None, None) => "unknown".to_string(),
+        }
+    ));
+    lines.push(format!(
+        "  - Whitelisted As Settlement Layer: {}",
+        format_yes_no(settlement.is_settlement_layer)
+    ));
+
+    lines.push(String::new());
+    lines.push("Fee Parameters".to_string());
+    match chain.fee_params.as_ref() {
+        Some(fee_params) => {
+            lines.push(format!(
+                "  - Pubdata Pricing Mode: {}",
+                fee_params.pubdata_pricing_mode
+            ));
+            lines.push(format!(
+                "  - Batch Overhead L1 Gas: {}",
+                fee_params.batch_overhead_l1_gas
+            ));
+            lines.push(format!(
+                "  - Max Pubdata Per Batch: {}",
+                fee_params.max_pubdata_per_batch
+            ));
+            lines.push(format!(
+                "  - Max L2 Gas Per Batch: {}",
+                fee_params.max_l2_gas_per_batch
+            ));
+            lines.push(format!(
+                "  - Priority Tx Max Pubdata: {}",
+                fee_params.priority_tx_max_pubdata
+            ));
+            lines.push(format!(
+                "  - Minimal L2 Gas Price: {}",
+                fee_params.minimal_l2_gas_price
+            ));
+        }
+        None => lines.push("  - Fee Params: unknown".to_string()),
+    }
+    lines.push(format!(
+        "  - Priority Tx Max Gas Limit: {}",
+        format_optional(chain.priority_tx_max_gas_limit)
+    ));
+    lines.push(format!(
+        "  - Transaction Filterer: {}",
+        match chain.transaction_filterer {
+            Some(address) if address.is_zero() => "none".to_string(),
+            Some(address) => address.to_string(),
+            None => "unknown".to_string(),
+        }
+    ));
+
+    if let Some(gateway) = inspection.gateway.as_deref() {
+        let gateway_chain = &gateway.chain;
+        lines.push(String::new());
+        lines.push("Gateway".to_string());
+        lines.push(format!("  - BridgeHub: {}", gateway.bridgehub));
+        lines.push(format!("  - CTM: {}", format_optional(gateway_chain.ctm)));
+        lines.push(format!(
+            "  - Chain Diamond Proxy: {}",
+            format_optional(gateway_chain.chain_contract)
+        ));
+        lines.push(format!(
+            "  - Protocol: {}",
+            format_optional(gateway_chain.protocol_version)
+        ));
+        lines.push(format!(
+            "  - Validator Timelock Ownable: {}",
+            format_optional(gateway_chain.validator_timelock)
+        ));
+        lines.push(format!(
+            "  - Chain Admin Ownable: {}",
+            format_optional(gateway_chain.admin)
+        ));
+        lines.push(format!(
+            "  - Chain Admin Owner: {}",
+            format_optional(gateway_chain.admin_owner)
+        ));
+    }
+
+    if let Some(l2) = inspection.l2.as_ref() {
+        lines.push(String::new());
+        lines.push("L2 Node".to_string());
+        lines.push(format!("  - Chain ID: {}", format_optional(l2.chain_id)));
+        lines.push(format!(
+            "  - L1 Chain ID: {}",
+            format_optional(l2.l1_chain_id)
+        ));
+        lines.push(format!("  - BridgeHub: {}", format_optional(l2.bridgehub)));
+        lines.push(format!(
+            "  - Main Contract: {}",
+            format_optional(l2.main_contract)
+        ));
+        lines.push(format!(
+            "  - Base Token L1 Address: {}",
+            format_optional(l2.base_token_l1_address)
+        ));
+        lines.push(format!(
+            "  - Latest L1 Batch: {}",
+            format_optional(l2.latest_l1_batch)
+        ));
+        if let Some(progress) = l2.batch_progress.as_ref() {
+            for (stage, batch, lag) in [
+                ("Committed", progress.committed, progress.commit_lag),
+                ("Proven", progress.proven, progress.proof_lag),
+                ("Executed", progress.executed, progress.execution_lag),
+            ] {
+                let behind = match lag.seconds {
+                    Some(seconds) => format!("{} batches, {seconds}s", lag.batches),
+                    None => format!("{} batches", lag.batches),
+                };
+                lines.push(format!("  - {stage} Batch: {batch} ({behind} behind)"));
+            }
+        }
+        match l2.bridge_contracts.as_ref() {
+            Some(bridges) => {
+                for (name, address) in bridges {
+                    lines.push(format!("  - Bridge {name}: {address}"));
+                }
+            }
+            None => lines.push("  - Bridges: unknown".to_string()),
+        }
+    }
+
+    if let Some(timeline) = inspection.timeline.as_ref() {
+        lines.push(String::new());
+        lines.push("Ownership Timeline".to_string());
+        if timeline.is_empty() {
+            lines.push("  - no control changes found".to_string());
+        }
+        for entry in timeline {
+            lines.push(format!(
+                "  - block {} (tx {}) {} {}: {} {}",
+                entry.block_number,
+                entry.transaction_hash,
+                entry.contract,
+                entry.address,
+                entry.event,
+                entry.description
+            ));
+        }
+    }
+
+    if verbose {
+        let signing_set_mode = chain
+            .multisig_signing_set_mode
+            .as_deref()
+            .unwrap_or("unknown");
+        let signing_threshold = chain
+            .multisig_signing_threshold
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        lines.push(String::new());
+        lines.push("Multisig Committer".to_string());
+        lines.push(format!("  - Signing Set Mode: {signing_set_mode}"));
+        lines.push(format!("  - Signing Threshold: {signing_threshold}"));
+        match chain.multisig_validators.as_ref() {
+            Some(validators) => {
+                lines.push("  - Validators:".to_string());
+                if validators.is_empty() {
+                    lines.push("    - none".to_string());
+                } else {
+                    for validator in validators {
+                        lines.push(format!("    - {validator}"));
+                    }
+                }
+            }
+            None => {
+                lines.push("  - Validators: unknown".to_string());
+            }
+        }
+    }
+
+    labels.annotate(&lines.join("\n"))
+}
+
+pub fn render_upgrade_history(history: &UpgradeHistory, verbose: bool) -> String {
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - BridgeHub: {}", history.bridgehub),
+        format!("  - Chain ID: {}", history.chain_id),
+        format!("  - CTM: {}", format_optional(history.ctm)),
+        format!(
+            "  - Chain Diamond Proxy: {}",
+            format_optional(history.chain_contract)
+        ),
+        format!(
+            "  - Protocol: {}",
+            format_optional(history.current_protocol_version)
+        ),
+        format!("  - Blocks: {}..={}", history.from_block, history.to_block),
+        String::new(),
+        "Upgrades".to_string(),
+    ];
+
+    if history.events.is_empty() {
+        lines.push("  - none found".to_string());
+    }
+    for event in &history.events {
+        let version = match (event.previous_protocol_version, event.protocol_version) {
+            (Some(previous), Some(new)) => format!("{previous} -> {new}"),
+            (None, Some(new)) => new.to_string(),
+            _ => "version unknown".to_string(),
+        };
+        lines.push(format!(
+            "  - block {} (tx {}) {} {}: {version}",
+            event.block_number, event.transaction_hash, event.source, event.event
+        ));
+        if let Some(init_address) = event.init_address {
+            lines.push(format!(
+                "    - Initializer: {init_address} (calldata hash {})",
+                event.init_calldata_hash.as_deref().unwrap_or("none")
+            ));
+        }
+        for change in &event.facet_changes {
+            if verbose {
+                lines.push(format!(
+                    "    - {} {}: {}",
+                    change.action,
+                    change.facet,
+                    change.selectors.join(",")
+                ));
+            } else {
+                lines.push(format!(
+                    "    - {} {}: {} selectors",
+                    change.action,
+                    change.facet,
+                    change.selectors.len()
+                ));
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_field_history(history: &FieldHistory) -> String {
+    let block = |block: Option<u64>| block.map_or_else(|| "unknown".to_string(), |b| b.to_string());
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - Chain ID: {}", history.chain_id),
+        format!("  - Field: {}", history.field),
+        format!(
+            "  - Snapshots: {}",
+            history
+                .entries
+                .iter()
+                .map(|entry| entry.snapshots)
+                .sum::<usize>()
+        ),
+        String::new(),
+        "History".to_string(),
+    ];
+    if history.entries.is_empty() {
+        lines.push("  - no stored snapshot includes this chain".to_string());
+    }
+    for entry in &history.entries {
+        lines.push(format!(
+            "  - Blocks {}..={}: {} ({} snapshots)",
+            block(entry.first_block),
+            block(entry.last_block),
+            entry.value.as_deref().unwrap_or("unset"),
+            entry.snapshots
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn render_field_bisection(bisection: &FieldBisection) -> String {
+    let value = |value: Option<&str>| value.unwrap_or("unset").to_string();
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - BridgeHub: {}", bisection.bridgehub),
+        format!("  - Chain ID: {}", bisection.chain_id),
+        format!("  - Field: {}", bisection.field),
+        format!(
+            "  - Blocks: {}..={} ({} probes)",
+            bisection.from_block, bisection.to_block, bisection.probes
+        ),
+        String::new(),
+        "Result".to_string(),
+        format!(
+            "  - At block {}: {}",
+            bisection.from_block,
+            value(bisection.from_value.as_deref())
+        ),
+        format!(
+            "  - At block {}: {}",
+            bisection.to_block,
+            value(bisection.to_value.as_deref())
+        ),
+    ];
+    match bisection.changed_at_block {
+        Some(block) => lines.push(format!(
+            "  - Changed At Block: {block} ({} -> {})",
+            value(bisection.previous_value.as_deref()),
+            value(bisection.to_value.as_deref())
+        )),
+        None => lines.push("  - Changed At Block: none (same value at both ends)".to_string()),
+    }
+    lines.join("\n")
+}
+
+pub fn render_snapshot_diff(diff: &SnapshotDiff) -> String {
+    let security_changes = diff
+        .changes
+        .iter()
+        .filter(|change| change.security_relevant)
+        .count();
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!(
+            "  - Blocks: {} -> {}",
+            format_optional(diff.old_block_number),
+            format_optional(diff.new_block_number)
+        ),
+        format!("  - Changes: {}", diff.changes.len()),
+        format!("  - Security-relevant: {security_changes}"),
+        String::new(),
+        "Changes".to_string(),
+    ];
+
+    if diff.changes.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    let value = |value: Option<&str>| value.unwrap_or("unset").to_string();
+    for change in &diff.changes {
+        let marker = if change.security_relevant { "!" } else { "-" };
+        let scope = match change.chain_id {
+            Some(chain_id) => format!("chain {chain_id} "),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "  {marker} {scope}{}: {} -> {}",
+            change.field,
+            value(change.old.as_deref()),
+            value(change.new.as_deref())
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn render_verification_report(report: &VerificationReport) -> String {
+    let chains = report
+        .chains_checked
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - BridgeHub: {}", report.bridgehub),
+        format!(
+            "  - Chains checked: {}",
+            if chains.is_empty() { "none" } else { &chains }
+        ),
+        format!("  - Fields checked: {}", report.fields_checked),
+        format!("  - Violations: {}", report.violations.len()),
+        String::new(),
+        "Violations".to_string(),
+    ];
+    if report.violations.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    for violation in &report.violations {
+        lines.push(format!(
+            "  - chain {} {}: expected {}, found {}",
+            violation.chain_id,
+            violation.field,
+            violation.expected,
+            violation.actual.as_deref().unwrap_or("unresolved")
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn render_audit_report(report: &AuditReport) -> String {
+    let mut counts: BTreeMap<Severity, usize> = BTreeMap::new();
+    for finding in &report.findings {
+        *counts.entry(finding.severity).or_default() += 1;
+    }
+    let breakdown = counts
+        .iter()
+        .rev()
+        .map(|(severity, count)| format!("{count} {severity}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut lines = vec![
+        "Summary".to_string(),
+        format!("  - BridgeHub: {}", report.bridgehub),
+        format!("  - Chains audited: {}", report.chains_audited.len()),
+        format!(
+            "  - Findings: {}{}",
+            report.findings.len(),
+            if breakdown.is_empty() {
+                String::new()
+            } else {
+                format!(" ({breakdown})")
+            }
+        ),
+        String::new(),
+        "Findings".to_string(),
+    ];
+    if report.findings.is_empty() {
+        lines.push("  - none".to_string());
+    }
+    for finding in &report.findings {
+        let scope = finding
+            .chain_id
+            .map(|chain_id| format!(" chain {chain_id}"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "  - [{}] {}{scope}: {}",
+            finding.severity, finding.rule, finding.message
+        ));
+    }
+    lines.join("\n")
+}
+
+fn format_optional<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn format_yes_no(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::model::{
+        AuditFinding, BaseToken, BatchLag, BatchProgress, BridgehubHistory, ChainCtm,
+        ChainDataAvailability, ChainInspection, ChainRegistration, ChainSummary, CtmRegistryEvent,
+        CtmSummary, DataAvailability, FacetChange, FeeParams, FieldHistoryEntry, L2Inspection,
+        ManifestViolation, ProtocolSemver, SettlementLayer, SnapshotChange, TimelineEntry,
+        TimelockValidator, TopologySnapshot, UpgradeEvent,
+    };
+
+    #[test]
+    fn renders_topology_snapshot() {
+        let snapshot = TopologySnapshot {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_ids: vec![324, 325],
+            chain_ctms: vec![
+                ChainCtm {
+                    chain_id: 325,
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                },
+                ChainCtm {
+                    chain_id: 324,
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                },
+            ],
+            ctms: vec![CtmSummary {
+                address: address!("0x0000000000000000000000000000000000000002"),
+                protocol_version: Some(ProtocolSemver::new(0, 17, 0)),
+            }],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        };
+
+        let output = render_topology(&snapshot, &AddressLabels::default());
+        assert!(output.contains("Summary"));
+        assert!(output.contains("BridgeHub: 0x0000000000000000000000000000000000000001"));
+        assert!(output.contains("Chains discovered: 2"));
+        assert!(output.contains("CTMs discovered: 1"));
+        assert!(output.contains(
+            "0x0000000000000000000000000000000000000002 (protocol version: 0.17.0, chain count: 2, chains: 324,325)"
+        ));
+        assert!(!output.contains("Details"));
+        assert!(!output.contains("Warnings"));
+        assert!(!output.contains("Data Availability"));
+        assert!(!output.contains("History"));
+    }
+
+    #[test]
+    fn renders_topology_history() {
+        let snapshot = TopologySnapshot {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_ids: vec![324],
+            chain_ctms: vec![],
+            ctms: vec![],
+            chain_data_availability: vec![],
+            history: Some(BridgehubHistory {
+                from_block: 100,
+                to_block: 200,
+                chain_registrations: vec![
+                    ChainRegistration {
+                        chain_id: 324,
+                        ctm: address!("0x0000000000000000000000000000000000000002"),
+                        chain_governance: address!("0x0000000000000000000000000000000000000003"),
+                        block_number: 120,
+                        transaction_hash: "0xabc".to_string(),
+                        removed: false,
+                    },
+                    ChainRegistration {
+                        chain_id: 325,
+                        ctm: address!("0x0000000000000000000000000000000000000002"),
+                        chain_governance: address!("0x0000000000000000000000000000000000000003"),
+                        block_number: 150,
+                        transaction_hash: "0xdef".to_string(),
+                        removed: true,
+                    },
+                ],
+                ctm_events: vec![CtmRegistryEvent {
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                    added: true,
+                    block_number: 110,
+                    transaction_hash: "0x123".to_string(),
+                }],
+            }),
+            warnings: vec![],
+        };
+
+        let output = render_topology(&snapshot, &AddressLabels::default());
+        assert!(output.contains("History (blocks 100..=200)"));
+        assert!(output.contains(
+            "    - chain 325 at block 150 (tx 0xdef, CTM 0x0000000000000000000000000000000000000002) [removed]"
+        ));
+        assert!(output.contains(
+            "    - added 0x0000000000000000000000000000000000000002 at block 110 (tx 0x123)"
+        ));
+        assert!(output.contains("  - Removed Chains: 325"));
+    }
+
+    #[test]
+    fn renders_topology_data_availability_breakdown() {
+        let rollup = DataAvailability {
+            classification: Some("rollup (calldata/blobs)".to_string()),
+            ..DataAvailability::default()
+        };
+        let snapshot = TopologySnapshot {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_ids: vec![324, 325, 326],
+            chain_ctms: vec![
+                ChainCtm {
+                    chain_id: 324,
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                },
+                ChainCtm {
+                    chain_id: 325,
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                },
+                ChainCtm {
+                    chain_id: 326,
+                    ctm: address!("0x0000000000000000000000000000000000000002"),
+                },
+            ],
+            ctms: vec![CtmSummary {
+                address: address!("0x0000000000000000000000000000000000000002"),
+                protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+            }],
+            chain_data_availability: vec![
+                ChainDataAvailability {
+                    chain_id: 324,
+                    data_availability: rollup.clone(),
+                },
+                ChainDataAvailability {
+                    chain_id: 325,
+                    data_availability: rollup,
+                },
+                ChainDataAvailability {
+                    chain_id: 326,
+                    data_availability: DataAvailability::default(),
+                },
+            ],
+            history: None,
+            warnings: vec![],
+        };
+
+        let output = render_topology(&snapshot, &AddressLabels::default());
+        assert!(output.contains(
+            "Data Availability\n  - 0x0000000000000000000000000000000000000002 (rollup (calldata/blobs): 2, unknown: 1)"
+        ));
+    }
+
+    #[test]
+    fn renders_chain_inspection() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(address!("0x0000000000000000000000000000000000000002")),
+                validator_timelock: Some(address!("0x0000000000000000000000000000000000000006")),
+                validator_timelock_owner: Some(address!(
+                    "0x0000000000000000000000000000000000000008"
+                )),
+                chain_contract: Some(address!("0x0000000000000000000000000000000000000003")),
+                admin: Some(address!("0x0000000000000000000000000000000000000004")),
+                admin_owner: Some(address!("0x0000000000000000000000000000000000000007")),
+                protocol_version: Some(ProtocolSemver::new(17, 0, 0)),
+                multisig_signing_set_mode: Some("shared".to_string()),
+                multisig_signing_threshold: Some(3),
+                multisig_validators: Some(vec![
+                    address!("0x0000000000000000000000000000000000000011"),
+                    address!("0x0000000000000000000000000000000000000012"),
+                ]),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains("Details"));
+        assert!(output.contains("  - BridgeHub: 0x0000000000000000000000000000000000000001"));
+        assert!(output.contains("  - Chain ID: 324"));
+        assert!(output.contains("CTM: 0x0000000000000000000000000000000000000002"));
+        assert!(
+            output
+                .contains("Validator Timelock Ownable: 0x0000000000000000000000000000000000000006")
+        );
+        assert!(
+            output.contains("Validator Timelock Owner: 0x0000000000000000000000000000000000000008")
+        );
+        assert!(output.contains("Chain Admin Ownable: 0x0000000000000000000000000000000000000004"));
+        assert!(output.contains("Chain Admin Owner: 0x0000000000000000000000000000000000000007"));
+        assert!(!output.contains("Multisig Committer"));
+        assert!(!output.contains("Warnings"));
+        assert!(!output.contains("Verifier:"));
+        assert!(output.contains("Execution Delay: unknown"));
+        assert!(output.contains("Operators: unknown"));
+    }
+
+    #[test]
+    fn renders_validator_timelock_roles() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(address!("0x0000000000000000000000000000000000000002")),
+                validator_timelock: Some(address!("0x0000000000000000000000000000000000000006")),
+                validator_timelock_execution_delay: Some(10800),
+                timelock_validators: Some(vec![TimelockValidator {
+                    address: address!("0x0000000000000000000000000000000000000011"),
+                    roles: vec!["committer".to_string(), "prover".to_string()],
+                }]),
+                chain_contract: Some(address!("0x0000000000000000000000000000000000000003")),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains("Validators\n  - Execution Delay: 10800s"));
+        assert!(
+            output.contains("    - 0x0000000000000000000000000000000000000011 (committer, prover)")
+        );
+    }
+
+    #[test]
+    fn renders_verbose_multisig_commit_details() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(address!("0x0000000000000000000000000000000000000002")),
+                validator_timelock: Some(address!("0x0000000000000000000000000000000000000006")),
+                validator_timelock_owner: Some(address!(
+                    "0x0000000000000000000000000000000000000008"
+                )),
+                chain_contract: Some(address!("0x0000000000000000000000000000000000000003")),
+                admin: Some(address!("0x0000000000000000000000000000000000000004")),
+                admin_owner: Some(address!("0x0000000000000000000000000000000000000007")),
+                protocol_version: Some(ProtocolSemver::new(17, 0, 0)),
+                multisig_signing_set_mode: Some("custom".to_string()),
+                multisig_signing_threshold: Some(2),
+                multisig_validators: Some(vec![
+                    address!("0x0000000000000000000000000000000000000011"),
+                    address!("0x0000000000000000000000000000000000000012"),
+                ]),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, true, &AddressLabels::default());
+        assert!(output.contains("Multisig Committer"));
+        assert!(output.contains("Signing Set Mode: custom"));
+        assert!(output.contains("Signing Threshold: 2"));
+        assert!(output.contains("  - Validators:"));
+        assert!(output.contains("    - 0x0000000000000000000000000000000000000011"));
+        assert!(output.contains("    - 0x0000000000000000000000000000000000000012"));
+    }
+
+    #[test]
+    fn renders_base_token_section() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                base_token: BaseToken {
+                    asset_id: Some(
+                        "0x1111111111111111111111111111111111111111111111111111111111111111"
+                            .to_string(),
+                    ),
+                    address: Some(address!("0x0000000000000000000000000000000000000009")),
+                    origin_chain_id: Some(1),
+                    name: Some("ZKsync".to_string()),
+                    symbol: Some("ZK".to_string()),
+                    decimals: Some(18),
+                    gas_price_multiplier_nominator: Some(3),
+                    gas_price_multiplier_denominator: Some(2),
+                },
+                data_availability: DataAvailability {
+                    pricing_mode: Some("validium".to_string()),
+                    l1_validator: Some(address!("0x00000000000000000000000000000000000000a1")),
+                    l2_validator: Some(address!("0x00000000000000000000000000000000000000a2")),
+                    classification: Some("validium (Avail)".to_string()),
+                },
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains("Base Token\n  - Kind: custom token"));
+        assert!(output.contains("  - Token: 0x0000000000000000000000000000000000000009"));
+        assert!(output.contains("  - Symbol: ZK"));
+        assert!(output.contains("  - Origin Chain ID: 1"));
+        assert!(output.contains("  - Gas Price Multiplier: 3/2"));
+        assert!(output.contains("Data Availability\n  - Mode: validium (Avail)"));
+        assert!(output.contains("  - L1 DA Validator: 0x00000000000000000000000000000000000000A1"));
+    }
+
+    #[test]
+    fn renders_fee_parameters_section() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                fee_params: Some(FeeParams {
+                    pubdata_pricing_mode: "rollup".to_string(),
+                    batch_overhead_l1_gas: 800_000,
+                    max_pubdata_per_batch: 120_000,
+                    max_l2_gas_per_batch: 80_000_000,
+                    priority_tx_max_pubdata: 99_000,
+                    minimal_l2_gas_price: 250_000_000,
+                }),
+                priority_tx_max_gas_limit: Some(4_700_000),
+                transaction_filterer: Some(address!("0x0000000000000000000000000000000000000000")),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains("Fee Parameters\n  - Pubdata Pricing Mode: rollup"));
+        assert!(output.contains("  - Batch Overhead L1 Gas: 800000"));
+        assert!(output.contains("  - Max L2 Gas Per Batch: 80000000"));
+        assert!(output.contains("  - Minimal L2 Gas Price: 250000000"));
+        assert!(output.contains("  - Priority Tx Max Gas Limit: 4700000"));
+        assert!(output.contains("  - Transaction Filterer: none"));
+    }
+
+    #[test]
+    fn renders_ownership_timeline() {
+        let mut inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+        assert!(
+            !render_chain_inspection(&inspection, false, &AddressLabels::default())
+                .contains("Ownership Timeline")
+        );
+
+        inspection.timeline = Some(vec![TimelineEntry {
+            block_number: 120,
+            log_index: 3,
+            transaction_hash: "0xabc".to_string(),
+            contract: "chain admin".to_string(),
+            address: address!("0x0000000000000000000000000000000000000005"),
+            event: "OwnershipTransferred".to_string(),
+            description: "owner 0x0000000000000000000000000000000000000000 -> 0x0000000000000000000000000000000000000006".to_string(),
+        }]);
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains(
+            "Ownership Timeline\n  - block 120 (tx 0xabc) chain admin 0x0000000000000000000000000000000000000005: OwnershipTransferred owner 0x0000000000000000000000000000000000000000 -> 0x0000000000000000000000000000000000000006"
+        ));
+    }
+
+    #[test]
+    fn renders_upgrade_history() {
+        let history = UpgradeHistory {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_id: 324,
+            ctm: Some(address!("0x0000000000000000000000000000000000000002")),
+            chain_contract: Some(address!("0x0000000000000000000000000000000000000003")),
+            current_protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+            from_block: 10,
+            to_block: 500,
+            events: vec![UpgradeEvent {
+                block_number: 120,
+                log_index: 0,
+                transaction_hash: "0xabc".to_string(),
+                source: "chain diamond".to_string(),
+                event: "ExecuteUpgrade".to_string(),
+                previous_protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+                protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+                facet_changes: vec![FacetChange {
+                    facet: address!("0x0000000000000000000000000000000000000004"),
+                    action: "replace".to_string(),
+                    selectors: vec!["0x12345678".to_string(), "0x9abcdef0".to_string()],
+                }],
+                init_address: Some(address!("0x0000000000000000000000000000000000000005")),
+                init_calldata_hash: Some("0xdead".to_string()),
+            }],
+            warnings: vec![],
+        };
+
+        let output = render_upgrade_history(&history, false);
+        assert!(output.contains("  - Blocks: 10..=500"));
+        assert!(
+            output.contains(
+                "  - block 120 (tx 0xabc) chain diamond ExecuteUpgrade: 0.28.0 -> 0.29.0"
+            )
+        );
+        assert!(output.contains(
+            "    - Initializer: 0x0000000000000000000000000000000000000005 (calldata hash 0xdead)"
+        ));
+        assert!(
+            output
+                .contains("    - replace 0x0000000000000000000000000000000000000004: 2 selectors")
+        );
+
+        let output = render_upgrade_history(&history, true);
+        assert!(output.contains(
+            "    - replace 0x0000000000000000000000000000000000000004: 0x12345678,0x9abcdef0"
+        ));
+    }
+
+    #[test]
+    fn renders_snapshot_diff_with_security_markers() {
+        let diff = SnapshotDiff {
+            old_block_number: Some(100),
+            new_block_number: Some(200),
+            changes: vec![
+                SnapshotChange {
+                    chain_id: Some(325),
+                    field: "chain".to_string(),
+                    old: None,
+                    new: Some("registered".to_string()),
+                    security_relevant: false,
+                },
+                SnapshotChange {
+                    chain_id: Some(324),
+                    field: "admin".to_string(),
+                    old: Some("0x0000000000000000000000000000000000000002".to_string()),
+                    new: Some("0x0000000000000000000000000000000000000003".to_string()),
+                    security_relevant: true,
+                },
+            ],
+        };
+
+        let output = render_snapshot_diff(&diff);
+        assert!(output.contains("  - Blocks: 100 -> 200"));
+        assert!(output.contains("  - Security-relevant: 1"));
+        assert!(output.contains("  - chain 325 chain: unset -> registered"));
+        assert!(output.contains(
+            "  ! chain 324 admin: 0x0000000000000000000000000000000000000002 -> 0x0000000000000000000000000000000000000003"
+        ));
+    }
+
+    #[test]
+    fn renders_manifest_violations() {
+        let report = VerificationReport {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chains_checked: vec![324, 325],
+            fields_checked: 6,
+            violations: vec![ManifestViolation {
+                chain_id: 325,
+                field: "protocol_version".to_string(),
+                expected: "0.29.0".to_string(),
+                actual: None,
+            }],
+            warnings: vec![],
+        };
+
+        let output = render_verification_report(&report);
+        assert!(output.contains("  - Chains checked: 324, 325"));
+        assert!(output.contains("  - Violations: 1"));
+        assert!(
+            output.contains("  - chain 325 protocol_version: expected 0.29.0, found unresolved")
+        );
+    }
+
+    #[test]
+    fn renders_audit_findings_by_severity() {
+        let report = AuditReport {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chains_audited: vec![324, 325],
+            findings: vec![
+                AuditFinding {
+                    rule: "admin-owner-eoa".to_string(),
+                    severity: Severity::High,
+                    chain_id: Some(324),
+                    message:
+                        "chain admin owner 0x00000000000000000000000000000000000000e0 is an EOA"
+                            .to_string(),
+                },
+                AuditFinding {
+                    rule: "unresolved-ctm".to_string(),
+                    severity: Severity::Medium,
+                    chain_id: Some(325),
+                    message: "chain is registered but its CTM could not be resolved".to_string(),
+                },
+            ],
+            warnings: vec![],
+        };
+
+        let output = render_audit_report(&report);
+        assert!(output.contains("  - Findings: 2 (1 high, 1 medium)"));
+        assert!(output.contains(
+            "  - [high] admin-owner-eoa chain 324: chain admin owner 0x00000000000000000000000000000000000000e0 is an EOA"
+        ));
+    }
+
+    #[test]
+    fn renders_field_bisection() {
+        let bisection = FieldBisection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_id: 324,
+            field: "admin_owner".to_string(),
+            from_block: 100,
+            to_block: 900,
+            from_value: None,
+            to_value: Some("0x0000000000000000000000000000000000000002".to_string()),
+            changed_at_block: Some(512),
+            previous_value: None,
+            probes: 12,
+        };
+
+        let output = render_field_bisection(&bisection);
+        assert!(output.contains("  - Field: admin_owner"));
+        assert!(output.contains("  - Blocks: 100..=900 (12 probes)"));
+        assert!(output.contains(
+            "  - Changed At Block: 512 (unset -> 0x0000000000000000000000000000000000000002)"
+        ));
+    }
+
+    #[test]
+    fn renders_settlement_layer_and_followed_gateway() {
+        let chain = ChainSummary {
+            chain_id: 324,
+            settlement: SettlementLayer {
+                l1_chain_id: Some(1),
+                settlement_chain_id: Some(9075),
+                settlement_layer_whitelisted: Some(true),
+                diamond_settlement_layer: Some(address!(
+                    "0x0000000000000000000000000000000000000009"
+                )),
+                is_settlement_layer: Some(false),
+                migrated: Some(true),
+            },
+            ..Default::default()
+        };
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: chain.clone(),
+            gateway: Some(Box::new(ChainInspection {
+                bridgehub: address!("0x0000000000000000000000000000000000010002"),
+                chain: ChainSummary {
+                    ctm: Some(address!("0x000000000000000000000000000000000000000a")),
+                    settlement: SettlementLayer::default(),
+                    ..chain
+                },
+                gateway: None,
+                timeline: None,
+                l2: None,
+                warnings: vec![],
+                unresolved: Default::default(),
+            })),
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains("Settlement Layer\n  - Settles On: gateway (chain 9075)"));
+        assert!(output.contains("  - Settlement Layer Whitelisted: yes"));
+        assert!(
+            output.contains(
+                "  - Diamond Settlement Layer: 0x0000000000000000000000000000000000000009"
+            )
+        );
+        assert!(output.contains(
+            "Gateway\n  - BridgeHub: 0x0000000000000000000000000000000000010002\n  - CTM: 0x000000000000000000000000000000000000000A"
+        ));
+    }
+
+    #[test]
+    fn renders_l2_node_section() {
+        let inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: Some(L2Inspection {
+                chain_id: Some(324),
+                l1_chain_id: Some(1),
+                bridgehub: Some(address!("0x0000000000000000000000000000000000000001")),
+                main_contract: None,
+                base_token_l1_address: None,
+                bridge_contracts: Some(BTreeMap::from([(
+                    "l1SharedDefaultBridge".to_string(),
+                    address!("0x00000000000000000000000000000000000000b1"),
+                )])),
+                latest_l1_batch: Some(4_200),
+                batch_progress: Some(BatchProgress {
+                    latest_sealed: 4_200,
+                    committed: 4_198,
+                    proven: 4_190,
+                    executed: 4_190,
+                    commit_lag: BatchLag {
+                        batches: 2,
+                        seconds: Some(120),
+                    },
+                    proof_lag: BatchLag {
+                        batches: 10,
+                        seconds: None,
+                    },
+                    execution_lag: BatchLag {
+                        batches: 10,
+                        seconds: None,
+                    },
+                }),
+            }),
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        let output = render_chain_inspection(&inspection, false, &AddressLabels::default());
+        assert!(output.contains(
+            "L2 Node\n  - Chain ID: 324\n  - L1 Chain ID: 1\n  - BridgeHub: 0x0000000000000000000000000000000000000001\n  - Main Contract: unknown"
+        ));
+        assert!(output.contains(
+            "  - Latest L1 Batch: 4200\n  - Committed Batch: 4198 (2 batches, 120s behind)\n  - Proven Batch: 4190 (10 batches behind)"
+        ));
+        assert!(output.contains(
+            "  - Bridge l1SharedDefaultBridge: 0x00000000000000000000000000000000000000B1"
+        ));
+    }
+
+    #[test]
+    fn renders_json_with_topology_and_chains() {
+        let snapshot = TopologySnapshot {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain_ids: vec![324],
+            chain_ctms: vec![],
+            ctms: vec![],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        };
+
+        let output = render_formatted(
+            OutputFormat::Json,
+            &snapshot,
+            &[],
+            &[],
+            &AddressLabels::default(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&output).expect("output is JSON");
+        assert_eq!(value["chains"], serde_json::json!([]));
+        let topology: TopologySnapshot =
+            serde_json::from_value(value["topology"].clone()).expect("topology deserializes");
+        assert_eq!(topology, snapshot);
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+    }
+
+    #[test]
+    fn formats_with_multisig_columns_need_multisig_details() {
+        let formats = OutputFormat::ALL
+            .into_iter()
+            .filter(|format| format.needs_multisig_details())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            formats,
+            vec![
+                OutputFormat::Markdown,
+                OutputFormat::Html,
+                OutputFormat::Csv,
+                OutputFormat::Tsv
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_field_history_runs() {
+        let history = FieldHistory {
+            chain_id: 324,
+            field: "protocol_version".to_string(),
+            entries: vec![
+                FieldHistoryEntry {
+                    value: Some("0.28.0".to_string()),
+                    first_block: Some(100),
+                    last_block: Some(200),
+                    snapshots: 2,
+                },
+                FieldHistoryEntry {
+                    value: None,
+                    first_block: None,
+                    last_block: None,
+                    snapshots: 1,
+                },
+            ],
+        };
+
+        let output = render_field_history(&history);
+        assert!(output.contains("  - Field: protocol_version\n  - Snapshots: 3"));
+        assert!(output.contains(
+            "History\n  - Blocks 100..=200: 0.28.0 (2 snapshots)\n  - Blocks unknown..=unknown: unset (1 snapshots)"
+        ));
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+
+use alloy_primitives::Address;
+
+use crate::diagnostic::Diagnostic;
+use crate::model::{ChainInspection, TopologySnapshot};
+
+use super::{AddressLabels, format_optional};
+
+const CTM_COLUMNS: [&str; 3] = ["CTM", "Protocol Version", "Chains"];
+const CHAIN_COLUMNS: [&str; 7] = [
+    "Chain ID",
+    "CTM",
+    "Protocol Version",
+    "Diamond",
+    "Admin Owner",
+    "Timelock Owner",
+    "Threshold",
+];
+
+fn ctm_rows(snapshot: &TopologySnapshot) -> Vec<[String; 3]> {
+    let mut chain_ids: BTreeMap<Address, Vec<u64>> = BTreeMap::new();
+    for entry in &snapshot.chain_ctms {
+        chain_ids.entry(entry.ctm).or_default().push(entry.chain_id);
+    }
+    snapshot
+        .ctms
+        .iter()
+        .map(|ctm| {
+            let mut chains = chain_ids.remove(&ctm.address).unwrap_or_default();
+            chains.sort_unstable();
+            let chains = if chains.is_empty() {
+                "none".to_string()
+            } else {
+                chains
+                    .iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            [
+                ctm.address.to_string(),
+                format_optional(ctm.protocol_version),
+                chains,
+            ]
+        })
+        .collect()
+}
+
+fn chain_row(inspection: &ChainInspection) -> [String; 7] {
+    let chain = &inspection.chain;
+    [
+        chain.chain_id.to_string(),
+        format_optional(chain.ctm),
+        format_optional(chain.protocol_version),
+        format_optional(chain.chain_contract),
+        format_optional(chain.admin_owner),
+        format_optional(chain.validator_timelock_owner),
+        format_optional(chain.multisig_signing_threshold),
+    ]
+}
+
+/// Topology warnings followed by every chain's own warnings.
+fn all_warnings<'a>(
+    snapshot: &'a TopologySnapshot,
+    inspections: &'a [ChainInspection],
+) -> impl Iterator<Item = &'a Diagnostic> {
+    snapshot.warnings.iter().chain(
+        inspections
+            .iter()
+            .flat_map(|inspection| &inspection.warnings),
+    )
+}
+
+/// Labels the addresses of every cell, before the cells are escaped.
+fn label_rows<const N: usize>(rows: Vec<[String; N]>, labels: &AddressLabels) -> Vec<[String; N]> {
+    rows.into_iter()
+        .map(|row| row.map(|cell| labels.annotate(&cell)))
+        .collect()
+}
+
+fn warning_line(warning: &Diagnostic) -> String {
+    match warning.chain_id {
+        Some(chain_id) => format!("{} chain {chain_id}: {warning}", warning.code),
+        None => format!("{} {warning}", warning.code),
+    }
+}
+
+/// Renders CTM and chain tables plus the warnings list as Markdown.
+pub fn render_markdown(
+    snapshot: &TopologySnapshot,
+    inspections: &[ChainInspection],
+    labels: &AddressLabels,
+) -> String {
+    let title = labels.annotate(&format!("BridgeHub {}", snapshot.bridgehub));
+    let mut lines = vec![format!("# {}", escape_markdown(&title)), String::new()];
+
+    lines.push("## CTMs".to_string());
+    lines.push(String::new());
+    push_markdown_table(
+        &mut lines,
+        &CTM_COLUMNS,
+        label_rows(ctm_rows(snapshot), labels),
+    );
+
+    lines.push(String::new());
+    lines.push("## Chains".to_string());
+    lines.push(String::new());
+    push_markdown_table(
+        &mut lines,
+        &CHAIN_COLUMNS,
+        label_rows(inspections.iter().map(chain_row).collect(), labels),
+    );
+
+    lines.push(String::new());
+    lines.push("## Warnings".to_string());
+    lines.push(String::new());
+    let warnings = all_warnings(snapshot, inspections)
+        .map(|warning| {
+            let line = labels.annotate(&warning_line(warning));
+            format!("- {}", escape_markdown(&line))
+        })
+        .collect::<Vec<_>>();
+    if warnings.is_empty() {
+        lines.push("- none".to_string());
+    }
+    lines.extend(warnings);
+    lines.join("\n")
+}
+
+fn push_markdown_table<const N: usize>(
+    lines: &mut Vec<String>,
+    columns: &[&str; N],
+    rows: Vec<[String; N]>,
+) {
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("|{}", " --- |".repeat(N)));
+    for row in rows {
+        let cells = row
+            .iter()
+            .map(|cell| escape_markdown(cell))
+            .collect::<Vec<_>>();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse;margin:.5em 0}\
+th,td{border:1px solid #ccc;padding:.25em .5em;text-align:left}\
+td,code{font-family:monospace}\
+details{margin:.5em 0}\
+summary{cursor:pointer;font-weight:bold}\
+.warning{color:#b00}";
+
+/// Renders a self-contained HTML report with one collapsible section per
+/// chain.
+pub fn render_html(
+    snapshot: &TopologySnapshot,
+    inspections: &[ChainInspection],
+    labels: &AddressLabels,
+) -> String {
+    let title = labels.annotate(&format!("BridgeHub {}", snapshot.bridgehub));
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>mercator: {}</title>", escape_html(&title)),
+        format!("<style>{HTML_STYLE}</style>"),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("<h1>{}</h1>", escape_html(&title)),
+        "<h2>CTMs</h2>".to_string(),
+    ];
+    push_html_table(
+        &mut lines,
+        &CTM_COLUMNS,
+        label_rows(ctm_rows(snapshot), labels),
+    );
+
+    lines.push("<h2>Chains</h2>".to_string());
+    if inspections.is_empty() {
+        lines.push("<p>none inspected</p>".to_string());
+    }
+    for inspection in inspections {
+        let chain = &inspection.chain;
+        let mut summary = format!("Chain {}", chain.chain_id);
+        if let Some(version) = chain.protocol_version {
+            summary.push_str(&format!(" (v{version})"));
+        }
+        if !inspection.warnings.is_empty() {
+            summary.push_str(&format!(", {} warnings", inspection.warnings.len()));
+        }
+        lines.push("<details>".to_string());
+        lines.push(format!("<summary>{}</summary>", escape_html(&summary)));
+        let fields = [
+            ("CTM", format_optional(chain.ctm)),
+            ("Protocol", format_optional(chain.protocol_version)),
+            ("Chain Diamond Proxy", format_optional(chain.chain_contract)),
+            ("Chain Admin Ownable", format_optional(chain.admin)),
+            ("Chain Admin Owner", format_optional(chain.admin_owner)),
+            (
+                "Validator Timelock Ownable",
+                format_optional(chain.validator_timelock),
+            ),
+            (
+                "Validator Timelock Owner",
+                format_optional(chain.validator_timelock_owner),
+            ),
+            (
+                "Signing Set Mode",
+                chain
+                    .multisig_signing_set_mode
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+            (
+                "Signing Threshold",
+                format_optional(chain.multisig_signing_threshold),
+            ),
+            (
+                "Settlement Chain ID",
+                format_optional(chain.settlement.settlement_chain_id),
+            ),
+        ];
+        lines.push("<table>".to_string());
+        for (name, value) in fields {
+            lines.push(format!(
+                "<tr><th>{name}</th><td>{}</td></tr>",
+                escape_html(&labels.annotate(&value))
+            ));
+        }
+        lines.push("</table>".to_string());
+        push_html_warnings(
+            &mut lines,
+            &inspection.warnings.iter().collect::<Vec<_>>(),
+            labels,
+        );
+        lines.push("</details>".to_string());
+    }
+
+    lines.push("<h2>Warnings</h2>".to_string());
+    let warnings = all_warnings(snapshot, inspections).collect::<Vec<_>>();
+    if warnings.is_empty() {
+        lines.push("<p>none</p>".to_string());
+    }
+    push_html_warnings(&mut lines, &warnings, labels);
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines.join("\n")
+}
+
+fn push_html_table<const N: usize>(
+    lines: &mut Vec<String>,
+    columns: &[&str; N],
+    rows: Vec<[String; N]>,
+) {
+    lines.push("<table>".to_string());
+    let header = columns
+        .iter()
+        .map(|column| format!("<th>{column}</th>"))
+        .collect::<String>();
+    lines.push(format!("<tr>{header}</tr>"));
+    for row in rows {
+        let cells = row
+            .iter()
+            .map(|cell| format!("<td>{}</td>", escape_html(cell)))
+            .collect::<String>();
+        lines.push(format!("<tr>{cells}</tr>"));
+    }
+    lines.push("</table>".to_string());
+}
+
+fn push_html_warnings(lines: &mut Vec<String>, warnings: &[&Diagnostic], labels: &AddressLabels) {
+    if warnings.is_empty() {
+        return;
+    }
+    lines.push("<ul class=\"warning\">".to_string());
+    for warning in warnings {
+        let line = labels.annotate(&warning_line(warning));
+        lines.push(format!("<li>{}</li>", escape_html(&line)));
+    }
+    lines.push("</ul>".to_string());
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::diagnostic::DiagnosticCode;
+    use crate::model::{ChainCtm, ChainSummary, CtmSummary, ProtocolSemver};
+
+    fn fixture() -> (TopologySnapshot, Vec<ChainInspection>) {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let ctm = address!("0x000000000000000000000000000000000000000c");
+        let snapshot = TopologySnapshot {
+            bridgehub,
+            chain_ids: vec![324],
+            chain_ctms: vec![ChainCtm { chain_id: 324, ctm }],
+            ctms: vec![CtmSummary {
+                address: ctm,
+                protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+            }],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        };
+        let inspection = ChainInspection {
+            bridgehub,
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(ctm),
+                validator_timelock_owner: Some(address!(
+                    "0x00000000000000000000000000000000000000f0"
+                )),
+                chain_contract: Some(address!("0x00000000000000000000000000000000000000d1")),
+                protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+                multisig_signing_threshold: Some(2),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![
+                Diagnostic::new(
+                    DiagnosticCode::TransactionFilterer,
+                    "transaction filterer <set> | censoring",
+                )
+                .chain(324),
+            ],
+            unresolved: Default::default(),
+        };
+        (snapshot, vec![inspection])
+    }
+
+    #[test]
+    fn renders_markdown_tables_and_escapes_cells() {
+        let (snapshot, inspections) = fixture();
+        let output = render_markdown(&snapshot, &inspections, &AddressLabels::default());
+
+        assert!(output.starts_with(
+            "# BridgeHub 0x0000000000000000000000000000000000000001\n\n## CTMs\n\n| CTM | Protocol Version | Chains |\n| --- | --- | --- |\n| 0x000000000000000000000000000000000000000C | 0.28.0 | 324 |"
+        ));
+        assert!(output.contains(
+            "| 324 | 0x000000000000000000000000000000000000000C | 0.28.0 | 0x00000000000000000000000000000000000000D1 | unknown | 0x00000000000000000000000000000000000000F0 | 2 |"
+        ));
+        assert!(output.ends_with(
+            "## Warnings\n\n- W005 chain 324: transaction filterer <set> \\| censoring"
+        ));
+    }
+
+    #[test]
+    fn renders_html_report_with_collapsible_chains() {
+        let (snapshot, inspections) = fixture();
+        let output = render_html(&snapshot, &inspections, &AddressLabels::default());
+
+        assert!(output.starts_with("<!DOCTYPE html>\n<html>"));
+        assert!(output.contains("<summary>Chain 324 (v0.28.0), 1 warnings</summary>"));
+        assert!(output.contains(
+            "<tr><th>Chain Diamond Proxy</th><td>0x00000000000000000000000000000000000000D1</td></tr>"
+        ));
+        assert!(output.contains("<tr><th>Signing Threshold</th><td>2</td></tr>"));
+        assert_eq!(
+            output
+                .matches("<li>W005 chain 324: transaction filterer &lt;set&gt; | censoring</li>")
+                .count(),
+            2
+        );
+        assert!(!output.contains("<script"));
+        assert!(output.ends_with("</body>\n</html>"));
+    }
+
+    #[test]
+    fn labels_addresses_in_reports() {
+        let (snapshot, inspections) = fixture();
+        let labels = AddressLabels::new(BTreeMap::from([(
+            address!("0x00000000000000000000000000000000000000f0"),
+            "Ops <Safe> | 3/5".to_string(),
+        )]));
+
+        let markdown = render_markdown(&snapshot, &inspections, &labels);
+        assert!(
+            markdown.contains(
+                "| 0x00000000000000000000000000000000000000F0 (Ops <Safe> \\| 3/5) | 2 |"
+            )
+        );
+        let html = render_html(&snapshot, &inspections, &labels);
+        assert!(html.contains(
+            "<td>0x00000000000000000000000000000000000000F0 (Ops &lt;Safe&gt; | 3/5)</td>"
+        ));
+    }
+}
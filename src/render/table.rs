@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use alloy_primitives::Address;
+
+use crate::model::{ChainInspection, ProtocolSemver, TopologySnapshot};
+
+/// A column of the per-chain CSV/TSV export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainColumn {
+    ChainId,
+    Ctm,
+    CtmVersion,
+    ChainVersion,
+    Diamond,
+    Admin,
+    AdminOwner,
+    Timelock,
+    TimelockOwner,
+    SigningMode,
+    Threshold,
+    ValidatorCount,
+}
+
+impl ChainColumn {
+    pub const ALL: [Self; 12] = [
+        Self::ChainId,
+        Self::Ctm,
+        Self::CtmVersion,
+        Self::ChainVersion,
+        Self::Diamond,
+        Self::Admin,
+        Self::AdminOwner,
+        Self::Timelock,
+        Self::TimelockOwner,
+        Self::SigningMode,
+        Self::Threshold,
+        Self::ValidatorCount,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ChainId => "chain_id",
+            Self::Ctm => "ctm",
+            Self::CtmVersion => "ctm_version",
+            Self::ChainVersion => "chain_version",
+            Self::Diamond => "diamond",
+            Self::Admin => "admin",
+            Self::AdminOwner => "admin_owner",
+            Self::Timelock => "timelock",
+            Self::TimelockOwner => "timelock_owner",
+            Self::SigningMode => "signing_mode",
+            Self::Threshold => "threshold",
+            Self::ValidatorCount => "validator_count",
+        }
+    }
+
+    /// Cell value; unknown values are left empty.
+    fn value(
+        self,
+        inspection: &ChainInspection,
+        ctm_versions: &BTreeMap<Address, ProtocolSemver>,
+    ) -> String {
+        fn cell<T: ToString>(value: Option<T>) -> String {
+            value.map(|value| value.to_string()).unwrap_or_default()
+        }
+
+        let chain = &inspection.chain;
+        match self {
+            Self::ChainId => chain.chain_id.to_string(),
+            Self::Ctm => cell(chain.ctm),
+            Self::CtmVersion => cell(chain.ctm.and_then(|ctm| ctm_versions.get(&ctm))),
+            Self::ChainVersion => cell(chain.protocol_version),
+            Self::Diamond => cell(chain.chain_contract),
+            Self::Admin => cell(chain.admin),
+            Self::AdminOwner => cell(chain.admin_owner),
+            Self::Timelock => cell(chain.validator_timelock),
+            Self::TimelockOwner => cell(chain.validator_timelock_owner),
+            Self::SigningMode => cell(chain.multisig_signing_set_mode.as_deref()),
+            Self::Threshold => cell(chain.multisig_signing_threshold),
+            Self::ValidatorCount => cell(chain.multisig_validators.as_ref().map(Vec::len)),
+        }
+    }
+}
+
+impl fmt::Display for ChainColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ChainColumn {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|column| column.name() == value)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown column `{value}` (expected one of: {names})")
+            })
+    }
+}
+
+/// Renders one row per inspected chain, separated by `delimiter`, with a
+/// header row. Every column is emitted, in `ChainColumn::ALL` order, when
+/// `columns` is empty.
+pub fn render_delimited(
+    snapshot: &TopologySnapshot,
+    inspections: &[ChainInspection],
+    columns: &[ChainColumn],
+    delimiter: char,
+) -> String {
+    let columns = if columns.is_empty() {
+        &ChainColumn::ALL[..]
+    } else {
+        columns
+    };
+    let ctm_versions = snapshot
+        .ctms
+        .iter()
+        .filter_map(|ctm| Some((ctm.address, ctm.protocol_version?)))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut lines = vec![delimited_row(
+        columns.iter().map(|column| column.name().to_string()),
+        delimiter,
+    )];
+    for inspection in inspections {
+        lines.push(delimited_row(
+            columns
+                .iter()
+                .map(|column| column.value(inspection, &ctm_versions)),
+            delimiter,
+        ));
+    }
+    lines.join("\n")
+}
+
+fn delimited_row(cells: impl Iterator<Item = String>, delimiter: char) -> String {
+    cells
+        .map(|cell| quote_cell(&cell, delimiter))
+        .collect::<Vec<_>>()
+        .join(&delimiter.to_string())
+}
+
+/// Quotes a cell containing the delimiter, a quote or a line break, doubling
+/// embedded quotes (RFC 4180).
+fn quote_cell(cell: &str, delimiter: char) -> String {
+    if cell.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::model::{ChainCtm, ChainSummary, CtmSummary};
+
+    fn fixture() -> (TopologySnapshot, Vec<ChainInspection>) {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let ctm = address!("0x000000000000000000000000000000000000000c");
+        let snapshot = TopologySnapshot {
+            bridgehub,
+            chain_ids: vec![324],
+            chain_ctms: vec![ChainCtm { chain_id: 324, ctm }],
+            ctms: vec![CtmSummary {
+                address: ctm,
+                protocol_version: Some(ProtocolSemver::new(0, 29, 1)),
+            }],
+            chain_data_availability: vec![],
+            history: None,
+            warnings: vec![],
+        };
+        let inspection = ChainInspection {
+            bridgehub,
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(ctm),
+                chain_contract: Some(address!("0x00000000000000000000000000000000000000d1")),
+                protocol_version: Some(ProtocolSemver::new(0, 28, 0)),
+                multisig_signing_set_mode: Some("custom, \"strict\"".to_string()),
+                multisig_signing_threshold: Some(2),
+                multisig_validators: Some(vec![
+                    address!("0x00000000000000000000000000000000000000b1"),
+                    address!("0x00000000000000000000000000000000000000b2"),
+                    address!("0x00000000000000000000000000000000000000b3"),
+                ]),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+        (snapshot, vec![inspection])
+    }
+
+    #[test]
+    fn renders_every_column_as_csv_with_quoting() {
+        let (snapshot, inspections) = fixture();
+        let output = render_delimited(&snapshot, &inspections, &[], ',');
+
+        assert_eq!(
+            output,
+            "chain_id,ctm,ctm_version,chain_version,diamond,admin,admin_owner,timelock,timelock_owner,signing_mode,threshold,validator_count\n\
+             324,0x000000000000000000000000000000000000000C,0.29.1,0.28.0,0x00000000000000000000000000000000000000D1,,,,,\"custom, \"\"strict\"\"\",2,3"
+        );
+    }
+
+    #[test]
+    fn renders_selected_columns_as_tsv() {
+        let (snapshot, inspections) = fixture();
+        let output = render_delimited(
+            &snapshot,
+            &inspections,
+            &[
+                ChainColumn::ChainId,
+                ChainColumn::SigningMode,
+                ChainColumn::CtmVersion,
+            ],
+            '\t',
+        );
+
+        assert_eq!(
+            output,
+            "chain_id\tsigning_mode\tctm_version\n324\t\"custom, \"\"strict\"\"\"\t0.29.1"
+        );
+    }
+
+    #[test]
+    fn parses_column_names() {
+        assert_eq!(
+            "timelock_owner".parse::<ChainColumn>(),
+            Ok(ChainColumn::TimelockOwner)
+        );
+        assert!("owner".parse::<ChainColumn>().is_err());
+    }
+}
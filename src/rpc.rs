@@ -1,13 +1,114 @@
 use std::{str::FromStr, time::Duration};
 
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, B256, Bytes, U256};
 use alloy_provider::{Provider, ProviderBuilder, network::TransactionBuilder};
-use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_eth::{BlockId, BlockNumberOrTag, Filter, TransactionRequest};
+use serde_json::Value;
 use thiserror::Error;
 use tokio::runtime::Runtime;
 
 pub trait RpcClient {
     fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError>;
+
+    /// `eth_call` against the state at `block`; needs an archive node for old blocks.
+    fn eth_call_at_block(&self, _to: &str, _data: &str, _block: u64) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_call at block"))
+    }
+
+    fn eth_chain_id(&self) -> Result<u64, RpcError> {
+        Err(RpcError::Unsupported("eth_chainId"))
+    }
+
+    /// Reads a raw storage word; `slot` and the result are 0x-prefixed hex.
+    fn eth_get_storage_at(&self, _address: &str, _slot: &str) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_getStorageAt"))
+    }
+
+    /// `eth_getStorageAt` against the state at `block`.
+    fn eth_get_storage_at_block(
+        &self,
+        _address: &str,
+        _slot: &str,
+        _block: u64,
+    ) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_getStorageAt at block"))
+    }
+
+    fn eth_block_number(&self) -> Result<u64, RpcError> {
+        Err(RpcError::Unsupported("eth_blockNumber"))
+    }
+
+    fn eth_get_logs(&self, _filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+        Err(RpcError::Unsupported("eth_getLogs"))
+    }
+
+    /// Deployed bytecode as 0x-prefixed hex; `0x` for EOAs.
+    fn eth_get_code(&self, _address: &str) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_getCode"))
+    }
+
+    /// `eth_getCode` against the state at `block`.
+    fn eth_get_code_at_block(&self, _address: &str, _block: u64) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_getCode at block"))
+    }
+
+    /// Unix timestamp of `block`.
+    fn eth_get_block_timestamp(&self, _block: u64) -> Result<u64, RpcError> {
+        Err(RpcError::Unsupported("eth_getBlockByNumber"))
+    }
+
+    /// Hash of `block` as 0x-prefixed hex.
+    fn eth_get_block_hash(&self, _block: u64) -> Result<String, RpcError> {
+        Err(RpcError::Unsupported("eth_getBlockByNumber"))
+    }
+
+    /// Calls a method without a typed wrapper, such as the `zks_*` namespace.
+    fn raw_request(&self, method: &'static str, _params: Value) -> Result<Value, RpcError> {
+        Err(RpcError::Unsupported(method))
+    }
+}
+
+/// Pins every `eth_call` of the wrapped client to one block, so the existing
+/// getters read historical state.
+pub struct AtBlock<'a> {
+    client: &'a dyn RpcClient,
+    block: u64,
+}
+
+impl<'a> AtBlock<'a> {
+    pub fn new(client: &'a dyn RpcClient, block: u64) -> Self {
+        Self { client, block }
+    }
+}
+
+impl RpcClient for AtBlock<'_> {
+    fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+        self.client.eth_call_at_block(to, data, self.block)
+    }
+
+    fn eth_chain_id(&self) -> Result<u64, RpcError> {
+        self.client.eth_chain_id()
+    }
+}
+
+/// `eth_getLogs` query for one contract over an inclusive block range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    pub address: String,
+    /// Accepted `topic0` values (event signature hashes); empty matches any.
+    pub event_signatures: Vec<String>,
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub log_index: u64,
 }
 
 #[derive(Debug, Clone, Error)]
@@ -16,6 +117,8 @@ pub enum RpcError {
     Transport(String),
     #[error("invalid rpc response: {0}")]
     InvalidResponse(String),
+    #[error("rpc method not supported by client: {0}")]
+    Unsupported(&'static str),
 }
 
 pub struct HttpRpcClient {
@@ -45,10 +148,28 @@ impl HttpRpcClient {
             runtime,
         })
     }
-}
 
-impl RpcClient for HttpRpcClient {
-    fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+    fn provider(&self) -> impl Provider {
+        ProviderBuilder::new().connect_reqwest(self.reqwest_client.clone(), self.rpc_url.clone())
+    }
+
+    fn block_header(&self, block: u64) -> Result<alloy_rpc_types_eth::Header, RpcError> {
+        let provider = self.provider();
+
+        let block = self
+            .runtime
+            .block_on(async {
+                provider
+                    .get_block_by_number(BlockNumberOrTag::Number(block))
+                    .await
+            })
+            .map_err(|err| RpcError::Transport(err.to_string()))?
+            .ok_or_else(|| RpcError::InvalidResponse(format!("block {block} not found")))?;
+
+        Ok(block.header)
+    }
+
+    fn call(&self, to: &str, data: &str, block: Option<u64>) -> Result<String, RpcError> {
         let to_address =
             Address::from_str(to).map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
         let calldata =
@@ -58,14 +179,159 @@ impl RpcClient for HttpRpcClient {
             .with_to(to_address)
             .with_input(calldata);
 
-        let provider = ProviderBuilder::new()
-            .connect_reqwest(self.reqwest_client.clone(), self.rpc_url.clone());
+        let provider = self.provider();
+        let block = block.map_or(BlockId::latest(), BlockId::number);
 
         let result = self
             .runtime
-            .block_on(async { provider.call(tx).await })
+            .block_on(async { provider.call(tx).block(block).await })
             .map_err(|err| RpcError::Transport(err.to_string()))?;
 
         Ok(result.to_string())
     }
+    fn storage_at(
+        &self,
+        address: &str,
+        slot: &str,
+        block: Option<u64>,
+    ) -> Result<String, RpcError> {
+        let address =
+            Address::from_str(address).map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+        let slot =
+            U256::from_str(slot).map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        let provider = self.provider();
+        let block = block.map_or(BlockId::latest(), BlockId::number);
+
+        let value = self
+            .runtime
+            .block_on(async { provider.get_storage_at(address, slot).block_id(block).await })
+            .map_err(|err| RpcError::Transport(err.to_string()))?;
+
+        Ok(format!("{value:#066x}"))
+    }
+
+    fn code_at(&self, address: &str, block: Option<u64>) -> Result<String, RpcError> {
+        let address =
+            Address::from_str(address).map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        let provider = self.provider();
+        let block = block.map_or(BlockId::latest(), BlockId::number);
+
+        let code = self
+            .runtime
+            .block_on(async { provider.get_code_at(address).block_id(block).await })
+            .map_err(|err| RpcError::Transport(err.to_string()))?;
+
+        Ok(code.to_string())
+    }
+}
+
+impl RpcClient for HttpRpcClient {
+    fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+        self.call(to, data, None)
+    }
+
+    fn eth_call_at_block(&self, to: &str, data: &str, block: u64) -> Result<String, RpcError> {
+        self.call(to, data, Some(block))
+    }
+
+    fn eth_chain_id(&self) -> Result<u64, RpcError> {
+        let provider = self.provider();
+        self.runtime
+            .block_on(async { provider.get_chain_id().await })
+            .map_err(|err| RpcError::Transport(err.to_string()))
+    }
+
+    fn eth_get_storage_at(&self, address: &str, slot: &str) -> Result<String, RpcError> {
+        self.storage_at(address, slot, None)
+    }
+
+    fn eth_get_storage_at_block(
+        &self,
+        address: &str,
+        slot: &str,
+        block: u64,
+    ) -> Result<String, RpcError> {
+        self.storage_at(address, slot, Some(block))
+    }
+
+    fn eth_block_number(&self) -> Result<u64, RpcError> {
+        let provider = self.provider();
+        self.runtime
+            .block_on(async { provider.get_block_number().await })
+            .map_err(|err| RpcError::Transport(err.to_string()))
+    }
+
+    fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+        let address = Address::from_str(&filter.address)
+            .map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+        let event_signatures = filter
+            .event_signatures
+            .iter()
+            .map(|signature| B256::from_str(signature))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        let mut query = Filter::new()
+            .address(address)
+            .from_block(filter.from_block)
+            .to_block(filter.to_block);
+        if !event_signatures.is_empty() {
+            query = query.event_signature(event_signatures);
+        }
+
+        let provider = self.provider();
+
+        let logs = self
+            .runtime
+            .block_on(async { provider.get_logs(&query).await })
+            .map_err(|err| RpcError::Transport(err.to_string()))?;
+
+        logs.into_iter()
+            .map(|log| {
+                let missing = |field: &str| {
+                    RpcError::InvalidResponse(format!("log is missing {field} (pending block?)"))
+                };
+                Ok(RpcLog {
+                    address: format!("{:#x}", log.address()),
+                    topics: log
+                        .topics()
+                        .iter()
+                        .map(|topic| format!("{topic:#x}"))
+                        .collect(),
+                    data: log.data().data.to_string(),
+                    block_number: log.block_number.ok_or_else(|| missing("blockNumber"))?,
+                    transaction_hash: log
+                        .transaction_hash
+                        .map(|hash| format!("{hash:#x}"))
+                        .ok_or_else(|| missing("transactionHash"))?,
+                    log_index: log.log_index.ok_or_else(|| missing("logIndex"))?,
+                })
+            })
+            .collect()
+    }
+
+    fn eth_get_code(&self, address: &str) -> Result<String, RpcError> {
+        self.code_at(address, None)
+    }
+
+    fn eth_get_code_at_block(&self, address: &str, block: u64) -> Result<String, RpcError> {
+        self.code_at(address, Some(block))
+    }
+
+    fn eth_get_block_timestamp(&self, block: u64) -> Result<u64, RpcError> {
+        Ok(self.block_header(block)?.timestamp)
+    }
+
+    fn eth_get_block_hash(&self, block: u64) -> Result<String, RpcError> {
+        Ok(self.block_header(block)?.hash.to_string())
+    }
+
+    fn raw_request(&self, method: &'static str, params: Value) -> Result<Value, RpcError> {
+        let provider = self.provider();
+        self.runtime
+            .block_on(async { provider.raw_request(method.into(), params).await })
+            .map_err(|err| RpcError::Transport(err.to_string()))
+    }
 }
@@ -0,0 +1,266 @@
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::Address;
+
+use crate::rpc::{AtBlock, RpcClient, RpcError};
+
+use super::bridgehub::{self, BridgehubError};
+use super::diamond;
+
+/// Chain fields that can be bisected; names match `CHAIN_FIELDS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectField {
+    Ctm,
+    ChainContract,
+    ValidatorTimelock,
+    ValidatorTimelockOwner,
+    Admin,
+    AdminOwner,
+    ProtocolVersion,
+    BaseToken,
+    SettlementChainId,
+    TransactionFilterer,
+}
+
+impl BisectField {
+    pub const ALL: [Self; 10] = [
+        Self::Ctm,
+        Self::ChainContract,
+        Self::ValidatorTimelock,
+        Self::ValidatorTimelockOwner,
+        Self::Admin,
+        Self::AdminOwner,
+        Self::ProtocolVersion,
+        Self::BaseToken,
+        Self::SettlementChainId,
+        Self::TransactionFilterer,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ctm => "ctm",
+            Self::ChainContract => "chain_contract",
+            Self::ValidatorTimelock => "validator_timelock",
+            Self::ValidatorTimelockOwner => "validator_timelock_owner",
+            Self::Admin => "admin",
+            Self::AdminOwner => "admin_owner",
+            Self::ProtocolVersion => "protocol_version",
+            Self::BaseToken => "base_token",
+            Self::SettlementChainId => "settlement_chain_id",
+            Self::TransactionFilterer => "transaction_filterer",
+        }
+    }
+}
+
+impl fmt::Display for BisectField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BisectField {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == value)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown field `{value}` (expected one of: {names})")
+            })
+    }
+}
+
+/// Reads `field` for `chain_id` as of `block`.
+///
+/// Returns `Ok(None)` when the value does not exist yet at that block (empty
+/// or reverted calls, e.g. before a contract was deployed or registered);
+/// transport failures are propagated so a non-archive node is not mistaken
+/// for a change.
+pub fn resolve_field_at_block(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+    field: BisectField,
+    block: u64,
+) -> Result<Option<String>, BridgehubError> {
+    let client = AtBlock::new(client, block);
+    match resolve_field(&client, bridgehub, chain_id, field) {
+        Ok(value) => Ok(value),
+        Err(BridgehubError::Decode(_)) => Ok(None),
+        Err(BridgehubError::Rpc(RpcError::Transport(message)))
+            if message.to_ascii_lowercase().contains("revert") =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn resolve_field(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+    field: BisectField,
+) -> Result<Option<String>, BridgehubError> {
+    let ctm = || bridgehub::get_chain_type_manager(client, bridgehub, chain_id);
+    let chain_contract = || bridgehub::get_zk_chain(client, bridgehub, chain_id);
+
+    let value = match field {
+        BisectField::Ctm => ctm()?.to_string(),
+        BisectField::ChainContract => chain_contract()?.to_string(),
+        BisectField::ValidatorTimelock => {
+            bridgehub::get_ctm_validator_timelock(client, non_zero(ctm()?)?)?.to_string()
+        }
+        BisectField::ValidatorTimelockOwner => {
+            let timelock = bridgehub::get_ctm_validator_timelock(client, non_zero(ctm()?)?)?;
+            bridgehub::get_contract_owner(client, non_zero(timelock)?)?.to_string()
+        }
+        BisectField::Admin => {
+            bridgehub::get_ctm_chain_admin(client, non_zero(ctm()?)?, chain_id)?.to_string()
+        }
+        BisectField::AdminOwner => {
+            let admin = bridgehub::get_ctm_chain_admin(client, non_zero(ctm()?)?, chain_id)?;
+            bridgehub::get_contract_owner(client, non_zero(admin)?)?.to_string()
+        }
+        BisectField::ProtocolVersion => {
+            bridgehub::get_ctm_chain_protocol_semver(client, non_zero(ctm()?)?, chain_id)?
+                .to_string()
+        }
+        BisectField::BaseToken => {
+            bridgehub::get_base_token(client, bridgehub, chain_id)?.to_string()
+        }
+        BisectField::SettlementChainId => {
+            bridgehub::get_settlement_layer(client, bridgehub, chain_id)?.to_string()
+        }
+        BisectField::TransactionFilterer => {
+            diamond::get_transaction_filterer(client, non_zero(chain_contract()?)?)?.to_string()
+        }
+    };
+    Ok(Some(value))
+}
+
+/// A zero address at an intermediate hop means the chain is not wired up yet.
+fn non_zero(address: Address) -> Result<Address, BridgehubError> {
+    if address.is_zero() {
+        return Err(BridgehubError::Decode(
+            "intermediate contract is not set".to_string(),
+        ));
+    }
+    Ok(address)
+}
+
+/// Outcome of a binary search between two blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectOutcome {
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+    /// First block holding `to_value` after a block that did not.
+    pub changed_at_block: Option<u64>,
+    pub probes: usize,
+}
+
+/// Binary-searches `from_block..=to_block` for the block at which `read`
+/// first returns the value it has at `to_block`.
+///
+/// Assumes the value changed once in the range; with several changes this
+/// still finds a block where it switched to the final value.
+pub fn bisect_blocks<F>(
+    from_block: u64,
+    to_block: u64,
+    mut read: F,
+) -> Result<BisectOutcome, BridgehubError>
+where
+    F: FnMut(u64) -> Result<Option<String>, BridgehubError>,
+{
+    let mut probes = 0;
+    let mut probe = |block| {
+        probes += 1;
+        read(block)
+    };
+
+    let to_value = probe(to_block)?;
+    let from_value = probe(from_block)?;
+    if from_value == to_value {
+        return Ok(BisectOutcome {
+            from_value,
+            to_value,
+            changed_at_block: None,
+            probes,
+        });
+    }
+
+    let (mut low, mut high) = (from_block, to_block);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if probe(mid)? == to_value {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Ok(BisectOutcome {
+        from_value,
+        to_value,
+        changed_at_block: Some(high),
+        probes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_first_block_with_the_final_value() {
+        let outcome = bisect_blocks(100, 1_000, |block| {
+            Ok(Some(if block >= 731 { "new" } else { "old" }.to_string()))
+        })
+        .expect("bisection should succeed");
+
+        assert_eq!(outcome.changed_at_block, Some(731));
+        assert_eq!(outcome.from_value.as_deref(), Some("old"));
+        assert_eq!(outcome.to_value.as_deref(), Some("new"));
+        assert!(outcome.probes <= 2 + 10);
+    }
+
+    #[test]
+    fn reports_unchanged_ranges() {
+        let outcome = bisect_blocks(1, 50, |_| Ok(Some("same".to_string())))
+            .expect("bisection should succeed");
+        assert_eq!(outcome.changed_at_block, None);
+        assert_eq!(outcome.probes, 2);
+    }
+
+    #[test]
+    fn treats_missing_values_as_a_distinct_state() {
+        let outcome = bisect_blocks(0, 64, |block| {
+            Ok((block >= 10).then(|| "0x0000000000000000000000000000000000000001".to_string()))
+        })
+        .expect("bisection should succeed");
+        assert_eq!(outcome.from_value, None);
+        assert_eq!(outcome.changed_at_block, Some(10));
+    }
+
+    #[test]
+    fn parses_field_names() {
+        assert_eq!(
+            "admin_owner".parse::<BisectField>(),
+            Ok(BisectField::AdminOwner)
+        );
+        assert!(
+            "owner"
+                .parse::<BisectField>()
+                .unwrap_err()
+                .contains("admin_owner")
+        );
+        for field in BisectField::ALL {
+            assert!(
+                crate::model::CHAIN_FIELDS.contains(&field.name()),
+                "{field}"
+            );
+        }
+    }
+}
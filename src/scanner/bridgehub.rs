@@ -1,9 +1,8 @@
-use std::str::FromStr;
-
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256, U256, address, keccak256};
 use alloy_sol_types::{SolCall, sol};
 use thiserror::Error;
 
+use crate::model::ProtocolSemver;
 use crate::rpc::{RpcClient, RpcError};
 
 sol! {
@@ -11,6 +10,7 @@ sol! {
     function chainTypeManager(uint256 chainId) external view returns (address ctm);
     function getZKChain(uint256 chainId) external view returns (address chainContract);
     function owner() external view returns (address ownerAddress);
+    function pendingOwner() external view returns (address pendingOwnerAddress);
     function isCustomSigningSetActive(address chainAddress) external view returns (bool);
     function getSigningThreshold(address chainAddress) external view returns (uint64);
     function getValidatorsCount(address chainAddress) external view returns (uint256);
@@ -21,8 +21,20 @@ sol! {
     function getSemverProtocolVersion() external view returns (uint32 major, uint32 minor, uint32 patch);
     function getChainAdmin(uint256 chainId) external view returns (address admin);
     function getProtocolVersion(uint256 chainId) external view returns (uint256 version);
+    function getRoleMemberCount(address chainAddress, bytes32 role) external view returns (uint256);
+    function getRoleMember(address chainAddress, bytes32 role, uint256 index) external view returns (address);
+    function executionDelay() external view returns (uint32);
+    function validators(uint256 chainId, address validator) external view returns (bool);
+    function baseTokenAssetId(uint256 chainId) external view returns (bytes32 assetId);
+    function baseToken(uint256 chainId) external view returns (address token);
+    function assetRouter() external view returns (address router);
+    function settlementLayer(uint256 chainId) external view returns (uint256 settlementLayerChainId);
+    function whitelistedSettlementLayers(uint256 chainId) external view returns (bool);
 }
 
+/// Bridgehub address on ZK chains acting as a settlement layer (gateway).
+pub const L2_BRIDGEHUB_ADDRESS: Address = address!("0x0000000000000000000000000000000000010002");
+
 #[derive(Debug, Error)]
 pub enum BridgehubError {
     #[error("rpc error: {0}")]
@@ -31,13 +43,59 @@ pub enum BridgehubError {
     Decode(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorTimelockKind {
+    PostV29,
+    Legacy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorTimelockRole {
+    Precommitter,
+    Committer,
+    Prover,
+    Executor,
+    Reverter,
+}
+
+impl ValidatorTimelockRole {
+    pub const ALL: [Self; 5] = [
+        Self::Precommitter,
+        Self::Committer,
+        Self::Prover,
+        Self::Executor,
+        Self::Reverter,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Precommitter => "precommitter",
+            Self::Committer => "committer",
+            Self::Prover => "prover",
+            Self::Executor => "executor",
+            Self::Reverter => "reverter",
+        }
+    }
+
+    /// AccessControl role identifier, `keccak256("<NAME>_ROLE")`.
+    pub fn role_id(self) -> B256 {
+        let label = match self {
+            Self::Precommitter => "PRECOMMITTER_ROLE",
+            Self::Committer => "COMMITTER_ROLE",
+            Self::Prover => "PROVER_ROLE",
+            Self::Executor => "EXECUTOR_ROLE",
+            Self::Reverter => "REVERTER_ROLE",
+        };
+        keccak256(label.as_bytes())
+    }
+}
+
 pub fn get_all_zk_chain_chain_ids(
     client: &dyn RpcClient,
-    bridgehub: &str,
+    bridgehub: Address,
 ) -> Result<Vec<u64>, BridgehubError> {
     let calldata = encode_get_all_zk_chain_chain_ids_calldata();
-    let response = client.eth_call(bridgehub, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, bridgehub, &calldata)?;
     let decoded = getAllZKChainChainIDsCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
 
@@ -49,86 +107,90 @@ pub fn get_all_zk_chain_chain_ids(
 
 pub fn get_chain_type_manager(
     client: &dyn RpcClient,
-    bridgehub: &str,
+    bridgehub: Address,
     chain_id: u64,
-) -> Result<String, BridgehubError> {
+) -> Result<Address, BridgehubError> {
     let calldata = encode_chain_type_manager_calldata(chain_id);
-    let response = client.eth_call(bridgehub, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, bridgehub, &calldata)?;
     let decoded = chainTypeManagerCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 pub fn get_zk_chain(
     client: &dyn RpcClient,
-    bridgehub: &str,
+    bridgehub: Address,
     chain_id: u64,
-) -> Result<String, BridgehubError> {
+) -> Result<Address, BridgehubError> {
     let calldata = encode_get_zk_chain_calldata(chain_id);
-    let response = client.eth_call(bridgehub, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, bridgehub, &calldata)?;
     let decoded = getZKChainCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 pub fn get_ctm_protocol_semver(
     client: &dyn RpcClient,
-    ctm: &str,
-) -> Result<String, BridgehubError> {
-    if let Ok((major, minor, patch)) = get_ctm_semver_components(client, ctm) {
-        return Ok(format!("{major}.{minor}.{patch}"));
+    ctm: Address,
+) -> Result<ProtocolSemver, BridgehubError> {
+    if let Ok(version) = get_ctm_semver_components(client, ctm) {
+        return Ok(version);
     }
 
     let raw = get_ctm_protocol_version_raw(client, ctm)?;
-    let (major, minor, patch) = decode_packed_semver(raw)?;
-    Ok(format!("{major}.{minor}.{patch}"))
+    decode_packed_semver(raw)
 }
 
 pub fn get_ctm_chain_admin(
     client: &dyn RpcClient,
-    ctm: &str,
+    ctm: Address,
     chain_id: u64,
-) -> Result<String, BridgehubError> {
+) -> Result<Address, BridgehubError> {
     let calldata = encode_get_chain_admin_calldata(chain_id);
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = getChainAdminCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 pub fn get_ctm_chain_protocol_semver(
     client: &dyn RpcClient,
-    ctm: &str,
+    ctm: Address,
     chain_id: u64,
-) -> Result<String, BridgehubError> {
+) -> Result<ProtocolSemver, BridgehubError> {
     let raw = get_ctm_chain_protocol_version_raw(client, ctm, chain_id)?;
-    let (major, minor, patch) = decode_packed_semver(raw)?;
-    Ok(format!("{major}.{minor}.{patch}"))
+    decode_packed_semver(raw)
 }
 
 pub fn get_contract_owner(
     client: &dyn RpcClient,
-    contract: &str,
-) -> Result<String, BridgehubError> {
+    contract: Address,
+) -> Result<Address, BridgehubError> {
     let calldata = encode_owner_calldata();
-    let response = client.eth_call(contract, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, contract, &calldata)?;
     let decoded = ownerCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
+}
+
+pub fn get_contract_pending_owner(
+    client: &dyn RpcClient,
+    contract: Address,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_pending_owner_calldata();
+    let bytes = call_contract(client, contract, &calldata)?;
+    let decoded = pendingOwnerCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
 }
 
 pub fn get_multisig_is_custom_signing_set_active(
     client: &dyn RpcClient,
-    multisig_committer: &str,
-    chain_contract: &str,
+    multisig_committer: Address,
+    chain_contract: Address,
 ) -> Result<bool, BridgehubError> {
-    let calldata = encode_is_custom_signing_set_active_calldata(chain_contract)?;
-    let response = client.eth_call(multisig_committer, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let calldata = encode_is_custom_signing_set_active_calldata(chain_contract);
+    let bytes = call_contract(client, multisig_committer, &calldata)?;
     let decoded = isCustomSigningSetActiveCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
     Ok(decoded)
@@ -136,12 +198,11 @@ pub fn get_multisig_is_custom_signing_set_active(
 
 pub fn get_multisig_signing_threshold(
     client: &dyn RpcClient,
-    multisig_committer: &str,
-    chain_contract: &str,
+    multisig_committer: Address,
+    chain_contract: Address,
 ) -> Result<u64, BridgehubError> {
-    let calldata = encode_get_signing_threshold_calldata(chain_contract)?;
-    let response = client.eth_call(multisig_committer, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let calldata = encode_get_signing_threshold_calldata(chain_contract);
+    let bytes = call_contract(client, multisig_committer, &calldata)?;
     let decoded = getSigningThresholdCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
     Ok(decoded)
@@ -149,12 +210,11 @@ pub fn get_multisig_signing_threshold(
 
 pub fn get_multisig_validators_count(
     client: &dyn RpcClient,
-    multisig_committer: &str,
-    chain_contract: &str,
+    multisig_committer: Address,
+    chain_contract: Address,
 ) -> Result<u64, BridgehubError> {
-    let calldata = encode_get_validators_count_calldata(chain_contract)?;
-    let response = client.eth_call(multisig_committer, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let calldata = encode_get_validators_count_calldata(chain_contract);
+    let bytes = call_contract(client, multisig_committer, &calldata)?;
     let decoded = getValidatorsCountCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
     u256_to_u64(decoded)
@@ -162,47 +222,169 @@ pub fn get_multisig_validators_count(
 
 pub fn get_multisig_validator_member(
     client: &dyn RpcClient,
-    multisig_committer: &str,
-    chain_contract: &str,
+    multisig_committer: Address,
+    chain_contract: Address,
     index: u64,
-) -> Result<String, BridgehubError> {
-    let calldata = encode_get_validators_member_calldata(chain_contract, index)?;
-    let response = client.eth_call(multisig_committer, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_get_validators_member_calldata(chain_contract, index);
+    let bytes = call_contract(client, multisig_committer, &calldata)?;
     let decoded = getValidatorsMemberCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 pub fn get_ctm_validator_timelock(
     client: &dyn RpcClient,
-    ctm: &str,
-) -> Result<String, BridgehubError> {
+    ctm: Address,
+) -> Result<Address, BridgehubError> {
+    resolve_ctm_validator_timelock(client, ctm).map(|(address, _)| address)
+}
+
+pub fn resolve_ctm_validator_timelock(
+    client: &dyn RpcClient,
+    ctm: Address,
+) -> Result<(Address, ValidatorTimelockKind), BridgehubError> {
     if let Ok(address) = get_ctm_validator_timelock_post_v29(client, ctm)
-        && !is_zero_address(&address)
+        && !address.is_zero()
     {
-        return Ok(address);
+        return Ok((address, ValidatorTimelockKind::PostV29));
     }
 
     get_ctm_validator_timelock_legacy(client, ctm)
+        .map(|address| (address, ValidatorTimelockKind::Legacy))
+}
+
+pub fn get_timelock_role_member_count(
+    client: &dyn RpcClient,
+    validator_timelock: Address,
+    chain_contract: Address,
+    role: ValidatorTimelockRole,
+) -> Result<u64, BridgehubError> {
+    let calldata = encode_get_role_member_count_calldata(chain_contract, role);
+    let bytes = call_contract(client, validator_timelock, &calldata)?;
+    let decoded = getRoleMemberCountCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    u256_to_u64(decoded)
+}
+
+pub fn get_timelock_role_member(
+    client: &dyn RpcClient,
+    validator_timelock: Address,
+    chain_contract: Address,
+    role: ValidatorTimelockRole,
+    index: u64,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_get_role_member_calldata(chain_contract, role, index);
+    let bytes = call_contract(client, validator_timelock, &calldata)?;
+    let decoded = getRoleMemberCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_timelock_execution_delay(
+    client: &dyn RpcClient,
+    validator_timelock: Address,
+) -> Result<u64, BridgehubError> {
+    let calldata = encode_execution_delay_calldata();
+    let bytes = call_contract(client, validator_timelock, &calldata)?;
+    let decoded = executionDelayCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(u64::from(decoded))
+}
+
+pub fn get_legacy_timelock_is_validator(
+    client: &dyn RpcClient,
+    validator_timelock: Address,
+    chain_id: u64,
+    validator: Address,
+) -> Result<bool, BridgehubError> {
+    let calldata = encode_legacy_validators_calldata(chain_id, validator);
+    let bytes = call_contract(client, validator_timelock, &calldata)?;
+    let decoded = validatorsCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_base_token_asset_id(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+) -> Result<String, BridgehubError> {
+    let calldata = encode_base_token_asset_id_calldata(chain_id);
+    let bytes = call_contract(client, bridgehub, &calldata)?;
+    let decoded = baseTokenAssetIdCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(format!("{decoded:#x}"))
+}
+
+pub fn get_base_token(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_base_token_calldata(chain_id);
+    let bytes = call_contract(client, bridgehub, &calldata)?;
+    let decoded = baseTokenCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_asset_router(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_asset_router_calldata();
+    let bytes = call_contract(client, bridgehub, &calldata)?;
+    let decoded = assetRouterCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_settlement_layer(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+) -> Result<u64, BridgehubError> {
+    let calldata = encode_settlement_layer_calldata(chain_id);
+    let bytes = call_contract(client, bridgehub, &calldata)?;
+    let decoded = settlementLayerCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    u256_to_u64(decoded)
+}
+
+pub fn get_is_whitelisted_settlement_layer(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+) -> Result<bool, BridgehubError> {
+    let calldata = encode_whitelisted_settlement_layers_calldata(chain_id);
+    let bytes = call_contract(client, bridgehub, &calldata)?;
+    let decoded = whitelistedSettlementLayersCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
 }
 
 fn get_ctm_semver_components(
     client: &dyn RpcClient,
-    ctm: &str,
-) -> Result<(u32, u32, u32), BridgehubError> {
+    ctm: Address,
+) -> Result<ProtocolSemver, BridgehubError> {
     let calldata = encode_get_semver_protocol_version_calldata();
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = getSemverProtocolVersionCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok((decoded.major, decoded.minor, decoded.patch))
+    Ok(ProtocolSemver::new(
+        decoded.major,
+        decoded.minor,
+        decoded.patch,
+    ))
 }
 
-fn get_ctm_protocol_version_raw(client: &dyn RpcClient, ctm: &str) -> Result<U256, BridgehubError> {
+fn get_ctm_protocol_version_raw(
+    client: &dyn RpcClient,
+    ctm: Address,
+) -> Result<U256, BridgehubError> {
     let calldata = encode_protocol_version_calldata();
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = protocolVersionCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
     Ok(decoded)
@@ -210,12 +392,11 @@ fn get_ctm_protocol_version_raw(client: &dyn RpcClient, ctm: &str) -> Result<U25
 
 fn get_ctm_chain_protocol_version_raw(
     client: &dyn RpcClient,
-    ctm: &str,
+    ctm: Address,
     chain_id: u64,
 ) -> Result<U256, BridgehubError> {
     let calldata = encode_get_chain_protocol_version_calldata(chain_id);
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = getProtocolVersionCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
     Ok(decoded)
@@ -223,26 +404,24 @@ fn get_ctm_chain_protocol_version_raw(
 
 fn get_ctm_validator_timelock_post_v29(
     client: &dyn RpcClient,
-    ctm: &str,
-) -> Result<String, BridgehubError> {
+    ctm: Address,
+) -> Result<Address, BridgehubError> {
     let calldata = encode_validator_timelock_post_v29_calldata();
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = validatorTimelockPostV29Call::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 fn get_ctm_validator_timelock_legacy(
     client: &dyn RpcClient,
-    ctm: &str,
-) -> Result<String, BridgehubError> {
+    ctm: Address,
+) -> Result<Address, BridgehubError> {
     let calldata = encode_validator_timelock_calldata();
-    let response = client.eth_call(ctm, &calldata)?;
-    let bytes = decode_hex_data(&response)?;
+    let bytes = call_contract(client, ctm, &calldata)?;
     let decoded = validatorTimelockCall::abi_decode_returns(&bytes)
         .map_err(|err| BridgehubError::Decode(err.to_string()))?;
-    Ok(format!("{decoded:#x}"))
+    Ok(decoded)
 }
 
 pub fn encode_get_all_zk_chain_chain_ids_calldata() -> String {
@@ -272,50 +451,41 @@ pub fn encode_owner_calldata() -> String {
     format!("0x{}", hex::encode(ownerCall {}.abi_encode()))
 }
 
-pub fn encode_is_custom_signing_set_active_calldata(
-    chain_contract: &str,
-) -> Result<String, BridgehubError> {
-    let chain_address = parse_address(chain_contract)?;
+pub fn encode_pending_owner_calldata() -> String {
+    format!("0x{}", hex::encode(pendingOwnerCall {}.abi_encode()))
+}
+
+pub fn encode_is_custom_signing_set_active_calldata(chain_contract: Address) -> String {
     let calldata = isCustomSigningSetActiveCall {
-        chainAddress: chain_address,
+        chainAddress: chain_contract,
     }
     .abi_encode();
-    Ok(format!("0x{}", hex::encode(calldata)))
+    format!("0x{}", hex::encode(calldata))
 }
 
-pub fn encode_get_signing_threshold_calldata(
-    chain_contract: &str,
-) -> Result<String, BridgehubError> {
-    let chain_address = parse_address(chain_contract)?;
+pub fn encode_get_signing_threshold_calldata(chain_contract: Address) -> String {
     let calldata = getSigningThresholdCall {
-        chainAddress: chain_address,
+        chainAddress: chain_contract,
     }
     .abi_encode();
-    Ok(format!("0x{}", hex::encode(calldata)))
+    format!("0x{}", hex::encode(calldata))
 }
 
-pub fn encode_get_validators_count_calldata(
-    chain_contract: &str,
-) -> Result<String, BridgehubError> {
-    let chain_address = parse_address(chain_contract)?;
+pub fn encode_get_validators_count_calldata(chain_contract: Address) -> String {
     let calldata = getValidatorsCountCall {
-        chainAddress: chain_address,
+        chainAddress: chain_contract,
     }
     .abi_encode();
-    Ok(format!("0x{}", hex::encode(calldata)))
+    format!("0x{}", hex::encode(calldata))
 }
 
-pub fn encode_get_validators_member_calldata(
-    chain_contract: &str,
-    index: u64,
-) -> Result<String, BridgehubError> {
-    let chain_address = parse_address(chain_contract)?;
+pub fn encode_get_validators_member_calldata(chain_contract: Address, index: u64) -> String {
     let calldata = getValidatorsMemberCall {
-        chainAddress: chain_address,
+        chainAddress: chain_contract,
         index: U256::from(index),
     }
     .abi_encode();
-    Ok(format!("0x{}", hex::encode(calldata)))
+    format!("0x{}", hex::encode(calldata))
 }
 
 pub fn encode_validator_timelock_calldata() -> String {
@@ -356,7 +526,82 @@ pub fn encode_get_chain_protocol_version_calldata(chain_id: u64) -> String {
     format!("0x{}", hex::encode(calldata))
 }
 
-fn decode_hex_data(value: &str) -> Result<Vec<u8>, BridgehubError> {
+pub fn encode_get_role_member_count_calldata(
+    chain_contract: Address,
+    role: ValidatorTimelockRole,
+) -> String {
+    let calldata = getRoleMemberCountCall {
+        chainAddress: chain_contract,
+        role: role.role_id(),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_get_role_member_calldata(
+    chain_contract: Address,
+    role: ValidatorTimelockRole,
+    index: u64,
+) -> String {
+    let calldata = getRoleMemberCall {
+        chainAddress: chain_contract,
+        role: role.role_id(),
+        index: U256::from(index),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_execution_delay_calldata() -> String {
+    format!("0x{}", hex::encode(executionDelayCall {}.abi_encode()))
+}
+
+pub fn encode_legacy_validators_calldata(chain_id: u64, validator: Address) -> String {
+    let calldata = validatorsCall {
+        chainId: U256::from(chain_id),
+        validator,
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_base_token_asset_id_calldata(chain_id: u64) -> String {
+    let calldata = baseTokenAssetIdCall {
+        chainId: U256::from(chain_id),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_base_token_calldata(chain_id: u64) -> String {
+    let calldata = baseTokenCall {
+        chainId: U256::from(chain_id),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_asset_router_calldata() -> String {
+    format!("0x{}", hex::encode(assetRouterCall {}.abi_encode()))
+}
+
+pub fn encode_settlement_layer_calldata(chain_id: u64) -> String {
+    let calldata = settlementLayerCall {
+        chainId: U256::from(chain_id),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub fn encode_whitelisted_settlement_layers_calldata(chain_id: u64) -> String {
+    let calldata = whitelistedSettlementLayersCall {
+        chainId: U256::from(chain_id),
+    }
+    .abi_encode();
+    format!("0x{}", hex::encode(calldata))
+}
+
+pub(crate) fn decode_hex_data(value: &str) -> Result<Vec<u8>, BridgehubError> {
     let stripped = value
         .strip_prefix("0x")
         .ok_or_else(|| BridgehubError::Decode("eth_call result was not 0x-prefixed".to_string()))?;
@@ -364,12 +609,12 @@ fn decode_hex_data(value: &str) -> Result<Vec<u8>, BridgehubError> {
     hex::decode(stripped).map_err(|err| BridgehubError::Decode(err.to_string()))
 }
 
-fn u256_to_u64(value: U256) -> Result<u64, BridgehubError> {
+pub(crate) fn u256_to_u64(value: U256) -> Result<u64, BridgehubError> {
     u64::try_from(value)
         .map_err(|_| BridgehubError::Decode("decoded chain id does not fit into u64".to_string()))
 }
 
-fn decode_packed_semver(value: U256) -> Result<(u32, u32, u32), BridgehubError> {
+pub(crate) fn decode_packed_semver(value: U256) -> Result<ProtocolSemver, BridgehubError> {
     let mask = U256::from(u32::MAX as u64);
     let major_u64 = ((value >> 64usize) & mask).to::<u64>();
     let minor_u64 = ((value >> 32usize) & mask).to::<u64>();
@@ -382,15 +627,17 @@ fn decode_packed_semver(value: U256) -> Result<(u32, u32, u32), BridgehubError>
     let patch = u32::try_from(patch_u64)
         .map_err(|_| BridgehubError::Decode("semver patch does not fit into u32".to_string()))?;
 
-    Ok((major, minor, patch))
+    Ok(ProtocolSemver::new(major, minor, patch))
 }
 
-fn is_zero_address(address: &str) -> bool {
-    address == "0x0000000000000000000000000000000000000000"
-}
-
-fn parse_address(value: &str) -> Result<Address, BridgehubError> {
-    Address::from_str(value).map_err(|err| BridgehubError::Decode(err.to_string()))
+/// `eth_call` to a typed contract address, returning the raw return data.
+pub(crate) fn call_contract(
+    client: &dyn RpcClient,
+    to: Address,
+    calldata: &str,
+) -> Result<Vec<u8>, BridgehubError> {
+    let response = client.eth_call(&format!("{to:#x}"), calldata)?;
+    decode_hex_data(&response)
 }
 
 #[cfg(test)]
@@ -469,6 +716,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_execution_delay_calldata() {
+        let data = encode_execution_delay_calldata();
+        assert_eq!(data, "0x8b257989");
+    }
+
+    #[test]
+    fn encodes_get_role_member_count_calldata() {
+        let data = encode_get_role_member_count_calldata(
+            address!("0xcccccccccccccccccccccccccccccccccccccccc"),
+            ValidatorTimelockRole::Committer,
+        );
+        assert_eq!(
+            data,
+            format!(
+                "0x{}000000000000000000000000cccccccccccccccccccccccccccccccccccccccc{}",
+                hex::encode(&keccak256(b"getRoleMemberCount(address,bytes32)")[..4]),
+                hex::encode(keccak256(b"COMMITTER_ROLE"))
+            )
+        );
+    }
+
+    #[test]
+    fn encodes_legacy_validators_calldata() {
+        let data = encode_legacy_validators_calldata(
+            324,
+            address!("0x1111111111111111111111111111111111111111"),
+        );
+        assert_eq!(
+            data,
+            format!(
+                "0x{}0000000000000000000000000000000000000000000000000000000000000144\
+                 0000000000000000000000001111111111111111111111111111111111111111",
+                hex::encode(&keccak256(b"validators(uint256,address)")[..4])
+            )
+        );
+    }
+
+    #[test]
+    fn validator_timelock_role_ids_are_role_name_hashes() {
+        assert_eq!(
+            ValidatorTimelockRole::Prover.role_id(),
+            keccak256(b"PROVER_ROLE")
+        );
+        assert_eq!(ValidatorTimelockRole::ALL.len(), 5);
+        assert_eq!(ValidatorTimelockRole::Precommitter.name(), "precommitter");
+    }
+
+    #[test]
+    fn encodes_base_token_calldata() {
+        assert_eq!(
+            encode_base_token_asset_id_calldata(324),
+            format!(
+                "0x{}0000000000000000000000000000000000000000000000000000000000000144",
+                hex::encode(&keccak256(b"baseTokenAssetId(uint256)")[..4])
+            )
+        );
+        assert_eq!(
+            encode_base_token_calldata(324),
+            format!(
+                "0x{}0000000000000000000000000000000000000000000000000000000000000144",
+                hex::encode(&keccak256(b"baseToken(uint256)")[..4])
+            )
+        );
+    }
+
+    #[test]
+    fn encodes_settlement_layer_calldata() {
+        assert_eq!(
+            encode_settlement_layer_calldata(324),
+            format!(
+                "0x{}0000000000000000000000000000000000000000000000000000000000000144",
+                hex::encode(&keccak256(b"settlementLayer(uint256)")[..4])
+            )
+        );
+        assert_eq!(
+            encode_whitelisted_settlement_layers_calldata(9075),
+            format!(
+                "0x{}0000000000000000000000000000000000000000000000000000000000002373",
+                hex::encode(&keccak256(b"whitelistedSettlementLayers(uint256)")[..4])
+            )
+        );
+    }
+
     #[test]
     fn decodes_get_all_chain_ids_return() {
         let data = "0x0000000000000000000000000000000000000000000000000000000000000020\
@@ -526,6 +857,6 @@ mod tests {
     fn decodes_packed_semver_value() {
         let packed = (U256::from(1u32) << 64) | (U256::from(29u32) << 32) | U256::from(4u32);
         let decoded = decode_packed_semver(packed).expect("packed decode should succeed");
-        assert_eq!(decoded, (1, 29, 4));
+        assert_eq!(decoded, ProtocolSemver::new(1, 29, 4));
     }
 }
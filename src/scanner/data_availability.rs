@@ -0,0 +1,189 @@
+use std::{fmt, str::FromStr};
+
+use alloy_primitives::Address;
+
+use crate::model::DataAvailability;
+
+/// DA layer a chain's L1 DA validator is known to attest to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaLayer {
+    Rollup,
+    Avail,
+    Celestia,
+    EigenDa,
+    NoDa,
+}
+
+impl DaLayer {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rollup => "rollup (calldata/blobs)",
+            Self::Avail => "validium (Avail)",
+            Self::Celestia => "validium (Celestia)",
+            Self::EigenDa => "validium (EigenDA)",
+            Self::NoDa => "validium (no DA)",
+        }
+    }
+
+    fn expected_pricing_mode(self) -> &'static str {
+        match self {
+            Self::Rollup => PRICING_MODE_ROLLUP,
+            _ => PRICING_MODE_VALIDIUM,
+        }
+    }
+}
+
+impl fmt::Display for DaLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for DaLayer {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "rollup" => Ok(Self::Rollup),
+            "avail" => Ok(Self::Avail),
+            "celestia" => Ok(Self::Celestia),
+            "eigenda" => Ok(Self::EigenDa),
+            "no-da" | "validium" => Ok(Self::NoDa),
+            other => Err(format!(
+                "unknown DA layer `{other}` (expected rollup, avail, celestia, eigenda or no-da)"
+            )),
+        }
+    }
+}
+
+/// An L1 DA validator deployment with a known DA layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownDaValidator {
+    pub address: Address,
+    pub layer: DaLayer,
+}
+
+pub const PRICING_MODE_ROLLUP: &str = "rollup";
+pub const PRICING_MODE_VALIDIUM: &str = "validium";
+
+pub fn pricing_mode_name(mode: u8) -> Option<&'static str> {
+    match mode {
+        0 => Some(PRICING_MODE_ROLLUP),
+        1 => Some(PRICING_MODE_VALIDIUM),
+        _ => None,
+    }
+}
+
+/// Classifies a chain's DA setup from its pricing mode and L1 DA validator.
+///
+/// Known validators take precedence; otherwise rollup-priced chains are
+/// assumed to post calldata/blobs and validium chains are left unclassified.
+pub fn classify_data_availability(
+    data_availability: &DataAvailability,
+    known: &[KnownDaValidator],
+) -> Option<String> {
+    let l1_validator = data_availability.l1_validator;
+    if let Some(l1_validator) = l1_validator
+        && let Some(entry) = known.iter().find(|entry| entry.address == l1_validator)
+    {
+        return Some(entry.layer.label().to_string());
+    }
+
+    if l1_validator.is_none() && data_availability.pricing_mode.is_some() {
+        return Some("no DA validator set".to_string());
+    }
+
+    match data_availability.pricing_mode.as_deref() {
+        Some(PRICING_MODE_ROLLUP) => Some(DaLayer::Rollup.label().to_string()),
+        Some(PRICING_MODE_VALIDIUM) => Some("validium (unknown DA layer)".to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the known layer whose pricing mode disagrees with the chain's.
+pub fn pricing_mode_mismatch(
+    data_availability: &DataAvailability,
+    known: &[KnownDaValidator],
+) -> Option<DaLayer> {
+    let l1_validator = data_availability.l1_validator?;
+    let pricing_mode = data_availability.pricing_mode.as_deref()?;
+    let entry = known.iter().find(|entry| entry.address == l1_validator)?;
+    (entry.layer.expected_pricing_mode() != pricing_mode).then_some(entry.layer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    fn data_availability(pricing_mode: &str, l1_validator: Option<Address>) -> DataAvailability {
+        DataAvailability {
+            pricing_mode: Some(pricing_mode.to_string()),
+            l1_validator,
+            l2_validator: None,
+            classification: None,
+        }
+    }
+
+    #[test]
+    fn classifies_known_validator_first() {
+        let known = vec![KnownDaValidator {
+            address: address!("0x00000000000000000000000000000000000000a1"),
+            layer: DaLayer::Avail,
+        }];
+        let da = data_availability(
+            PRICING_MODE_VALIDIUM,
+            Some(address!("0x00000000000000000000000000000000000000a1")),
+        );
+        assert_eq!(
+            classify_data_availability(&da, &known).as_deref(),
+            Some("validium (Avail)")
+        );
+        assert_eq!(pricing_mode_mismatch(&da, &known), None);
+    }
+
+    #[test]
+    fn falls_back_to_pricing_mode() {
+        let rollup = data_availability(
+            PRICING_MODE_ROLLUP,
+            Some(address!("0x00000000000000000000000000000000000000b1")),
+        );
+        let validium = data_availability(
+            PRICING_MODE_VALIDIUM,
+            Some(address!("0x00000000000000000000000000000000000000b1")),
+        );
+        assert_eq!(
+            classify_data_availability(&rollup, &[]).as_deref(),
+            Some("rollup (calldata/blobs)")
+        );
+        assert_eq!(
+            classify_data_availability(&validium, &[]).as_deref(),
+            Some("validium (unknown DA layer)")
+        );
+        assert_eq!(
+            classify_data_availability(&data_availability(PRICING_MODE_ROLLUP, None), &[])
+                .as_deref(),
+            Some("no DA validator set")
+        );
+    }
+
+    #[test]
+    fn flags_pricing_mode_mismatch() {
+        let known = vec![KnownDaValidator {
+            address: address!("0x00000000000000000000000000000000000000c1"),
+            layer: DaLayer::Celestia,
+        }];
+        let da = data_availability(
+            PRICING_MODE_ROLLUP,
+            Some(address!("0x00000000000000000000000000000000000000c1")),
+        );
+        assert_eq!(pricing_mode_mismatch(&da, &known), Some(DaLayer::Celestia));
+    }
+
+    #[test]
+    fn parses_da_layer_names() {
+        assert_eq!("EigenDA".parse::<DaLayer>(), Ok(DaLayer::EigenDa));
+        assert_eq!("no-da".parse::<DaLayer>(), Ok(DaLayer::NoDa));
+        assert!("blobs".parse::<DaLayer>().is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::str::FromStr;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{SolCall, sol};
+
+use crate::model::FeeParams;
+use crate::rpc::RpcClient;
+
+use super::bridgehub::{BridgehubError, call_contract, u256_to_u64};
+use super::data_availability::pricing_mode_name;
+
+/// Storage slot of `ZKChainStorage.feeParams`, which has no public getter.
+pub const FEE_PARAMS_STORAGE_SLOT: u64 = 38;
+
+sol! {
+    function baseTokenGasPriceMultiplierNominator() external view returns (uint128);
+    function baseTokenGasPriceMultiplierDenominator() external view returns (uint128);
+    function getPubdataPricingMode() external view returns (uint8 mode);
+    function getDAValidatorPair() external view returns (address l1DAValidator, address l2DAValidator);
+    function getSettlementLayer() external view returns (address settlementLayer);
+    function getPriorityTxMaxGasLimit() external view returns (uint256 limit);
+    function getTransactionFilterer() external view returns (address filterer);
+    function getTotalBatchesCommitted() external view returns (uint256);
+    function getTotalBatchesVerified() external view returns (uint256);
+    function getTotalBatchesExecuted() external view returns (uint256);
+}
+
+pub fn get_base_token_gas_price_multiplier(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<(u128, u128), BridgehubError> {
+    let calldata = encode_base_token_gas_price_multiplier_nominator_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let nominator = baseTokenGasPriceMultiplierNominatorCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+
+    let calldata = encode_base_token_gas_price_multiplier_denominator_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let denominator = baseTokenGasPriceMultiplierDenominatorCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+
+    Ok((nominator, denominator))
+}
+
+pub fn get_pubdata_pricing_mode(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<u8, BridgehubError> {
+    let calldata = encode_get_pubdata_pricing_mode_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let decoded = getPubdataPricingModeCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_da_validator_pair(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<(Address, Address), BridgehubError> {
+    let calldata = encode_get_da_validator_pair_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let decoded = getDAValidatorPairCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok((decoded.l1DAValidator, decoded.l2DAValidator))
+}
+
+pub fn get_settlement_layer(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_get_settlement_layer_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let decoded = getSettlementLayerCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_fee_params(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<FeeParams, BridgehubError> {
+    let slot = format!("{:#x}", U256::from(FEE_PARAMS_STORAGE_SLOT));
+    let response = client.eth_get_storage_at(&format!("{chain_contract:#x}"), &slot)?;
+    let word = U256::from_str(&response).map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    decode_fee_params(word)
+}
+
+pub fn get_priority_tx_max_gas_limit(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<u64, BridgehubError> {
+    let calldata = encode_get_priority_tx_max_gas_limit_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let decoded = getPriorityTxMaxGasLimitCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    u256_to_u64(decoded)
+}
+
+pub fn get_transaction_filterer(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_get_transaction_filterer_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let decoded = getTransactionFiltererCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+/// Batch counters `(committed, proven, executed)` of the diamond.
+pub fn get_total_batches(
+    client: &dyn RpcClient,
+    chain_contract: Address,
+) -> Result<(u64, u64, u64), BridgehubError> {
+    let calldata = encode_get_total_batches_committed_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let committed = getTotalBatchesCommittedCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+
+    let calldata = encode_get_total_batches_verified_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let proven = getTotalBatchesVerifiedCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+
+    let calldata = encode_get_total_batches_executed_calldata();
+    let bytes = call_contract(client, chain_contract, &calldata)?;
+    let executed = getTotalBatchesExecutedCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+
+    Ok((
+        u256_to_u64(committed)?,
+        u256_to_u64(proven)?,
+        u256_to_u64(executed)?,
+    ))
+}
+
+/// Unpacks `FeeParams { pubdataPricingMode: uint8, batchOverheadL1Gas: uint32,
+/// maxPubdataPerBatch: uint32, maxL2GasPerBatch: uint32, priorityTxMaxPubdata: uint32,
+/// minimalL2GasPrice: uint64 }` from its single storage word (low-order first).
+pub fn decode_fee_params(word: U256) -> Result<FeeParams, BridgehubError> {
+    let field = |offset: usize, bits: usize| -> u64 {
+        ((word >> offset) & ((U256::from(1u8) << bits) - U256::from(1u8))).to::<u64>()
+    };
+
+    let mode = field(0, 8) as u8;
+    let pubdata_pricing_mode = pricing_mode_name(mode)
+        .ok_or_else(|| BridgehubError::Decode(format!("unknown pubdata pricing mode {mode}")))?;
+
+    Ok(FeeParams {
+        pubdata_pricing_mode: pubdata_pricing_mode.to_string(),
+        batch_overhead_l1_gas: field(8, 32) as u32,
+        max_pubdata_per_batch: field(40, 32) as u32,
+        max_l2_gas_per_batch: field(72, 32) as u32,
+        priority_tx_max_pubdata: field(104, 32) as u32,
+        minimal_l2_gas_price: field(136, 64),
+    })
+}
+
+pub fn encode_base_token_gas_price_multiplier_nominator_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(baseTokenGasPriceMultiplierNominatorCall {}.abi_encode())
+    )
+}
+
+pub fn encode_base_token_gas_price_multiplier_denominator_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(baseTokenGasPriceMultiplierDenominatorCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_pubdata_pricing_mode_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getPubdataPricingModeCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_da_validator_pair_calldata() -> String {
+    format!("0x{}", hex::encode(getDAValidatorPairCall {}.abi_encode()))
+}
+
+pub fn encode_get_settlement_layer_calldata() -> String {
+    format!("0x{}", hex::encode(getSettlementLayerCall {}.abi_encode()))
+}
+
+pub fn encode_get_priority_tx_max_gas_limit_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getPriorityTxMaxGasLimitCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_transaction_filterer_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getTransactionFiltererCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_total_batches_committed_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getTotalBatchesCommittedCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_total_batches_verified_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getTotalBatchesVerifiedCall {}.abi_encode())
+    )
+}
+
+pub fn encode_get_total_batches_executed_calldata() -> String {
+    format!(
+        "0x{}",
+        hex::encode(getTotalBatchesExecutedCall {}.abi_encode())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::bridgehub::decode_hex_data;
+
+    #[test]
+    fn encodes_base_token_gas_price_multiplier_calldata() {
+        assert_eq!(
+            encode_base_token_gas_price_multiplier_nominator_calldata(),
+            "0xea6c029c"
+        );
+        assert_eq!(
+            encode_base_token_gas_price_multiplier_denominator_calldata(),
+            "0x1de72e34"
+        );
+    }
+
+    #[test]
+    fn encodes_data_availability_calldata() {
+        assert_eq!(encode_get_pubdata_pricing_mode_calldata(), "0x06d49e5b");
+        assert_eq!(encode_get_da_validator_pair_calldata(), "0x5a590335");
+    }
+
+    #[test]
+    fn encodes_total_batches_calldata() {
+        assert_eq!(encode_get_total_batches_committed_calldata(), "0xdb1f0bf9");
+        assert_eq!(encode_get_total_batches_verified_calldata(), "0xef3f0bae");
+        assert_eq!(encode_get_total_batches_executed_calldata(), "0xb8c2f66f");
+    }
+
+    #[test]
+    fn encodes_get_settlement_layer_calldata() {
+        assert_eq!(encode_get_settlement_layer_calldata(), "0x6a27e8b5");
+    }
+
+    #[test]
+    fn decodes_da_validator_pair_return() {
+        let data = "0x\
+                    000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+                    000000000000000000000000bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let bytes = decode_hex_data(data).expect("hex decode should succeed");
+        let decoded =
+            getDAValidatorPairCall::abi_decode_returns(&bytes).expect("abi decode should work");
+        assert_eq!(
+            format!("{:#x}", decoded.l1DAValidator),
+            "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+        );
+        assert_eq!(
+            format!("{:#x}", decoded.l2DAValidator),
+            "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+        );
+    }
+
+    #[test]
+    fn encodes_fee_getter_calldata() {
+        assert_eq!(
+            encode_get_priority_tx_max_gas_limit_calldata(),
+            "0x0ec6b0b7"
+        );
+        assert_eq!(encode_get_transaction_filterer_calldata(), "0x22c5cf23");
+    }
+
+    #[test]
+    fn decodes_packed_fee_params() {
+        let word = U256::from(1u8)
+            | (U256::from(1_000_000u32) << 8)
+            | (U256::from(120_000u32) << 40)
+            | (U256::from(80_000_000u32) << 72)
+            | (U256::from(99_000u32) << 104)
+            | (U256::from(250_000_000u64) << 136);
+
+        let fee_params = decode_fee_params(word).expect("fee params should decode");
+        assert_eq!(
+            fee_params,
+            FeeParams {
+                pubdata_pricing_mode: "validium".to_string(),
+                batch_overhead_l1_gas: 1_000_000,
+                max_pubdata_per_batch: 120_000,
+                max_l2_gas_per_batch: 80_000_000,
+                priority_tx_max_pubdata: 99_000,
+                minimal_l2_gas_price: 250_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_pricing_mode_in_fee_params() {
+        assert!(decode_fee_params(U256::from(7u8)).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{SolEvent, sol};
+
+use crate::model::{BridgehubHistory, ChainRegistration, CtmRegistryEvent};
+use crate::rpc::{RpcClient, RpcLog};
+
+use super::bridgehub::{BridgehubError, decode_hex_data, u256_to_u64};
+use super::logs::fetch_logs;
+
+sol! {
+    event NewChain(uint256 indexed chainId, address chainTypeManager, address indexed chainGovernance);
+    event ChainTypeManagerAdded(address indexed chainTypeManager);
+    event ChainTypeManagerRemoved(address indexed chainTypeManager);
+    event StateTransitionManagerAdded(address indexed stateTransitionManager);
+    event StateTransitionManagerRemoved(address indexed stateTransitionManager);
+}
+
+/// Walks Bridgehub chain-registration and CTM registry events from
+/// `from_block` to `to_block`. Chains registered in that window that are not
+/// in `current_chain_ids` are marked as removed.
+pub fn index_bridgehub_history(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+    current_chain_ids: &[u64],
+) -> Result<BridgehubHistory, BridgehubError> {
+    let logs = fetch_logs(
+        client,
+        bridgehub,
+        &bridgehub_history_event_signatures(),
+        from_block,
+        to_block,
+        max_block_range,
+    )?;
+
+    let mut chain_registrations = Vec::new();
+    let mut ctm_events = Vec::new();
+    for log in &logs {
+        let topics = log_topics(log)?;
+        let data = decode_hex_data(&log.data)?;
+        let Some(topic0) = topics.first() else {
+            continue;
+        };
+
+        if *topic0 == NewChain::SIGNATURE_HASH {
+            let event = NewChain::decode_raw_log(topics.iter().copied(), &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            let chain_id = u256_to_u64(event.chainId)?;
+            chain_registrations.push(ChainRegistration {
+                chain_id,
+                ctm: event.chainTypeManager,
+                chain_governance: event.chainGovernance,
+                block_number: log.block_number,
+                transaction_hash: log.transaction_hash.clone(),
+                removed: !current_chain_ids.contains(&chain_id),
+            });
+            continue;
+        }
+
+        let (ctm, added) = if *topic0 == ChainTypeManagerAdded::SIGNATURE_HASH {
+            let event = ChainTypeManagerAdded::decode_raw_log(topics.iter().copied(), &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            (event.chainTypeManager, true)
+        } else if *topic0 == ChainTypeManagerRemoved::SIGNATURE_HASH {
+            let event = ChainTypeManagerRemoved::decode_raw_log(topics.iter().copied(), &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            (event.chainTypeManager, false)
+        } else if *topic0 == StateTransitionManagerAdded::SIGNATURE_HASH {
+            let event = StateTransitionManagerAdded::decode_raw_log(topics.iter().copied(), &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            (event.stateTransitionManager, true)
+        } else if *topic0 == StateTransitionManagerRemoved::SIGNATURE_HASH {
+            let event =
+                StateTransitionManagerRemoved::decode_raw_log(topics.iter().copied(), &data)
+                    .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            (event.stateTransitionManager, false)
+        } else {
+            continue;
+        };
+        ctm_events.push(CtmRegistryEvent {
+            ctm,
+            added,
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash.clone(),
+        });
+    }
+
+    Ok(BridgehubHistory {
+        from_block,
+        to_block,
+        chain_registrations,
+        ctm_events,
+    })
+}
+
+/// `topic0` values of every event the history indexer understands, covering
+/// both the current (`ChainTypeManager*`) and pre-v26 (`StateTransitionManager*`) names.
+pub fn bridgehub_history_event_signatures() -> Vec<String> {
+    [
+        NewChain::SIGNATURE_HASH,
+        ChainTypeManagerAdded::SIGNATURE_HASH,
+        ChainTypeManagerRemoved::SIGNATURE_HASH,
+        StateTransitionManagerAdded::SIGNATURE_HASH,
+        StateTransitionManagerRemoved::SIGNATURE_HASH,
+    ]
+    .iter()
+    .map(|hash| format!("{hash:#x}"))
+    .collect()
+}
+
+pub(crate) fn log_topics(log: &RpcLog) -> Result<Vec<B256>, BridgehubError> {
+    log.topics
+        .iter()
+        .map(|topic| B256::from_str(topic).map_err(|err| BridgehubError::Decode(err.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{U256, address};
+
+    use super::*;
+    use crate::rpc::{LogFilter, RpcError};
+
+    struct ScriptedLogs(Vec<RpcLog>);
+
+    impl RpcClient for ScriptedLogs {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|log| (filter.from_block..=filter.to_block).contains(&log.block_number))
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn raw_log(block_number: u64, topics: Vec<B256>, data: Vec<u8>) -> RpcLog {
+        RpcLog {
+            address: "0x0000000000000000000000000000000000000001".to_string(),
+            topics: topics.iter().map(|topic| format!("{topic:#x}")).collect(),
+            data: format!("0x{}", hex::encode(data)),
+            block_number,
+            transaction_hash: format!("0x{block_number:064x}"),
+            log_index: 0,
+        }
+    }
+
+    fn new_chain_log(block_number: u64, chain_id: u64, ctm: Address) -> RpcLog {
+        let event = NewChain {
+            chainId: U256::from(chain_id),
+            chainTypeManager: ctm,
+            chainGovernance: Address::repeat_byte(0x99),
+        };
+        let encoded = event.encode_log_data();
+        raw_log(
+            block_number,
+            encoded.topics().to_vec(),
+            encoded.data.to_vec(),
+        )
+    }
+
+    #[test]
+    fn indexes_chain_registrations_and_ctm_events() {
+        let ctm = Address::repeat_byte(0xaa);
+        let ctm_added = ChainTypeManagerAdded {
+            chainTypeManager: ctm,
+        }
+        .encode_log_data();
+        let legacy_removed = StateTransitionManagerRemoved {
+            stateTransitionManager: Address::repeat_byte(0xbb),
+        }
+        .encode_log_data();
+        let rpc = ScriptedLogs(vec![
+            raw_log(10, ctm_added.topics().to_vec(), ctm_added.data.to_vec()),
+            new_chain_log(20, 324, ctm),
+            new_chain_log(30, 325, ctm),
+            raw_log(
+                40,
+                legacy_removed.topics().to_vec(),
+                legacy_removed.data.to_vec(),
+            ),
+        ]);
+
+        let history = index_bridgehub_history(
+            &rpc,
+            address!("0x0000000000000000000000000000000000000001"),
+            0,
+            100,
+            25,
+            &[324],
+        )
+        .expect("history should index");
+
+        assert_eq!(history.from_block, 0);
+        assert_eq!(history.to_block, 100);
+        assert_eq!(
+            history.chain_registrations,
+            vec![
+                ChainRegistration {
+                    chain_id: 324,
+                    ctm: address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                    chain_governance: address!("0x9999999999999999999999999999999999999999"),
+                    block_number: 20,
+                    transaction_hash: format!("0x{:064x}", 20),
+                    removed: false,
+                },
+                ChainRegistration {
+                    chain_id: 325,
+                    ctm: address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                    chain_governance: address!("0x9999999999999999999999999999999999999999"),
+                    block_number: 30,
+                    transaction_hash: format!("0x{:064x}", 30),
+                    removed: true,
+                },
+            ]
+        );
+        assert_eq!(
+            history.ctm_events,
+            vec![
+                CtmRegistryEvent {
+                    ctm: address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                    added: true,
+                    block_number: 10,
+                    transaction_hash: format!("0x{:064x}", 10),
+                },
+                CtmRegistryEvent {
+                    ctm: address!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+                    added: false,
+                    block_number: 40,
+                    transaction_hash: format!("0x{:064x}", 40),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn requests_every_known_event_signature() {
+        let signatures = bridgehub_history_event_signatures();
+        assert_eq!(signatures.len(), 5);
+        assert_eq!(signatures[0], format!("{:#x}", NewChain::SIGNATURE_HASH));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use alloy_primitives::{Address, U256};
+use serde_json::{Value, json};
+
+use crate::rpc::RpcClient;
+
+use super::bridgehub::{BridgehubError, u256_to_u64};
+
+pub fn get_l1_chain_id(client: &dyn RpcClient) -> Result<u64, BridgehubError> {
+    decode_quantity(&client.raw_request("zks_L1ChainId", json!([]))?)
+}
+
+pub fn get_l1_batch_number(client: &dyn RpcClient) -> Result<u64, BridgehubError> {
+    decode_quantity(&client.raw_request("zks_L1BatchNumber", json!([]))?)
+}
+
+pub fn get_bridgehub_contract(client: &dyn RpcClient) -> Result<Address, BridgehubError> {
+    decode_address(&client.raw_request("zks_getBridgehubContract", json!([]))?)
+}
+
+pub fn get_main_contract(client: &dyn RpcClient) -> Result<Address, BridgehubError> {
+    decode_address(&client.raw_request("zks_getMainContract", json!([]))?)
+}
+
+pub fn get_base_token_l1_address(client: &dyn RpcClient) -> Result<Address, BridgehubError> {
+    decode_address(&client.raw_request("zks_getBaseTokenL1Address", json!([]))?)
+}
+
+/// Unix timestamp of an L1 batch, from `zks_getL1BatchDetails`.
+pub fn get_l1_batch_timestamp(client: &dyn RpcClient, batch: u64) -> Result<u64, BridgehubError> {
+    let response = client.raw_request("zks_getL1BatchDetails", json!([batch]))?;
+    match response.get("timestamp") {
+        Some(timestamp) => decode_quantity(timestamp),
+        None => Err(BridgehubError::Decode(format!(
+            "no details for l1 batch {batch}: {response}"
+        ))),
+    }
+}
+
+/// Bridge addresses keyed by response field; unset bridges are skipped.
+pub fn get_bridge_contracts(
+    client: &dyn RpcClient,
+) -> Result<BTreeMap<String, Address>, BridgehubError> {
+    let response = client.raw_request("zks_getBridgeContracts", json!([]))?;
+    let Value::Object(fields) = response else {
+        return Err(BridgehubError::Decode(format!(
+            "expected an object of bridge addresses, got {response}"
+        )));
+    };
+    fields
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(name, value)| Ok((name.clone(), decode_address(value)?)))
+        .collect()
+}
+
+fn decode_quantity(value: &Value) -> Result<u64, BridgehubError> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .ok_or_else(|| BridgehubError::Decode(format!("invalid quantity {number}"))),
+        Value::String(text) => U256::from_str(text)
+            .map_err(|err| BridgehubError::Decode(format!("invalid quantity {text}: {err}")))
+            .and_then(u256_to_u64),
+        _ => Err(BridgehubError::Decode(format!("invalid quantity {value}"))),
+    }
+}
+
+fn decode_address(value: &Value) -> Result<Address, BridgehubError> {
+    value
+        .as_str()
+        .and_then(|text| Address::from_str(text).ok())
+        .ok_or_else(|| BridgehubError::Decode(format!("invalid address {value}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::rpc::RpcError;
+
+    #[test]
+    fn decodes_hex_and_numeric_quantities() {
+        assert_eq!(decode_quantity(&json!("0xaa36a7")).ok(), Some(11_155_111));
+        assert_eq!(decode_quantity(&json!(324)).ok(), Some(324));
+        assert!(decode_quantity(&json!("zz")).is_err());
+        assert!(decode_quantity(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn decodes_l1_batch_timestamp_and_rejects_unknown_batches() {
+        struct Batches;
+
+        impl RpcClient for Batches {
+            fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+                Err(RpcError::Unsupported("eth_call"))
+            }
+
+            fn raw_request(&self, _method: &'static str, params: Value) -> Result<Value, RpcError> {
+                Ok(match params[0].as_u64() {
+                    Some(7) => json!({ "number": 7, "timestamp": 1_700_000_000 }),
+                    _ => Value::Null,
+                })
+            }
+        }
+
+        assert_eq!(
+            get_l1_batch_timestamp(&Batches, 7).ok(),
+            Some(1_700_000_000)
+        );
+        assert!(get_l1_batch_timestamp(&Batches, 8).is_err());
+    }
+
+    #[test]
+    fn decodes_bridge_contracts_skipping_unset_bridges() {
+        struct Bridges;
+
+        impl RpcClient for Bridges {
+            fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+                Err(RpcError::Unsupported("eth_call"))
+            }
+
+            fn raw_request(
+                &self,
+                _method: &'static str,
+                _params: Value,
+            ) -> Result<Value, RpcError> {
+                Ok(json!({
+                    "l1SharedDefaultBridge": "0x00000000000000000000000000000000000000b1",
+                    "l2SharedDefaultBridge": "0x00000000000000000000000000000000000000b2",
+                    "l1WethBridge": null,
+                }))
+            }
+        }
+
+        let bridges = get_bridge_contracts(&Bridges).expect("bridges should decode");
+        assert_eq!(
+            bridges,
+            BTreeMap::from([
+                (
+                    "l1SharedDefaultBridge".to_string(),
+                    address!("0x00000000000000000000000000000000000000b1")
+                ),
+                (
+                    "l2SharedDefaultBridge".to_string(),
+                    address!("0x00000000000000000000000000000000000000b2")
+                ),
+            ])
+        );
+    }
+}
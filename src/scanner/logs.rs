@@ -0,0 +1,320 @@
+use std::{thread, time::Duration};
+
+use alloy_primitives::Address;
+
+use crate::rpc::{LogFilter, RpcClient, RpcError, RpcLog};
+
+/// Block span requested per `eth_getLogs` call before any provider pushback.
+pub const DEFAULT_LOG_BLOCK_RANGE: u64 = 10_000;
+
+/// Retries of one range after a rate limit or timeout before giving up.
+const MAX_TRANSIENT_RETRIES: u32 = 4;
+/// Wait before the first retry; doubled on each further one.
+const TRANSIENT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Fetches logs emitted by `address` in `from_block..=to_block`, split into
+/// ranges of at most `max_block_range` blocks.
+///
+/// When the provider rejects a range as too large, the range is halved and
+/// retried; after a successful request it grows back towards
+/// `max_block_range`. Rate limits and timeouts retry the same range after a
+/// growing pause. Logs are returned in chain order.
+pub fn fetch_logs(
+    client: &dyn RpcClient,
+    address: Address,
+    event_signatures: &[String],
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+) -> Result<Vec<RpcLog>, RpcError> {
+    fetch_logs_with_backoff(
+        client,
+        address,
+        event_signatures,
+        from_block,
+        to_block,
+        max_block_range,
+        TRANSIENT_BACKOFF,
+    )
+}
+
+fn fetch_logs_with_backoff(
+    client: &dyn RpcClient,
+    address: Address,
+    event_signatures: &[String],
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+    backoff: Duration,
+) -> Result<Vec<RpcLog>, RpcError> {
+    let max_block_range = max_block_range.max(1);
+    let mut block_range = max_block_range;
+    let mut logs = Vec::new();
+    let mut start = from_block;
+    let mut retries = 0;
+
+    while start <= to_block {
+        let end = start.saturating_add(block_range - 1).min(to_block);
+        let filter = LogFilter {
+            address: format!("{address:#x}"),
+            event_signatures: event_signatures.to_vec(),
+            from_block: start,
+            to_block: end,
+        };
+
+        match client.eth_get_logs(&filter) {
+            Ok(batch) => {
+                retries = 0;
+                logs.extend(batch);
+                if end == to_block {
+                    break;
+                }
+                start = end + 1;
+                block_range = block_range.saturating_mul(2).min(max_block_range);
+            }
+            Err(err) if end > start && is_range_limit_error(&err) => {
+                block_range = (end - start).div_ceil(2).max(1);
+            }
+            Err(err) if retries < MAX_TRANSIENT_RETRIES && is_transient_error(&err) => {
+                thread::sleep(backoff * 2u32.pow(retries));
+                retries += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    logs.sort_by_key(|log| (log.block_number, log.log_index));
+    Ok(logs)
+}
+
+/// Heuristic for provider errors that mean "ask for fewer blocks".
+///
+/// Providers word this differently (`block range is too wide`, `query
+/// returned more than 10000 results`, `log response size exceeded`, ...).
+pub fn is_range_limit_error(err: &RpcError) -> bool {
+    transport_message_contains(
+        err,
+        &[
+            "block range",
+            "range too",
+            "range is too",
+            "returned more than",
+            "too many results",
+            "too many logs",
+            "response size",
+        ],
+    )
+}
+
+/// Heuristic for rate limits and timeouts, which clear up on their own.
+pub fn is_transient_error(err: &RpcError) -> bool {
+    transport_message_contains(
+        err,
+        &[
+            "429",
+            "too many requests",
+            "rate limit",
+            "rate-limit",
+            "timeout",
+            "timed out",
+        ],
+    )
+}
+
+fn transport_message_contains(err: &RpcError, patterns: &[&str]) -> bool {
+    let RpcError::Transport(message) = err else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    patterns.iter().any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use alloy_primitives::address;
+
+    struct RangeLimitedRpc {
+        max_span: u64,
+        log_blocks: Vec<u64>,
+        requests: RefCell<Vec<(u64, u64)>>,
+    }
+
+    impl RpcClient for RangeLimitedRpc {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            self.requests
+                .borrow_mut()
+                .push((filter.from_block, filter.to_block));
+            if filter.to_block - filter.from_block + 1 > self.max_span {
+                return Err(RpcError::Transport(
+                    "server returned an error response: error code -32005: query exceeds max block range 1000"
+                        .to_string(),
+                ));
+            }
+            Ok(self
+                .log_blocks
+                .iter()
+                .filter(|block| (filter.from_block..=filter.to_block).contains(block))
+                .map(|block| RpcLog {
+                    address: filter.address.clone(),
+                    topics: vec![],
+                    data: "0x".to_string(),
+                    block_number: *block,
+                    transaction_hash: format!("0x{block:064x}"),
+                    log_index: 0,
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn shrinks_range_until_provider_accepts_it() {
+        let rpc = RangeLimitedRpc {
+            max_span: 1_000,
+            log_blocks: vec![5, 1_500, 2_999, 4_000],
+            requests: RefCell::new(vec![]),
+        };
+
+        let logs = fetch_logs(
+            &rpc,
+            address!("0x0000000000000000000000000000000000000001"),
+            &[],
+            0,
+            3_999,
+            4_000,
+        )
+        .expect("logs should be fetched");
+
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![5, 1_500, 2_999]
+        );
+        let requests = rpc.requests.borrow();
+        assert_eq!(requests[0], (0, 3_999));
+        assert_eq!(requests.last(), Some(&(3_000, 3_999)));
+        assert!(
+            requests
+                .windows(2)
+                .all(|pair| pair[1].0 == pair[0].0 || pair[1].0 == pair[0].1 + 1)
+        );
+    }
+
+    #[test]
+    fn propagates_non_range_errors() {
+        struct FailingRpc;
+
+        impl RpcClient for FailingRpc {
+            fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+                Err(RpcError::Unsupported("eth_call"))
+            }
+        }
+
+        let err = fetch_logs(
+            &FailingRpc,
+            address!("0x0000000000000000000000000000000000000001"),
+            &[],
+            0,
+            100,
+            10,
+        )
+        .expect_err("unsupported eth_getLogs should fail");
+        assert!(matches!(err, RpcError::Unsupported("eth_getLogs")));
+    }
+
+    #[test]
+    fn recognizes_provider_range_limit_messages() {
+        assert!(is_range_limit_error(&RpcError::Transport(
+            "query returned more than 10000 results".to_string()
+        )));
+        assert!(is_range_limit_error(&RpcError::Transport(
+            "eth_getLogs block range is too wide".to_string()
+        )));
+        assert!(!is_range_limit_error(&RpcError::Transport(
+            "connection refused".to_string()
+        )));
+        assert!(!is_range_limit_error(&RpcError::InvalidResponse(
+            "more than expected".to_string()
+        )));
+
+        let rate_limited = RpcError::Transport("HTTP error 429 Too Many Requests".to_string());
+        let timed_out = RpcError::Transport("operation timed out".to_string());
+        assert!(!is_range_limit_error(&rate_limited));
+        assert!(!is_range_limit_error(&timed_out));
+        assert!(is_transient_error(&rate_limited));
+        assert!(is_transient_error(&timed_out));
+    }
+
+    struct RateLimitedRpc {
+        failures: u32,
+        requests: RefCell<Vec<(u64, u64)>>,
+    }
+
+    impl RpcClient for RateLimitedRpc {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            let mut requests = self.requests.borrow_mut();
+            requests.push((filter.from_block, filter.to_block));
+            if requests.len() as u32 <= self.failures {
+                return Err(RpcError::Transport(
+                    "HTTP error 429 Too Many Requests".to_string(),
+                ));
+            }
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn retries_rate_limited_ranges_without_shrinking() {
+        let rpc = RateLimitedRpc {
+            failures: 2,
+            requests: RefCell::new(vec![]),
+        };
+
+        fetch_logs_with_backoff(
+            &rpc,
+            address!("0x0000000000000000000000000000000000000001"),
+            &[],
+            0,
+            999,
+            1_000,
+            Duration::ZERO,
+        )
+        .expect("logs should be fetched after the rate limit clears");
+
+        assert_eq!(*rpc.requests.borrow(), vec![(0, 999); 3]);
+    }
+
+    #[test]
+    fn gives_up_after_repeated_rate_limits() {
+        let rpc = RateLimitedRpc {
+            failures: u32::MAX,
+            requests: RefCell::new(vec![]),
+        };
+
+        let err = fetch_logs_with_backoff(
+            &rpc,
+            address!("0x0000000000000000000000000000000000000001"),
+            &[],
+            0,
+            999,
+            1_000,
+            Duration::ZERO,
+        )
+        .expect_err("a persistent rate limit should fail");
+
+        assert!(is_transient_error(&err));
+        assert_eq!(
+            rpc.requests.borrow().len() as u32,
+            MAX_TRANSIENT_RETRIES + 1
+        );
+    }
+}
@@ -1,21 +1,79 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy_primitives::Address;
 
 use thiserror::Error;
 
-use crate::model::{ChainCtm, ChainInspection, ChainSummary, CtmSummary, TopologySnapshot};
+use crate::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::model::{
+    BaseToken, BatchLag, BatchProgress, BridgehubHistory, ChainCtm, ChainDataAvailability,
+    ChainInspection, ChainSummary, CtmSummary, DataAvailability, FeeParams, FieldBisection,
+    L2Inspection, SavedSnapshot, SettlementLayer, TimelineEntry, TimelockValidator,
+    TopologySnapshot, UpgradeHistory,
+};
 use crate::rpc::RpcClient;
+use crate::snapshot::SNAPSHOT_FORMAT_VERSION;
 
+pub mod bisect;
 pub mod bridgehub;
+pub mod data_availability;
+pub mod diamond;
+pub mod history;
+pub mod l2;
+pub mod logs;
+pub mod timeline;
+pub mod token;
+pub mod upgrades;
+
+use bisect::BisectField;
+use bridgehub::{ValidatorTimelockKind, ValidatorTimelockRole};
+use data_availability::KnownDaValidator;
 
 #[derive(Debug, Error)]
 pub enum ScanError {
     #[error("bridgehub scan failed: {0}")]
     Bridgehub(#[from] bridgehub::BridgehubError),
+    #[error("invalid block range: {0}")]
+    InvalidBlockRange(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Resolve each chain's data availability for the per-CTM breakdown, at the
+    /// cost of a `getZKChain` call plus the DA reads per chain.
+    pub data_availability: bool,
+    /// L1 DA validators with a known DA layer, used for classification.
+    pub da_validators: Vec<KnownDaValidator>,
+    /// Index Bridgehub registry events when set.
+    pub history: Option<HistoryOptions>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryOptions {
+    pub from_block: u64,
+    /// Upper bound on the block span of a single `eth_getLogs` request.
+    pub max_block_range: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InspectOptions {
+    /// Resolve multisig committer details.
+    pub verbose: bool,
+    /// Addresses probed via `validators(chainId, address)` on legacy validator
+    /// timelocks, which cannot enumerate their validator set.
+    pub validator_candidates: Vec<Address>,
+    /// L1 DA validators with a known DA layer, used for classification.
+    pub da_validators: Vec<KnownDaValidator>,
+    /// Build an ownership/role timeline from logs when set.
+    pub timeline: Option<HistoryOptions>,
 }
 
 pub fn scan_bridgehub_topology(
     client: &dyn RpcClient,
-    bridgehub: &str,
+    bridgehub: Address,
+    options: &ScanOptions,
 ) -> Result<TopologySnapshot, ScanError> {
     let chain_ids = bridgehub::get_all_zk_chain_chain_ids(client, bridgehub)?;
     let mut chain_ctms = Vec::with_capacity(chain_ids.len());
@@ -24,10 +82,14 @@ pub fn scan_bridgehub_topology(
     for chain_id in &chain_ids {
         match bridgehub::get_chain_type_manager(client, bridgehub, *chain_id) {
             Ok(ctm) => {
-                if is_zero_address(&ctm) {
-                    warnings.push(format!(
-                        "chain {chain_id} returned zero address for chainTypeManager"
-                    ));
+                if ctm.is_zero() {
+                    warnings.push(
+                        Diagnostic::new(
+                            DiagnosticCode::ZeroAddress,
+                            format!("chain {chain_id} returned zero address for chainTypeManager"),
+                        )
+                        .chain(*chain_id),
+                    );
                 } else {
                     chain_ctms.push(ChainCtm {
                         chain_id: *chain_id,
@@ -35,25 +97,35 @@ pub fn scan_bridgehub_topology(
                     });
                 }
             }
-            Err(err) => warnings.push(format!(
-                "failed to resolve chainTypeManager for chain {chain_id}: {err}"
-            )),
+            Err(err) => warnings.push(
+                Diagnostic::resolution_failed(
+                    "chainTypeManager",
+                    format!("failed to resolve chainTypeManager for chain {chain_id}"),
+                    err,
+                )
+                .chain(*chain_id),
+            ),
         }
     }
 
     let mut deduped_ctms = BTreeSet::new();
     for mapping in &chain_ctms {
-        deduped_ctms.insert(mapping.ctm.clone());
+        deduped_ctms.insert(mapping.ctm);
     }
 
     let mut ctms = Vec::with_capacity(deduped_ctms.len());
     for ctm in deduped_ctms {
-        let protocol_version = match bridgehub::get_ctm_protocol_semver(client, &ctm) {
+        let protocol_version = match bridgehub::get_ctm_protocol_semver(client, ctm) {
             Ok(version) => Some(version),
             Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve protocol semver for ctm {ctm}: {err}"
-                ));
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getSemverProtocolVersion",
+                        format!("failed to resolve protocol semver for ctm {ctm}"),
+                        err,
+                    )
+                    .contract(ctm),
+                );
                 None
             }
         };
@@ -63,530 +135,3364 @@ pub fn scan_bridgehub_topology(
         });
     }
 
+    let mut chain_data_availability = Vec::new();
+    if options.data_availability {
+        for mapping in &chain_ctms {
+            let chain_id = mapping.chain_id;
+            let chain_contract = match bridgehub::get_zk_chain(client, bridgehub, chain_id) {
+                Ok(address) if !address.is_zero() => Some(address),
+                Ok(_) => None,
+                Err(err) => {
+                    warnings.push(
+                        Diagnostic::resolution_failed(
+                            "getZKChain",
+                            format!("failed to resolve getZKChain for chain {chain_id}"),
+                            err,
+                        )
+                        .chain(chain_id),
+                    );
+                    None
+                }
+            };
+            chain_data_availability.push(ChainDataAvailability {
+                chain_id,
+                data_availability: resolve_data_availability(
+                    client,
+                    chain_id,
+                    chain_contract,
+                    &options.da_validators,
+                    &mut warnings,
+                ),
+            });
+        }
+    }
+
+    let history = options
+        .history
+        .and_then(|history| resolve_history(client, bridgehub, history, &chain_ids, &mut warnings));
+
     Ok(TopologySnapshot {
-        bridgehub: bridgehub.to_string(),
+        bridgehub,
         chain_ids,
         chain_ctms,
         ctms,
+        chain_data_availability,
+        history,
         warnings,
     })
 }
 
+/// Latest block for a log query starting at `options.from_block`, or `None`
+/// (with a warning) when it cannot be determined or precedes the start.
+fn resolve_log_window_end(
+    client: &dyn RpcClient,
+    options: HistoryOptions,
+    purpose: &str,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<u64> {
+    let to_block = match client.eth_block_number() {
+        Ok(block_number) => block_number,
+        Err(err) => {
+            warnings.push(Diagnostic::resolution_failed(
+                "eth_blockNumber",
+                format!("failed to resolve eth_blockNumber for {purpose}"),
+                err,
+            ));
+            return None;
+        }
+    };
+    if options.from_block > to_block {
+        warnings.push(Diagnostic::new(
+            DiagnosticCode::IncompleteLogs,
+            format!(
+                "{purpose} start block {} is past the latest block {to_block}",
+                options.from_block
+            ),
+        ));
+        return None;
+    }
+    Some(to_block)
+}
+
+fn resolve_history(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    options: HistoryOptions,
+    chain_ids: &[u64],
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<BridgehubHistory> {
+    let to_block = resolve_log_window_end(client, options, "history", warnings)?;
+
+    let history = match history::index_bridgehub_history(
+        client,
+        bridgehub,
+        options.from_block,
+        to_block,
+        options.max_block_range,
+        chain_ids,
+    ) {
+        Ok(history) => history,
+        Err(err) => {
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::IncompleteLogs,
+                    "failed to index bridgehub history",
+                )
+                .error(err),
+            );
+            return None;
+        }
+    };
+
+    for chain_id in chain_ids {
+        if !history
+            .chain_registrations
+            .iter()
+            .any(|registration| registration.chain_id == *chain_id)
+        {
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::IncompleteLogs,
+                    format!(
+                        "chain {chain_id} has no NewChain event in blocks {}..={to_block}",
+                        options.from_block
+                    ),
+                )
+                .chain(*chain_id),
+            );
+        }
+    }
+
+    Some(history)
+}
+
 pub fn inspect_bridgehub_chain(
     client: &dyn RpcClient,
-    bridgehub: &str,
+    bridgehub: Address,
     chain_id: u64,
-    verbose: bool,
+    options: &InspectOptions,
 ) -> Result<ChainInspection, ScanError> {
     let mut warnings = Vec::new();
+    let mut unresolved = Unresolved::default();
 
+    let since = warnings.len();
     let ctm = match bridgehub::get_chain_type_manager(client, bridgehub, chain_id) {
-        Ok(address) if !is_zero_address(&address) => Some(address),
+        Ok(address) if !address.is_zero() => Some(address),
         Ok(_) => {
-            warnings.push(format!(
-                "chain {chain_id} returned zero address for chainTypeManager"
-            ));
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ZeroAddress,
+                    format!("chain {chain_id} returned zero address for chainTypeManager"),
+                )
+                .chain(chain_id),
+            );
             None
         }
         Err(err) => {
-            warnings.push(format!(
-                "failed to resolve chainTypeManager for chain {chain_id}: {err}"
-            ));
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "chainTypeManager",
+                    format!("failed to resolve chainTypeManager for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
             None
         }
     };
+    unresolved.record(&warnings[since..], &[], &["ctm"]);
 
+    let since = warnings.len();
     let chain_contract = match bridgehub::get_zk_chain(client, bridgehub, chain_id) {
-        Ok(address) if !is_zero_address(&address) => Some(address),
+        Ok(address) if !address.is_zero() => Some(address),
         Ok(_) => None,
         Err(err) => {
-            warnings.push(format!(
-                "failed to resolve getZKChain for chain {chain_id}: {err}"
-            ));
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "getZKChain",
+                    format!("failed to resolve getZKChain for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
             None
         }
     };
+    unresolved.record(&warnings[since..], &[], &["chain_contract"]);
 
-    let validator_timelock = match ctm.as_deref() {
-        Some(ctm) => match bridgehub::get_ctm_validator_timelock(client, ctm) {
-            Ok(address) if !is_zero_address(&address) => Some(address),
+    let since = warnings.len();
+    let validator_timelock = match ctm {
+        Some(ctm) => match bridgehub::resolve_ctm_validator_timelock(client, ctm) {
+            Ok((address, kind)) if !address.is_zero() => Some((address, kind)),
             Ok(_) => None,
             Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve validator timelock for chain {chain_id}: {err}"
-                ));
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "validatorTimelock",
+                        format!("failed to resolve validator timelock for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
                 None
             }
         },
         None => None,
     };
+    unresolved.record(&warnings[since..], &["ctm"], &["validator_timelock"]);
 
-    let validator_timelock_owner = match validator_timelock.as_deref() {
-        Some(validator_timelock) => match bridgehub::get_contract_owner(client, validator_timelock)
-        {
-            Ok(address) if !is_zero_address(&address) => Some(address),
-            Ok(_) => None,
-            Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve owner() for validator timelock on chain {chain_id}: {err}"
-                ));
-                None
+    let validator_timelock_kind = validator_timelock.as_ref().map(|(_, kind)| *kind);
+    let validator_timelock = validator_timelock.map(|(address, _)| address);
+
+    let since = warnings.len();
+    let validator_timelock_owner = match validator_timelock {
+        Some(validator_timelock) => {
+            match bridgehub::get_contract_owner(client, validator_timelock) {
+                Ok(address) if !address.is_zero() => Some(address),
+                Ok(_) => None,
+                Err(err) => {
+                    let message = format!(
+                        "failed to resolve owner() for validator timelock on chain {chain_id}"
+                    );
+                    warnings
+                        .push(Diagnostic::resolution_failed("owner", message, err).chain(chain_id));
+                    None
+                }
             }
-        },
+        }
+        None => None,
+    };
+    unresolved.record(
+        &warnings[since..],
+        &["validator_timelock"],
+        &["validator_timelock_owner"],
+    );
+
+    let since = warnings.len();
+    let validator_timelock_pending_owner = match validator_timelock {
+        Some(validator_timelock) => resolve_pending_owner(
+            client,
+            validator_timelock,
+            "validator timelock",
+            chain_id,
+            &mut warnings,
+        ),
+        None => None,
+    };
+    unresolved.record(
+        &warnings[since..],
+        &["validator_timelock"],
+        &["validator_timelock_pending_owner"],
+    );
+
+    let since = warnings.len();
+    let validator_timelock_execution_delay = match validator_timelock {
+        Some(validator_timelock) => {
+            match bridgehub::get_timelock_execution_delay(client, validator_timelock) {
+                Ok(delay) => Some(delay),
+                Err(err) => {
+                    let message = format!(
+                        "failed to resolve executionDelay for validator timelock on chain {chain_id}"
+                    );
+                    warnings.push(
+                        Diagnostic::resolution_failed("executionDelay", message, err)
+                            .chain(chain_id),
+                    );
+                    None
+                }
+            }
+        }
         None => None,
     };
+    unresolved.record(
+        &warnings[since..],
+        &["validator_timelock"],
+        &["validator_timelock_execution_delay"],
+    );
+
+    let since = warnings.len();
+    let timelock_validators = match (validator_timelock, validator_timelock_kind) {
+        (Some(validator_timelock), Some(kind)) => resolve_timelock_validators(
+            client,
+            chain_id,
+            chain_contract,
+            validator_timelock,
+            kind,
+            &options.validator_candidates,
+            &mut warnings,
+        ),
+        _ => None,
+    };
+    unresolved.record(
+        &warnings[since..],
+        &["validator_timelock", "chain_contract"],
+        &["timelock_validators"],
+    );
 
-    let admin = match ctm.as_deref() {
+    let since = warnings.len();
+    let admin = match ctm {
         Some(ctm) => match bridgehub::get_ctm_chain_admin(client, ctm, chain_id) {
-            Ok(address) if !is_zero_address(&address) => Some(address),
+            Ok(address) if !address.is_zero() => Some(address),
             Ok(_) => None,
             Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve getChainAdmin for chain {chain_id}: {err}"
-                ));
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getChainAdmin",
+                        format!("failed to resolve getChainAdmin for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
                 None
             }
         },
         None => None,
     };
+    unresolved.record(&warnings[since..], &["ctm"], &["admin"]);
 
-    let admin_owner = match admin.as_deref() {
+    let since = warnings.len();
+    let admin_owner = match admin {
         Some(admin) => match bridgehub::get_contract_owner(client, admin) {
-            Ok(address) if !is_zero_address(&address) => Some(address),
+            Ok(address) if !address.is_zero() => Some(address),
             Ok(_) => None,
             Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve owner() for admin on chain {chain_id}: {err}"
-                ));
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "owner",
+                        format!("failed to resolve owner() for admin on chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
                 None
             }
         },
         None => None,
     };
+    unresolved.record(&warnings[since..], &["admin"], &["admin_owner"]);
+
+    let since = warnings.len();
+    let admin_pending_owner = match admin {
+        Some(admin) => resolve_pending_owner(client, admin, "admin", chain_id, &mut warnings),
+        None => None,
+    };
+    unresolved.record(&warnings[since..], &["admin"], &["admin_pending_owner"]);
 
-    let protocol_version = match ctm.as_deref() {
+    let since = warnings.len();
+    let protocol_version = match ctm {
         Some(ctm) => match bridgehub::get_ctm_chain_protocol_semver(client, ctm, chain_id) {
             Ok(version) => Some(version),
             Err(err) => {
-                warnings.push(format!(
-                    "failed to resolve getProtocolVersion for chain {chain_id}: {err}"
-                ));
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getProtocolVersion",
+                        format!("failed to resolve getProtocolVersion for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
                 None
             }
         },
         None => None,
     };
+    unresolved.record(&warnings[since..], &["ctm"], &["protocol_version"]);
 
-    let (multisig_signing_set_mode, multisig_signing_threshold, multisig_validators) = if verbose {
-        resolve_multisig_committer_details(
-            client,
-            chain_id,
-            chain_contract.as_deref(),
-            validator_timelock.as_deref(),
-            &mut warnings,
-        )
-    } else {
-        (None, None, None)
+    let since = warnings.len();
+    let base_token = resolve_base_token(client, bridgehub, chain_id, chain_contract, &mut warnings);
+    unresolved.record(&warnings[since..], &["chain_contract"], &["base_token"]);
+
+    let since = warnings.len();
+    let data_availability = resolve_data_availability(
+        client,
+        chain_id,
+        chain_contract,
+        &options.da_validators,
+        &mut warnings,
+    );
+    unresolved.record(
+        &warnings[since..],
+        &["chain_contract"],
+        &["l1_da_validator", "l2_da_validator"],
+    );
+
+    let since = warnings.len();
+    let settlement =
+        resolve_settlement_layer(client, bridgehub, chain_id, chain_contract, &mut warnings);
+    unresolved.record(
+        &warnings[since..],
+        &["chain_contract"],
+        &["settlement_chain_id"],
+    );
+
+    let since = warnings.len();
+    let (fee_params, priority_tx_max_gas_limit, transaction_filterer) =
+        resolve_fee_configuration(client, chain_id, chain_contract, &mut warnings);
+    unresolved.record(
+        &warnings[since..],
+        &["chain_contract"],
+        &["priority_tx_max_gas_limit", "transaction_filterer"],
+    );
+
+    let since = warnings.len();
+    let (multisig_signing_set_mode, multisig_signing_threshold, multisig_validators) =
+        if options.verbose {
+            resolve_multisig_committer_details(
+                client,
+                chain_id,
+                chain_contract,
+                validator_timelock,
+                &mut warnings,
+            )
+        } else {
+            (None, None, None)
+        };
+    unresolved.record(
+        &warnings[since..],
+        &["validator_timelock", "chain_contract"],
+        &["multisig_signing_threshold", "multisig_validators"],
+    );
+
+    let chain = ChainSummary {
+        chain_id,
+        ctm,
+        validator_timelock,
+        validator_timelock_owner,
+        validator_timelock_pending_owner,
+        validator_timelock_execution_delay,
+        timelock_validators,
+        chain_contract,
+        admin,
+        admin_owner,
+        admin_pending_owner,
+        protocol_version,
+        base_token,
+        data_availability,
+        settlement,
+        fee_params,
+        priority_tx_max_gas_limit,
+        transaction_filterer,
+        multisig_signing_set_mode,
+        multisig_signing_threshold,
+        multisig_validators,
     };
 
+    let timeline = options
+        .timeline
+        .and_then(|timeline| resolve_timeline(client, bridgehub, &chain, timeline, &mut warnings));
+
     Ok(ChainInspection {
-        bridgehub: bridgehub.to_string(),
-        chain: ChainSummary {
-            chain_id,
-            ctm,
-            validator_timelock,
-            validator_timelock_owner,
-            chain_contract,
-            admin,
-            admin_owner,
-            protocol_version,
-            multisig_signing_set_mode,
-            multisig_signing_threshold,
-            multisig_validators,
-        },
+        bridgehub,
+        chain,
+        gateway: None,
+        timeline,
+        l2: None,
         warnings,
+        unresolved: unresolved.0,
     })
 }
 
-fn resolve_multisig_committer_details(
+/// Fields of one inspection whose reads failed, directly or through a field
+/// they were read from.
+#[derive(Default)]
+struct Unresolved(BTreeSet<String>);
+
+impl Unresolved {
+    fn record(&mut self, warnings: &[Diagnostic], depends_on: &[&str], fields: &[&str]) {
+        let failed = warnings
+            .iter()
+            .any(|warning| warning.code == DiagnosticCode::ResolutionFailed)
+            || depends_on.iter().any(|field| self.0.contains(*field));
+        if failed {
+            self.0.extend(fields.iter().map(|field| field.to_string()));
+        }
+    }
+}
+
+fn resolve_timeline(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain: &ChainSummary,
+    options: HistoryOptions,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<Vec<TimelineEntry>> {
+    let to_block = resolve_log_window_end(client, options, "timeline", warnings)?;
+
+    let contracts = [
+        ("bridgehub", Some(bridgehub)),
+        ("ctm", chain.ctm),
+        ("validator timelock", chain.validator_timelock),
+        ("validator timelock owner", chain.validator_timelock_owner),
+        ("chain diamond", chain.chain_contract),
+        ("chain admin", chain.admin),
+        ("chain admin owner", chain.admin_owner),
+        ("transaction filterer", chain.transaction_filterer),
+    ];
+
+    let mut seen = BTreeSet::new();
+    let mut entries = Vec::new();
+    for (contract, address) in contracts {
+        let Some(address) = address.filter(|address| !address.is_zero()) else {
+            continue;
+        };
+        if !seen.insert(address) {
+            continue;
+        }
+        // Owners may be EOAs, which emit no logs. When the code lookup fails
+        // the contract is scanned anyway.
+        if let Ok(code) = client.eth_get_code(&format!("{address:#x}"))
+            && code.trim_start_matches("0x").is_empty()
+        {
+            continue;
+        }
+        match timeline::fetch_contract_timeline(
+            client,
+            contract,
+            address,
+            chain.chain_contract,
+            options.from_block,
+            to_block,
+            options.max_block_range,
+        ) {
+            Ok(contract_entries) => entries.extend(contract_entries),
+            Err(err) => warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::IncompleteLogs,
+                    format!(
+                        "failed to fetch {contract} timeline for chain {}",
+                        chain.chain_id
+                    ),
+                )
+                .error(err)
+                .chain(chain.chain_id),
+            ),
+        }
+    }
+
+    entries.sort_by_key(|entry| (entry.block_number, entry.log_index));
+    Some(entries)
+}
+
+/// Reconstructs a chain's upgrade history: upgrades applied on its diamond
+/// and upgrades published by its CTM from `options.from_block` onwards.
+pub fn scan_chain_upgrades(
     client: &dyn RpcClient,
+    bridgehub: Address,
     chain_id: u64,
-    chain_contract: Option<&str>,
-    validator_timelock: Option<&str>,
-    warnings: &mut Vec<String>,
-) -> (Option<String>, Option<u64>, Option<Vec<String>>) {
-    let (Some(chain_contract), Some(validator_timelock)) = (chain_contract, validator_timelock)
-    else {
-        return (None, None, None);
-    };
+    options: HistoryOptions,
+) -> Result<UpgradeHistory, ScanError> {
+    let mut warnings = Vec::new();
 
-    let use_custom = match bridgehub::get_multisig_is_custom_signing_set_active(
-        client,
-        validator_timelock,
-        chain_contract,
-    ) {
-        Ok(use_custom) => use_custom,
+    let ctm = match bridgehub::get_chain_type_manager(client, bridgehub, chain_id) {
+        Ok(address) if !address.is_zero() => Some(address),
+        Ok(_) => {
+            warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ZeroAddress,
+                    format!("chain {chain_id} returned zero address for chainTypeManager"),
+                )
+                .chain(chain_id),
+            );
+            None
+        }
         Err(err) => {
-            warnings.push(format!(
-                "failed to resolve multisig configuration for chain {chain_id} from validator timelock {validator_timelock}: {err}"
-            ));
-            return (None, None, None);
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "chainTypeManager",
+                    format!("failed to resolve chainTypeManager for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
+            None
         }
     };
 
-    let signing_threshold = match bridgehub::get_multisig_signing_threshold(
-        client,
-        validator_timelock,
-        chain_contract,
-    ) {
-        Ok(threshold) => Some(threshold),
+    let chain_contract = match bridgehub::get_zk_chain(client, bridgehub, chain_id) {
+        Ok(address) if !address.is_zero() => Some(address),
+        Ok(_) => None,
         Err(err) => {
-            warnings.push(format!(
-                "failed to resolve multisig threshold for chain {chain_id} from validator timelock {validator_timelock}: {err}"
-            ));
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "getZKChain",
+                    format!("failed to resolve getZKChain for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
             None
         }
     };
 
-    let validators = match bridgehub::get_multisig_validators_count(
-        client,
-        validator_timelock,
-        chain_contract,
-    ) {
-        Ok(count) => {
-            let mut validators = Vec::with_capacity(count as usize);
-            for index in 0..count {
-                match bridgehub::get_multisig_validator_member(
-                    client,
-                    validator_timelock,
-                    chain_contract,
-                    index,
-                ) {
-                    Ok(address) => validators.push(address),
-                    Err(err) => {
-                        warnings.push(format!(
-                            "failed to resolve validator index {index} for chain {chain_id} from validator timelock {validator_timelock}: {err}"
-                        ));
-                        return (
-                            Some(if use_custom {
-                                "custom".to_string()
-                            } else {
-                                "shared".to_string()
-                            }),
-                            signing_threshold,
-                            None,
-                        );
-                    }
-                }
+    let current_protocol_version = match ctm {
+        Some(ctm) => match bridgehub::get_ctm_chain_protocol_semver(client, ctm, chain_id) {
+            Ok(version) => Some(version),
+            Err(err) => {
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getProtocolVersion",
+                        format!("failed to resolve getProtocolVersion for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
+                None
             }
-            Some(validators)
-        }
-        Err(err) => {
-            warnings.push(format!(
-                "failed to resolve multisig validators count for chain {chain_id} from validator timelock {validator_timelock}: {err}"
-            ));
-            None
-        }
+        },
+        None => None,
     };
 
-    (
-        Some(if use_custom {
-            "custom".to_string()
-        } else {
-            "shared".to_string()
-        }),
-        signing_threshold,
-        validators,
-    )
-}
+    let to_block = client
+        .eth_block_number()
+        .map_err(bridgehub::BridgehubError::from)?;
+
+    let mut events = Vec::new();
+    if options.from_block > to_block {
+        warnings.push(Diagnostic::new(
+            DiagnosticCode::IncompleteLogs,
+            format!(
+                "upgrades start block {} is past the latest block {to_block}",
+                options.from_block
+            ),
+        ));
+    } else {
+        let sources = [("chain diamond", chain_contract), ("ctm", ctm)];
+        for (source, address) in sources {
+            let Some(address) = address else {
+                continue;
+            };
+            match upgrades::fetch_upgrade_events(
+                client,
+                source,
+                address,
+                options.from_block,
+                to_block,
+                options.max_block_range,
+            ) {
+                Ok(source_events) => events.extend(source_events),
+                Err(err) => warnings.push(
+                    Diagnostic::new(
+                        DiagnosticCode::IncompleteLogs,
+                        format!("failed to fetch {source} upgrade events for chain {chain_id}"),
+                    )
+                    .error(err)
+                    .chain(chain_id),
+                ),
+            }
+        }
+    }
+    events.sort_by_key(|event| (event.block_number, event.log_index));
 
-fn is_zero_address(address: &str) -> bool {
-    address == "0x0000000000000000000000000000000000000000"
+    Ok(UpgradeHistory {
+        bridgehub,
+        chain_id,
+        ctm,
+        chain_contract,
+        current_protocol_version,
+        from_block: options.from_block,
+        to_block,
+        events,
+        warnings,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+/// Finds the block in `from_block..=to_block` (default: latest) at which
+/// `field` took its final value, by binary search over historical `eth_call`s.
+pub fn bisect_chain_field(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+    field: BisectField,
+    from_block: u64,
+    to_block: Option<u64>,
+) -> Result<FieldBisection, ScanError> {
+    let to_block = match to_block {
+        Some(block) => block,
+        None => client
+            .eth_block_number()
+            .map_err(bridgehub::BridgehubError::from)?,
+    };
+    if from_block >= to_block {
+        return Err(ScanError::InvalidBlockRange(format!(
+            "--from {from_block} must be lower than --to {to_block}"
+        )));
+    }
 
-    use crate::rpc::{RpcClient, RpcError};
+    let read = |block| bisect::resolve_field_at_block(client, bridgehub, chain_id, field, block);
+    let mut outcome = bisect::bisect_blocks(from_block, to_block, read)?;
 
-    use super::*;
+    let previous_value = match outcome.changed_at_block {
+        Some(block) => {
+            outcome.probes += 1;
+            read(block - 1)?
+        }
+        None => None,
+    };
 
-    #[derive(Default)]
-    struct MockRpcClient {
-        responses: HashMap<String, Result<String, RpcError>>,
-    }
+    Ok(FieldBisection {
+        bridgehub,
+        chain_id,
+        field: field.name().to_string(),
+        from_block,
+        to_block,
+        from_value: outcome.from_value,
+        to_value: outcome.to_value,
+        changed_at_block: outcome.changed_at_block,
+        previous_value,
+        probes: outcome.probes,
+    })
+}
 
-    impl MockRpcClient {
-        fn with_response(mut self, data: &str, response: Result<String, RpcError>) -> Self {
-            self.responses.insert(data.to_string(), response);
-            self
+/// Scans the Bridgehub topology and inspects every registered chain, for
+/// `scan --save`. Per-chain inspection failures become topology warnings.
+pub fn capture_snapshot(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    scan_options: &ScanOptions,
+    inspect_options: &InspectOptions,
+) -> Result<SavedSnapshot, ScanError> {
+    let mut warnings = Vec::new();
+    let network_chain_id = match client.eth_chain_id() {
+        Ok(chain_id) => Some(chain_id),
+        Err(err) => {
+            warnings.push(Diagnostic::resolution_failed(
+                "eth_chainId",
+                "failed to resolve network chain id",
+                err,
+            ));
+            None
         }
-    }
+    };
+    let block_number = match client.eth_block_number() {
+        Ok(block) => Some(block),
+        Err(err) => {
+            warnings.push(Diagnostic::resolution_failed(
+                "eth_blockNumber",
+                "failed to resolve snapshot block number",
+                err,
+            ));
+            None
+        }
+    };
 
-    impl RpcClient for MockRpcClient {
-        fn eth_call(&self, _to: &str, data: &str) -> Result<String, RpcError> {
-            self.responses.get(data).cloned().unwrap_or_else(|| {
-                Err(RpcError::InvalidResponse(format!(
-                    "missing mock response for calldata: {data}"
-                )))
-            })
+    // The breakdown is taken from the chain inspections below instead.
+    let topology_options = ScanOptions {
+        data_availability: false,
+        ..scan_options.clone()
+    };
+    let mut topology = scan_bridgehub_topology(client, bridgehub, &topology_options)?;
+    let mut inspections = Vec::with_capacity(topology.chain_ids.len());
+    for chain_id in &topology.chain_ids {
+        match inspect_bridgehub_chain(client, bridgehub, *chain_id, inspect_options) {
+            Ok(inspection) => inspections.push(inspection),
+            Err(err) => warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::ScanFailed,
+                    format!("failed to inspect chain {chain_id}"),
+                )
+                .error(err)
+                .chain(*chain_id),
+            ),
         }
     }
+    topology.chain_data_availability = inspections
+        .iter()
+        .map(|inspection| ChainDataAvailability {
+            chain_id: inspection.chain.chain_id,
+            data_availability: inspection.chain.data_availability.clone(),
+        })
+        .collect();
+    topology.warnings.extend(warnings);
 
-    #[test]
-    fn topology_scanner_collects_and_dedupes_ctms() {
-        let chain_ids_data = bridgehub::encode_get_all_zk_chain_chain_ids_calldata();
-        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
-        let chain_325_data = bridgehub::encode_chain_type_manager_calldata(325);
-        let protocol_version_data = bridgehub::encode_protocol_version_calldata();
+    let saved_at_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
 
-        let mock = MockRpcClient::default()
+    Ok(SavedSnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        network_chain_id,
+        block_number,
+        saved_at_unix,
+        topology,
+        inspections,
+    })
+}
+
+/// Topology around a single inspected chain, so `inspect` can reuse the
+/// graph, report and table renderers. Only the chain's own CTM is resolved.
+pub fn chain_topology(client: &dyn RpcClient, inspection: &ChainInspection) -> TopologySnapshot {
+    let chain = &inspection.chain;
+    let mut warnings = Vec::new();
+    let ctms = chain
+        .ctm
+        .map(|ctm| {
+            let protocol_version = match bridgehub::get_ctm_protocol_semver(client, ctm) {
+                Ok(version) => Some(version),
+                Err(err) => {
+                    warnings.push(
+                        Diagnostic::resolution_failed(
+                            "getSemverProtocolVersion",
+                            format!("failed to resolve protocol semver for ctm {ctm}"),
+                            err,
+                        )
+                        .contract(ctm),
+                    );
+                    None
+                }
+            };
+            CtmSummary {
+                address: ctm,
+                protocol_version,
+            }
+        })
+        .into_iter()
+        .collect();
+
+    TopologySnapshot {
+        bridgehub: inspection.bridgehub,
+        chain_ids: vec![chain.chain_id],
+        chain_ctms: chain
+            .ctm
+            .map(|ctm| ChainCtm {
+                chain_id: chain.chain_id,
+                ctm,
+            })
+            .into_iter()
+            .collect(),
+        ctms,
+        chain_data_availability: vec![],
+        history: None,
+        warnings,
+    }
+}
+
+/// Follows a chain that migrated off L1 to its gateway and inspects it
+/// through the gateway's Bridgehub. Gateway warnings are merged into
+/// `inspection.warnings` with a `gateway:` prefix.
+pub fn inspect_gateway_settlement(
+    gateway_client: &dyn RpcClient,
+    gateway_bridgehub: Address,
+    inspection: &mut ChainInspection,
+    options: &InspectOptions,
+) -> Result<(), ScanError> {
+    let chain_id = inspection.chain.chain_id;
+    let settlement = &inspection.chain.settlement;
+    if settlement.migrated != Some(true) {
+        inspection.warnings.push(
+            Diagnostic::new(
+                DiagnosticCode::Skipped,
+                format!(
+                    "chain {chain_id} has not migrated to a gateway; skipping gateway inspection"
+                ),
+            )
+            .chain(chain_id),
+        );
+        return Ok(());
+    }
+
+    match (
+        gateway_client.eth_chain_id(),
+        settlement.settlement_chain_id,
+    ) {
+        (Ok(gateway_chain_id), Some(settlement_chain_id))
+            if gateway_chain_id != settlement_chain_id =>
+        {
+            let message = format!(
+                "gateway rpc reports chain id {gateway_chain_id}, but chain {chain_id} settles on chain {settlement_chain_id}; skipping gateway inspection"
+            );
+            inspection.warnings.push(
+                Diagnostic::new(DiagnosticCode::SettlementLayerMismatch, message).chain(chain_id),
+            );
+            return Ok(());
+        }
+        (Ok(_), _) => {}
+        (Err(err), _) => inspection.warnings.push(Diagnostic::resolution_failed(
+            "eth_chainId",
+            "failed to resolve eth_chainId from gateway rpc",
+            err,
+        )),
+    }
+
+    // Start blocks refer to the L1, so the timeline is not rebuilt on the gateway.
+    let gateway_options = InspectOptions {
+        timeline: None,
+        ..options.clone()
+    };
+    let mut gateway = inspect_bridgehub_chain(
+        gateway_client,
+        gateway_bridgehub,
+        chain_id,
+        &gateway_options,
+    )?;
+    inspection
+        .warnings
+        .extend(gateway.warnings.drain(..).map(|mut warning| {
+            warning.message = format!("gateway: {}", warning.message);
+            warning
+        }));
+    inspection.gateway = Some(Box::new(gateway));
+    Ok(())
+}
+
+/// Reads what the chain's own L2 node reports about itself and flags any
+/// disagreement with the L1 view in `inspection` as `L2NodeMismatch`.
+pub fn inspect_l2_node(l2_client: &dyn RpcClient, inspection: &mut ChainInspection) {
+    let chain_id = inspection.chain.chain_id;
+    let mut warnings = Vec::new();
+    let l2 = L2Inspection {
+        chain_id: l2_value(
+            "eth_chainId",
+            l2_client
+                .eth_chain_id()
+                .map_err(bridgehub::BridgehubError::from),
+            chain_id,
+            &mut warnings,
+        ),
+        l1_chain_id: l2_value(
+            "zks_L1ChainId",
+            l2::get_l1_chain_id(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        bridgehub: l2_value(
+            "zks_getBridgehubContract",
+            l2::get_bridgehub_contract(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        main_contract: l2_value(
+            "zks_getMainContract",
+            l2::get_main_contract(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        base_token_l1_address: l2_value(
+            "zks_getBaseTokenL1Address",
+            l2::get_base_token_l1_address(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        bridge_contracts: l2_value(
+            "zks_getBridgeContracts",
+            l2::get_bridge_contracts(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        latest_l1_batch: l2_value(
+            "zks_L1BatchNumber",
+            l2::get_l1_batch_number(l2_client),
+            chain_id,
+            &mut warnings,
+        ),
+        batch_progress: None,
+    };
+
+    let chain = &inspection.chain;
+    let mismatches = [
+        mismatch("chain id", l2.chain_id, Some(chain_id)),
+        mismatch("L1 chain id", l2.l1_chain_id, chain.settlement.l1_chain_id),
+        mismatch("bridgehub", l2.bridgehub, Some(inspection.bridgehub)),
+        mismatch("diamond proxy", l2.main_contract, chain.chain_contract),
+        mismatch(
+            "base token",
+            l2.base_token_l1_address,
+            chain.base_token.address,
+        ),
+    ];
+    warnings.extend(
+        mismatches.into_iter().flatten().map(|message| {
+            Diagnostic::new(DiagnosticCode::L2NodeMismatch, message).chain(chain_id)
+        }),
+    );
+
+    inspection.warnings.extend(warnings);
+    inspection.l2 = Some(l2);
+}
+
+/// Compares the latest sealed batch reported by the L2 node with the batch
+/// counters of the chain diamond on its settlement layer: the gateway when it
+/// was followed, L1 otherwise. Requires `inspect_l2_node` to have run first.
+pub fn inspect_batch_progress(
+    settlement_client: &dyn RpcClient,
+    l2_client: &dyn RpcClient,
+    inspection: &mut ChainInspection,
+) {
+    let chain_id = inspection.chain.chain_id;
+    let chain_contract = match inspection.gateway.as_deref() {
+        Some(gateway) => gateway.chain.chain_contract,
+        None => inspection.chain.chain_contract,
+    };
+    let Some(latest_sealed) = inspection.l2.as_ref().and_then(|l2| l2.latest_l1_batch) else {
+        return;
+    };
+    let Some(chain_contract) = chain_contract else {
+        inspection.warnings.push(
+            Diagnostic::new(
+                DiagnosticCode::Skipped,
+                format!("chain {chain_id} has no diamond; skipping batch progress"),
+            )
+            .chain(chain_id),
+        );
+        return;
+    };
+    let (committed, proven, executed) =
+        match diamond::get_total_batches(settlement_client, chain_contract) {
+            Ok(counters) => counters,
+            Err(err) => {
+                inspection.warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getTotalBatchesCommitted",
+                        format!("failed to resolve batch counters for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id)
+                    .contract(chain_contract),
+                );
+                return;
+            }
+        };
+
+    let mut timestamps = BTreeMap::new();
+    for batch in [latest_sealed, committed, proven, executed] {
+        if timestamps.contains_key(&batch) {
+            continue;
+        }
+        match l2::get_l1_batch_timestamp(l2_client, batch) {
+            Ok(timestamp) => {
+                timestamps.insert(batch, Some(timestamp));
+            }
+            Err(err) => {
+                timestamps.insert(batch, None);
+                inspection.warnings.push(
+                    Diagnostic::resolution_failed(
+                        "zks_getL1BatchDetails",
+                        format!("failed to resolve l1 batch {batch} from l2 rpc"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
+            }
+        }
+    }
+    let lag = |batch: u64| BatchLag {
+        batches: latest_sealed.saturating_sub(batch),
+        seconds: match (timestamps[&latest_sealed], timestamps[&batch]) {
+            (Some(latest), Some(timestamp)) => Some(latest.saturating_sub(timestamp)),
+            _ => None,
+        },
+    };
+    let progress = BatchProgress {
+        latest_sealed,
+        committed,
+        proven,
+        executed,
+        commit_lag: lag(committed),
+        proof_lag: lag(proven),
+        execution_lag: lag(executed),
+    };
+    if let Some(l2) = inspection.l2.as_mut() {
+        l2.batch_progress = Some(progress);
+    }
+}
+
+fn l2_value<T>(
+    method: &str,
+    result: Result<T, bridgehub::BridgehubError>,
+    chain_id: u64,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    method,
+                    format!("failed to resolve {method} from l2 rpc"),
+                    err,
+                )
+                .chain(chain_id),
+            );
+            None
+        }
+    }
+}
+
+fn mismatch<T: PartialEq + fmt::Display>(
+    field: &str,
+    l2: Option<T>,
+    l1: Option<T>,
+) -> Option<String> {
+    match (l2, l1) {
+        (Some(l2), Some(l1)) if l2 != l1 => {
+            Some(format!("l2 node reports {field} {l2}, but L1 reports {l1}"))
+        }
+        _ => None,
+    }
+}
+
+fn resolve_settlement_layer(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    warnings: &mut Vec<Diagnostic>,
+) -> SettlementLayer {
+    let mut settlement = SettlementLayer::default();
+
+    match client.eth_chain_id() {
+        Ok(l1_chain_id) => settlement.l1_chain_id = Some(l1_chain_id),
+        Err(err) => warnings.push(Diagnostic::resolution_failed(
+            "eth_chainId",
+            "failed to resolve eth_chainId of the bridgehub network",
+            err,
+        )),
+    }
+
+    match bridgehub::get_settlement_layer(client, bridgehub, chain_id) {
+        Ok(0) => {}
+        Ok(settlement_chain_id) => settlement.settlement_chain_id = Some(settlement_chain_id),
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "settlementLayer",
+                format!("failed to resolve settlementLayer for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    match bridgehub::get_is_whitelisted_settlement_layer(client, bridgehub, chain_id) {
+        Ok(is_settlement_layer) => settlement.is_settlement_layer = Some(is_settlement_layer),
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "whitelistedSettlementLayers",
+                format!("failed to resolve whitelistedSettlementLayers for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    let mut diamond_resolved = false;
+    if let Some(chain_contract) = chain_contract {
+        match diamond::get_settlement_layer(client, chain_contract) {
+            Ok(address) => {
+                diamond_resolved = true;
+                settlement.diamond_settlement_layer = (!address.is_zero()).then_some(address);
+            }
+            Err(err) => warnings.push(
+                Diagnostic::resolution_failed(
+                    "getSettlementLayer",
+                    format!("failed to resolve getSettlementLayer for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            ),
+        }
+    }
+
+    settlement.migrated = match (settlement.settlement_chain_id, settlement.l1_chain_id) {
+        (Some(settlement_chain_id), Some(l1_chain_id)) => Some(settlement_chain_id != l1_chain_id),
+        _ if settlement.diamond_settlement_layer.is_some() => Some(true),
+        _ => None,
+    };
+
+    if settlement.migrated == Some(true)
+        && let Some(settlement_chain_id) = settlement.settlement_chain_id
+    {
+        match bridgehub::get_is_whitelisted_settlement_layer(client, bridgehub, settlement_chain_id)
+        {
+            Ok(whitelisted) => {
+                settlement.settlement_layer_whitelisted = Some(whitelisted);
+                if !whitelisted {
+                    let message = format!(
+                        "chain {chain_id} settles on chain {settlement_chain_id}, which is not a whitelisted settlement layer"
+                    );
+                    warnings.push(
+                        Diagnostic::new(DiagnosticCode::SettlementLayerMismatch, message)
+                            .chain(chain_id),
+                    );
+                }
+            }
+            Err(err) => {
+                let message = format!(
+                    "failed to resolve whitelistedSettlementLayers for settlement layer {settlement_chain_id}"
+                );
+                warnings.push(
+                    Diagnostic::resolution_failed("whitelistedSettlementLayers", message, err)
+                        .chain(chain_id),
+                );
+            }
+        }
+
+        if diamond_resolved && settlement.diamond_settlement_layer.is_none() {
+            let message = format!(
+                "bridgehub reports chain {chain_id} settling on chain {settlement_chain_id}, but its diamond has no settlement layer set"
+            );
+            warnings.push(
+                Diagnostic::new(DiagnosticCode::SettlementLayerMismatch, message).chain(chain_id),
+            );
+        }
+    }
+
+    settlement
+}
+
+/// Reads `pendingOwner()`; a zero address means no transfer is in flight.
+fn resolve_pending_owner(
+    client: &dyn RpcClient,
+    contract: Address,
+    label: &str,
+    chain_id: u64,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<Address> {
+    match bridgehub::get_contract_pending_owner(client, contract) {
+        Ok(address) if !address.is_zero() => {
+            let message = format!(
+                "{label} of chain {chain_id} has pending owner {address} awaiting acceptOwnership()"
+            );
+            warnings.push(Diagnostic::new(DiagnosticCode::PendingOwner, message).chain(chain_id));
+            Some(address)
+        }
+        Ok(_) => None,
+        Err(err) => {
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "pendingOwner",
+                    format!("failed to resolve pendingOwner() for {label} on chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
+            None
+        }
+    }
+}
+
+fn resolve_fee_configuration(
+    client: &dyn RpcClient,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    warnings: &mut Vec<Diagnostic>,
+) -> (Option<FeeParams>, Option<u64>, Option<Address>) {
+    let Some(chain_contract) = chain_contract else {
+        return (None, None, None);
+    };
+
+    let fee_params = match diamond::get_fee_params(client, chain_contract) {
+        Ok(fee_params) => Some(fee_params),
+        Err(err) => {
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "eth_getStorageAt",
+                    format!("failed to resolve fee params for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
+            None
+        }
+    };
+
+    let priority_tx_max_gas_limit =
+        match diamond::get_priority_tx_max_gas_limit(client, chain_contract) {
+            Ok(limit) => Some(limit),
+            Err(err) => {
+                warnings.push(
+                    Diagnostic::resolution_failed(
+                        "getPriorityTxMaxGasLimit",
+                        format!("failed to resolve getPriorityTxMaxGasLimit for chain {chain_id}"),
+                        err,
+                    )
+                    .chain(chain_id),
+                );
+                None
+            }
+        };
+
+    let transaction_filterer = match diamond::get_transaction_filterer(client, chain_contract) {
+        Ok(address) => {
+            if !address.is_zero() {
+                let message = format!(
+                    "chain {chain_id} has transaction filterer {address} set; it can censor L1->L2 priority transactions"
+                );
+                warnings.push(
+                    Diagnostic::new(DiagnosticCode::TransactionFilterer, message).chain(chain_id),
+                );
+            }
+            Some(address)
+        }
+        Err(err) => {
+            warnings.push(
+                Diagnostic::resolution_failed(
+                    "getTransactionFilterer",
+                    format!("failed to resolve getTransactionFilterer for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            );
+            None
+        }
+    };
+
+    (fee_params, priority_tx_max_gas_limit, transaction_filterer)
+}
+
+fn resolve_data_availability(
+    client: &dyn RpcClient,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    known: &[KnownDaValidator],
+    warnings: &mut Vec<Diagnostic>,
+) -> DataAvailability {
+    let mut data_availability = DataAvailability::default();
+    let Some(chain_contract) = chain_contract else {
+        return data_availability;
+    };
+
+    match diamond::get_pubdata_pricing_mode(client, chain_contract) {
+        Ok(mode) => match data_availability::pricing_mode_name(mode) {
+            Some(name) => data_availability.pricing_mode = Some(name.to_string()),
+            None => warnings.push(
+                Diagnostic::new(
+                    DiagnosticCode::DataAvailabilityMismatch,
+                    format!("chain {chain_id} returned unknown pubdata pricing mode {mode}"),
+                )
+                .chain(chain_id),
+            ),
+        },
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "getPubdataPricingMode",
+                format!("failed to resolve getPubdataPricingMode for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    match diamond::get_da_validator_pair(client, chain_contract) {
+        Ok((l1_validator, l2_validator)) => {
+            data_availability.l1_validator = (!l1_validator.is_zero()).then_some(l1_validator);
+            data_availability.l2_validator = (!l2_validator.is_zero()).then_some(l2_validator);
+        }
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "getDAValidatorPair",
+                format!("failed to resolve getDAValidatorPair for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    if let Some(layer) = data_availability::pricing_mode_mismatch(&data_availability, known) {
+        let message = format!(
+            "chain {chain_id} uses a {layer} DA validator but its pubdata pricing mode is {}",
+            data_availability
+                .pricing_mode
+                .as_deref()
+                .unwrap_or("unknown")
+        );
+        warnings.push(
+            Diagnostic::new(DiagnosticCode::DataAvailabilityMismatch, message).chain(chain_id),
+        );
+    }
+    data_availability.classification =
+        data_availability::classify_data_availability(&data_availability, known);
+
+    data_availability
+}
+
+fn resolve_base_token(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    warnings: &mut Vec<Diagnostic>,
+) -> BaseToken {
+    let mut base_token = BaseToken::default();
+
+    match bridgehub::get_base_token_asset_id(client, bridgehub, chain_id) {
+        Ok(asset_id) => base_token.asset_id = Some(asset_id),
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "baseTokenAssetId",
+                format!("failed to resolve baseTokenAssetId for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    match bridgehub::get_base_token(client, bridgehub, chain_id) {
+        Ok(address) if !address.is_zero() => base_token.address = Some(address),
+        Ok(_) => {}
+        Err(err) => warnings.push(
+            Diagnostic::resolution_failed(
+                "baseToken",
+                format!("failed to resolve baseToken for chain {chain_id}"),
+                err,
+            )
+            .chain(chain_id),
+        ),
+    }
+
+    if let Some(asset_id) = base_token.asset_id.as_deref() {
+        let native_token_vault = bridgehub::get_asset_router(client, bridgehub)
+            .and_then(|asset_router| token::get_native_token_vault(client, asset_router));
+        match native_token_vault {
+            Ok(native_token_vault) => {
+                match token::get_vault_origin_chain_id(client, native_token_vault, asset_id) {
+                    Ok(origin_chain_id) => base_token.origin_chain_id = Some(origin_chain_id),
+                    Err(err) => {
+                        let message = format!(
+                            "failed to resolve originChainId for base token asset {asset_id} of chain {chain_id}"
+                        );
+                        warnings.push(
+                            Diagnostic::resolution_failed("originChainId", message, err)
+                                .chain(chain_id),
+                        );
+                    }
+                }
+                match token::get_vault_token_address(client, native_token_vault, asset_id) {
+                    Ok(address) => match base_token.address {
+                        Some(base_token_address) if base_token_address != address => {
+                            let message = format!(
+                                "native token vault resolves base token asset {asset_id} of chain {chain_id} to {address}, but baseToken returned {base_token_address}"
+                            );
+                            warnings.push(
+                                Diagnostic::new(DiagnosticCode::BaseTokenMismatch, message)
+                                    .chain(chain_id),
+                            );
+                        }
+                        Some(_) => {}
+                        None if !address.is_zero() => base_token.address = Some(address),
+                        None => {}
+                    },
+                    Err(err) => {
+                        let message = format!(
+                            "failed to resolve tokenAddress for base token asset {asset_id} of chain {chain_id}"
+                        );
+                        warnings.push(
+                            Diagnostic::resolution_failed("tokenAddress", message, err)
+                                .chain(chain_id),
+                        );
+                    }
+                }
+            }
+            Err(err) => warnings.push(
+                Diagnostic::resolution_failed(
+                    "nativeTokenVault",
+                    format!("failed to resolve native token vault for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id),
+            ),
+        }
+    }
+
+    if let Some(address) = base_token.address {
+        if token::is_eth_token(address) {
+            base_token.name = Some("Ether".to_string());
+            base_token.symbol = Some("ETH".to_string());
+            base_token.decimals = Some(18);
+        } else {
+            match token::get_erc20_name(client, address) {
+                Ok(name) => base_token.name = Some(name),
+                Err(err) => warnings.push(
+                    Diagnostic::resolution_failed(
+                        "name",
+                        format!(
+                            "failed to resolve name() for base token {address} of chain {chain_id}"
+                        ),
+                        err,
+                    )
+                    .chain(chain_id)
+                    .contract(address),
+                ),
+            }
+            match token::get_erc20_symbol(client, address) {
+                Ok(symbol) => base_token.symbol = Some(symbol),
+                Err(err) => {
+                    let message = format!(
+                        "failed to resolve symbol() for base token {address} of chain {chain_id}"
+                    );
+                    warnings.push(
+                        Diagnostic::resolution_failed("symbol", message, err)
+                            .chain(chain_id)
+                            .contract(address),
+                    );
+                }
+            }
+            match token::get_erc20_decimals(client, address) {
+                Ok(decimals) => base_token.decimals = Some(decimals),
+                Err(err) => {
+                    let message = format!(
+                        "failed to resolve decimals() for base token {address} of chain {chain_id}"
+                    );
+                    warnings.push(
+                        Diagnostic::resolution_failed("decimals", message, err)
+                            .chain(chain_id)
+                            .contract(address),
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(chain_contract) = chain_contract {
+        match diamond::get_base_token_gas_price_multiplier(client, chain_contract) {
+            Ok((nominator, denominator)) => {
+                base_token.gas_price_multiplier_nominator = Some(nominator);
+                base_token.gas_price_multiplier_denominator = Some(denominator);
+            }
+            Err(err) => warnings.push(
+                Diagnostic::resolution_failed(
+                    "baseTokenGasPriceMultiplierNominator",
+                    format!(
+                        "failed to resolve base token gas price multiplier for chain {chain_id}"
+                    ),
+                    err,
+                )
+                .chain(chain_id),
+            ),
+        }
+    }
+
+    base_token
+}
+
+fn resolve_timelock_validators(
+    client: &dyn RpcClient,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    validator_timelock: Address,
+    kind: ValidatorTimelockKind,
+    candidates: &[Address],
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<Vec<TimelockValidator>> {
+    match kind {
+        ValidatorTimelockKind::PostV29 => {
+            let chain_contract = chain_contract?;
+            let mut roles_by_address: BTreeMap<Address, Vec<String>> = BTreeMap::new();
+            for role in ValidatorTimelockRole::ALL {
+                let count = match bridgehub::get_timelock_role_member_count(
+                    client,
+                    validator_timelock,
+                    chain_contract,
+                    role,
+                ) {
+                    Ok(count) => count,
+                    Err(err) => {
+                        let message = format!(
+                            "failed to resolve {} role member count for chain {chain_id} from validator timelock {validator_timelock}",
+                            role.name()
+                        );
+                        warnings.push(
+                            Diagnostic::resolution_failed("getRoleMemberCount", message, err)
+                                .chain(chain_id)
+                                .contract(validator_timelock),
+                        );
+                        return None;
+                    }
+                };
+                for index in 0..count {
+                    match bridgehub::get_timelock_role_member(
+                        client,
+                        validator_timelock,
+                        chain_contract,
+                        role,
+                        index,
+                    ) {
+                        Ok(address) => roles_by_address
+                            .entry(address)
+                            .or_default()
+                            .push(role.name().to_string()),
+                        Err(err) => {
+                            let message = format!(
+                                "failed to resolve {} role member index {index} for chain {chain_id} from validator timelock {validator_timelock}",
+                                role.name()
+                            );
+                            warnings.push(
+                                Diagnostic::resolution_failed("getRoleMember", message, err)
+                                    .chain(chain_id)
+                                    .contract(validator_timelock),
+                            );
+                            return None;
+                        }
+                    }
+                }
+            }
+
+            Some(
+                roles_by_address
+                    .into_iter()
+                    .map(|(address, roles)| TimelockValidator { address, roles })
+                    .collect(),
+            )
+        }
+        ValidatorTimelockKind::Legacy => {
+            if candidates.is_empty() {
+                let message = format!(
+                    "legacy validator timelock {validator_timelock} for chain {chain_id} cannot enumerate validators and no candidate addresses were provided"
+                );
+                warnings.push(
+                    Diagnostic::new(DiagnosticCode::LegacyValidatorTimelock, message)
+                        .chain(chain_id)
+                        .contract(validator_timelock),
+                );
+                return None;
+            }
+
+            let mut validators = Vec::new();
+            for &candidate in candidates {
+                match bridgehub::get_legacy_timelock_is_validator(
+                    client,
+                    validator_timelock,
+                    chain_id,
+                    candidate,
+                ) {
+                    Ok(true) => validators.push(TimelockValidator {
+                        address: candidate,
+                        roles: vec!["validator".to_string()],
+                    }),
+                    Ok(false) => {}
+                    Err(err) => {
+                        let message = format!(
+                            "failed to resolve validators({chain_id}, {candidate}) from legacy validator timelock {validator_timelock}"
+                        );
+                        warnings.push(
+                            Diagnostic::resolution_failed("validators", message, err)
+                                .chain(chain_id)
+                                .contract(validator_timelock),
+                        );
+                        return None;
+                    }
+                }
+            }
+            Some(validators)
+        }
+    }
+}
+
+fn resolve_multisig_committer_details(
+    client: &dyn RpcClient,
+    chain_id: u64,
+    chain_contract: Option<Address>,
+    validator_timelock: Option<Address>,
+    warnings: &mut Vec<Diagnostic>,
+) -> (Option<String>, Option<u64>, Option<Vec<Address>>) {
+    let (Some(chain_contract), Some(validator_timelock)) = (chain_contract, validator_timelock)
+    else {
+        return (None, None, None);
+    };
+
+    let use_custom = match bridgehub::get_multisig_is_custom_signing_set_active(
+        client,
+        validator_timelock,
+        chain_contract,
+    ) {
+        Ok(use_custom) => use_custom,
+        Err(err) => {
+            let message = format!(
+                "failed to resolve multisig configuration for chain {chain_id} from validator timelock {validator_timelock}"
+            );
+            warnings.push(
+                Diagnostic::resolution_failed("isCustomSigningSetActive", message, err)
+                    .chain(chain_id)
+                    .contract(validator_timelock),
+            );
+            return (None, None, None);
+        }
+    };
+
+    let signing_threshold = match bridgehub::get_multisig_signing_threshold(
+        client,
+        validator_timelock,
+        chain_contract,
+    ) {
+        Ok(threshold) => Some(threshold),
+        Err(err) => {
+            let message = format!(
+                "failed to resolve multisig threshold for chain {chain_id} from validator timelock {validator_timelock}"
+            );
+            warnings.push(
+                Diagnostic::resolution_failed("getSigningThreshold", message, err)
+                    .chain(chain_id)
+                    .contract(validator_timelock),
+            );
+            None
+        }
+    };
+
+    let validators = match bridgehub::get_multisig_validators_count(
+        client,
+        validator_timelock,
+        chain_contract,
+    ) {
+        Ok(count) => {
+            let mut validators = Vec::with_capacity(count as usize);
+            for index in 0..count {
+                match bridgehub::get_multisig_validator_member(
+                    client,
+                    validator_timelock,
+                    chain_contract,
+                    index,
+                ) {
+                    Ok(address) => validators.push(address),
+                    Err(err) => {
+                        let message = format!(
+                            "failed to resolve validator index {index} for chain {chain_id} from validator timelock {validator_timelock}"
+                        );
+                        warnings.push(
+                            Diagnostic::resolution_failed("validators", message, err)
+                                .chain(chain_id)
+                                .contract(validator_timelock),
+                        );
+                        return (
+                            Some(if use_custom {
+                                "custom".to_string()
+                            } else {
+                                "shared".to_string()
+                            }),
+                            signing_threshold,
+                            None,
+                        );
+                    }
+                }
+            }
+            Some(validators)
+        }
+        Err(err) => {
+            let message = format!(
+                "failed to resolve multisig validators count for chain {chain_id} from validator timelock {validator_timelock}"
+            );
+            warnings.push(
+                Diagnostic::resolution_failed("getValidatorsCount", message, err)
+                    .chain(chain_id)
+                    .contract(validator_timelock),
+            );
+            None
+        }
+    };
+
+    (
+        Some(if use_custom {
+            "custom".to_string()
+        } else {
+            "shared".to_string()
+        }),
+        signing_threshold,
+        validators,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy_primitives::{Address, LogData, U256, address};
+    use alloy_sol_types::SolEvent;
+
+    use crate::model::ProtocolSemver;
+    use crate::rpc::{LogFilter, RpcClient, RpcError, RpcLog};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockRpcClient {
+        chain_id: Option<u64>,
+        responses: HashMap<String, Result<String, RpcError>>,
+        storage: HashMap<String, Result<String, RpcError>>,
+        block_number: Option<u64>,
+        logs: HashMap<String, Vec<RpcLog>>,
+        historical_responses: HashMap<String, Vec<(u64, String)>>,
+        code: HashMap<String, String>,
+    }
+
+    impl MockRpcClient {
+        fn with_response(mut self, data: &str, response: Result<String, RpcError>) -> Self {
+            self.responses.insert(data.to_string(), response);
+            self
+        }
+
+        fn with_timelock_role_members(
+            self,
+            chain_contract: Address,
+            role: ValidatorTimelockRole,
+            members: &[Address],
+        ) -> Self {
+            let count_data = bridgehub::encode_get_role_member_count_calldata(chain_contract, role);
+            let mut mock = self.with_response(&count_data, Ok(format!("0x{:064x}", members.len())));
+            for (index, member) in members.iter().enumerate() {
+                let member_data =
+                    bridgehub::encode_get_role_member_calldata(chain_contract, role, index as u64);
+                mock =
+                    mock.with_response(&member_data, Ok(format!("0x{:0>64}", hex::encode(member))));
+            }
+            mock
+        }
+
+        fn with_eth_base_token(self, chain_id: u64) -> Self {
+            let asset_id = "0x05e1c3ae2ad34c6c8ecb6c6e2b1fea6c7c4e1e77bd1e7b9d3f5bbcf1ad2a0f2b";
+            let eth = "0x0000000000000000000000000000000000000000000000000000000000000001";
+            self.with_response(
+                &bridgehub::encode_base_token_asset_id_calldata(chain_id),
+                Ok(asset_id.to_string()),
+            )
+            .with_response(
+                &bridgehub::encode_base_token_calldata(chain_id),
+                Ok(eth.to_string()),
+            )
+            .with_response(
+                &bridgehub::encode_asset_router_calldata(),
+                Ok(
+                    "0x0000000000000000000000005555555555555555555555555555555555555555"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &token::encode_native_token_vault_calldata(),
+                Ok(
+                    "0x0000000000000000000000006666666666666666666666666666666666666666"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &token::encode_token_address_calldata(asset_id)
+                    .expect("token address calldata should encode"),
+                Ok(eth.to_string()),
+            )
+            .with_response(
+                &token::encode_origin_chain_id_calldata(asset_id)
+                    .expect("origin chain id calldata should encode"),
+                Ok(eth.to_string()),
+            )
+            .with_response(
+                &diamond::encode_base_token_gas_price_multiplier_nominator_calldata(),
+                Ok(eth.to_string()),
+            )
+            .with_response(
+                &diamond::encode_base_token_gas_price_multiplier_denominator_calldata(),
+                Ok(eth.to_string()),
+            )
+        }
+
+        fn with_chain_id(mut self, chain_id: u64) -> Self {
+            self.chain_id = Some(chain_id);
+            self
+        }
+
+        fn with_l1_settlement(self, chain_id: u64) -> Self {
+            let zero = "0x0000000000000000000000000000000000000000000000000000000000000000";
+            self.with_chain_id(1)
+                .with_response(
+                    &bridgehub::encode_settlement_layer_calldata(chain_id),
+                    Ok(
+                        "0x0000000000000000000000000000000000000000000000000000000000000001"
+                            .to_string(),
+                    ),
+                )
+                .with_response(
+                    &bridgehub::encode_whitelisted_settlement_layers_calldata(chain_id),
+                    Ok(zero.to_string()),
+                )
+                .with_response(
+                    &diamond::encode_get_settlement_layer_calldata(),
+                    Ok(zero.to_string()),
+                )
+        }
+
+        fn with_rollup_data_availability(self) -> Self {
+            self.with_response(
+                &diamond::encode_get_pubdata_pricing_mode_calldata(),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &diamond::encode_get_da_validator_pair_calldata(),
+                Ok("0x\
+                    000000000000000000000000d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1d1\
+                    000000000000000000000000d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2d2"
+                    .to_string()),
+            )
+        }
+
+        /// Serves `response` for `data` from `since_block` onwards (archive calls only).
+        fn with_response_since(mut self, data: &str, since_block: u64, response: &str) -> Self {
+            self.historical_responses
+                .entry(data.to_string())
+                .or_default()
+                .push((since_block, response.to_string()));
+            self
+        }
+
+        fn with_log(mut self, address: Address, block_number: u64, log: LogData) -> Self {
+            let address = format!("{address:#x}");
+            self.block_number = self.block_number.max(Some(block_number));
+            self.logs.entry(address.clone()).or_default().push(RpcLog {
+                address,
+                topics: log
+                    .topics()
+                    .iter()
+                    .map(|topic| format!("{topic:#x}"))
+                    .collect(),
+                data: format!("0x{}", hex::encode(&log.data)),
+                block_number,
+                transaction_hash: format!("0x{block_number:064x}"),
+                log_index: 0,
+            });
+            self
+        }
+
+        fn with_code(mut self, address: Address, code: &str) -> Self {
+            self.code.insert(format!("{address:#x}"), code.to_string());
+            self
+        }
+
+        fn with_storage(mut self, slot: &str, response: Result<String, RpcError>) -> Self {
+            self.storage.insert(slot.to_string(), response);
+            self
+        }
+
+        fn with_fee_configuration(self, filterer: Address) -> Self {
+            // Rollup pricing, 800k batch overhead, 120k max pubdata, 80M max L2 gas,
+            // 99k priority tx pubdata, 0.25 gwei minimal L2 gas price.
+            self.with_storage(
+                "0x26",
+                Ok(
+                    "0x00000000000000000000000ee6b280000182b804c4b4000001d4c0000c350000"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &diamond::encode_get_priority_tx_max_gas_limit_calldata(),
+                Ok(
+                    "0x000000000000000000000000000000000000000000000000000000000047b760"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &diamond::encode_get_transaction_filterer_calldata(),
+                Ok(format!("0x{:0>64}", hex::encode(filterer))),
+            )
+        }
+
+        fn with_empty_timelock_roles(self, chain_contract: Address) -> Self {
+            let mut mock = self.with_response(
+                &bridgehub::encode_execution_delay_calldata(),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                ),
+            );
+            for role in ValidatorTimelockRole::ALL {
+                mock = mock.with_timelock_role_members(chain_contract, role, &[]);
+            }
+            mock
+        }
+    }
+
+    impl RpcClient for MockRpcClient {
+        fn eth_call(&self, _to: &str, data: &str) -> Result<String, RpcError> {
+            self.responses.get(data).cloned().unwrap_or_else(|| {
+                Err(RpcError::InvalidResponse(format!(
+                    "missing mock response for calldata: {data}"
+                )))
+            })
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            self.chain_id.ok_or_else(|| {
+                RpcError::InvalidResponse("missing mock response for eth_chainId".to_string())
+            })
+        }
+
+        fn eth_get_storage_at(&self, _address: &str, slot: &str) -> Result<String, RpcError> {
+            self.storage.get(slot).cloned().unwrap_or_else(|| {
+                Err(RpcError::InvalidResponse(format!(
+                    "missing mock response for storage slot: {slot}"
+                )))
+            })
+        }
+
+        fn eth_call_at_block(&self, to: &str, data: &str, block: u64) -> Result<String, RpcError> {
+            match self.historical_responses.get(data) {
+                Some(history) => history
+                    .iter()
+                    .filter(|(since_block, _)| *since_block <= block)
+                    .max_by_key(|(since_block, _)| *since_block)
+                    .map(|(_, response)| Ok(response.clone()))
+                    .unwrap_or_else(|| Err(RpcError::Transport("execution reverted".to_string()))),
+                None => self.eth_call(to, data),
+            }
+        }
+
+        fn eth_get_code(&self, address: &str) -> Result<String, RpcError> {
+            self.code
+                .get(address)
+                .cloned()
+                .ok_or(RpcError::Unsupported("eth_getCode"))
+        }
+
+        fn eth_block_number(&self) -> Result<u64, RpcError> {
+            self.block_number.ok_or_else(|| {
+                RpcError::InvalidResponse("missing mock response for eth_blockNumber".to_string())
+            })
+        }
+
+        fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            Ok(self
+                .logs
+                .get(&filter.address)
+                .map(|logs| {
+                    logs.iter()
+                        .filter(|log| {
+                            (filter.from_block..=filter.to_block).contains(&log.block_number)
+                        })
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn topology_scanner_collects_and_dedupes_ctms() {
+        let chain_ids_data = bridgehub::encode_get_all_zk_chain_chain_ids_calldata();
+        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
+        let chain_325_data = bridgehub::encode_chain_type_manager_calldata(325);
+        let protocol_version_data = bridgehub::encode_protocol_version_calldata();
+
+        let mock = MockRpcClient::default()
+            .with_response(
+                &chain_ids_data,
+                Ok("0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000001440000000000000000000000000000000000000000000000000000000000000145".to_string()),
+            )
+            .with_response(
+                &chain_324_data,
+                Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+            )
+            .with_response(
+                &chain_325_data,
+                Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+            )
+            .with_response(
+                &protocol_version_data,
+                Ok("0x0000000000000000000000000000000000000000000000000000000000000007".to_string()),
+            )
+;
+
+        let snapshot = scan_bridgehub_topology(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions::default(),
+        )
+        .expect("scan should succeed");
+
+        assert_eq!(snapshot.chain_ids, vec![324, 325]);
+        assert_eq!(snapshot.chain_ctms.len(), 2);
+        assert_eq!(snapshot.ctms.len(), 1);
+        assert!(snapshot.warnings.is_empty());
+        assert_eq!(
+            snapshot.ctms[0].address,
+            address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+        );
+        assert_eq!(
+            snapshot.ctms[0].protocol_version,
+            Some(ProtocolSemver::new(0, 0, 7))
+        );
+    }
+
+    #[test]
+    fn inspect_chain_resolves_deep_details() {
+        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
+        let chain_324_zk_chain_data = bridgehub::encode_get_zk_chain_calldata(324);
+        let validator_timelock_data = bridgehub::encode_validator_timelock_post_v29_calldata();
+        let owner_data = bridgehub::encode_owner_calldata();
+        let chain_324_admin_data = bridgehub::encode_get_chain_admin_calldata(324);
+        let chain_324_protocol_data = bridgehub::encode_get_chain_protocol_version_calldata(324);
+
+        let mock = MockRpcClient::default()
+            .with_response(
+                &chain_324_data,
+                Ok(
+                    "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_zk_chain_data,
+                Ok(
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &validator_timelock_data,
+                Ok(
+                    "0x0000000000000000000000007777777777777777777777777777777777777777"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_admin_data,
+                Ok(
+                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &owner_data,
+                Ok(
+                    "0x0000000000000000000000004444444444444444444444444444444444444444"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_protocol_data,
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000007"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_pending_owner_calldata(),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                ),
+            )
+            .with_empty_timelock_roles(address!("0xcccccccccccccccccccccccccccccccccccccccc"))
+            .with_eth_base_token(324)
+            .with_rollup_data_availability()
+            .with_l1_settlement(324)
+            .with_fee_configuration(address!("0x0000000000000000000000000000000000000000"));
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions::default(),
+        )
+        .expect("inspect should succeed");
+
+        assert_eq!(inspection.chain.chain_id, 324);
+        assert_eq!(
+            inspection.chain.ctm,
+            Some(address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"))
+        );
+        assert_eq!(
+            inspection.chain.chain_contract,
+            Some(address!("0xcccccccccccccccccccccccccccccccccccccccc"))
+        );
+        assert_eq!(
+            inspection.chain.validator_timelock,
+            Some(address!("0x7777777777777777777777777777777777777777"))
+        );
+        assert_eq!(
+            inspection.chain.validator_timelock_owner,
+            Some(address!("0x4444444444444444444444444444444444444444"))
+        );
+        assert_eq!(
+            inspection.chain.admin,
+            Some(address!("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"))
+        );
+        assert_eq!(
+            inspection.chain.admin_owner,
+            Some(address!("0x4444444444444444444444444444444444444444"))
+        );
+        assert_eq!(
+            inspection.chain.protocol_version,
+            Some(ProtocolSemver::new(0, 0, 7))
+        );
+        assert_eq!(inspection.chain.validator_timelock_pending_owner, None);
+        assert_eq!(inspection.chain.admin_pending_owner, None);
+        assert_eq!(inspection.chain.multisig_signing_set_mode, None);
+        assert_eq!(inspection.chain.multisig_signing_threshold, None);
+        assert_eq!(inspection.chain.multisig_validators, None);
+        assert_eq!(inspection.chain.validator_timelock_execution_delay, Some(0));
+        assert_eq!(inspection.chain.timelock_validators, Some(vec![]));
+        assert_eq!(inspection.chain.base_token.symbol.as_deref(), Some("ETH"));
+        assert_eq!(
+            inspection.chain.data_availability.classification.as_deref(),
+            Some("rollup (calldata/blobs)")
+        );
+        assert_eq!(inspection.chain.settlement.l1_chain_id, Some(1));
+        assert_eq!(inspection.chain.settlement.migrated, Some(false));
+        assert_eq!(inspection.gateway, None);
+        assert_eq!(inspection.chain.base_token.origin_chain_id, Some(1));
+        assert_eq!(
+            inspection.chain.base_token.gas_price_multiplier_nominator,
+            Some(1)
+        );
+        assert_eq!(
+            inspection.chain.fee_params,
+            Some(FeeParams {
+                pubdata_pricing_mode: "rollup".to_string(),
+                batch_overhead_l1_gas: 800_000,
+                max_pubdata_per_batch: 120_000,
+                max_l2_gas_per_batch: 80_000_000,
+                priority_tx_max_pubdata: 99_000,
+                minimal_l2_gas_price: 250_000_000,
+            })
+        );
+        assert_eq!(inspection.chain.priority_tx_max_gas_limit, Some(4_700_000));
+        assert_eq!(
+            inspection.chain.transaction_filterer,
+            Some(address!("0x0000000000000000000000000000000000000000"))
+        );
+        assert!(inspection.warnings.is_empty());
+        assert!(inspection.unresolved.is_empty());
+    }
+
+    #[test]
+    fn inspect_chain_warns_about_transaction_filterer() {
+        let filterer = address!("0x3333333333333333333333333333333333333333");
+        let mock = MockRpcClient::default()
             .with_response(
-                &chain_ids_data,
-                Ok("0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000001440000000000000000000000000000000000000000000000000000000000000145".to_string()),
+                &bridgehub::encode_get_zk_chain_calldata(324),
+                Ok(
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                        .to_string(),
+                ),
+            )
+            .with_fee_configuration(filterer);
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions::default(),
+        )
+        .expect("inspect should succeed");
+
+        assert_eq!(inspection.chain.transaction_filterer, Some(filterer));
+        assert_eq!(inspection.chain.priority_tx_max_gas_limit, Some(4_700_000));
+        let warning = inspection
+            .warnings
+            .iter()
+            .find(|warning| warning.code == DiagnosticCode::TransactionFilterer)
+            .expect("filterer warning");
+        assert_eq!(
+            warning.to_string(),
+            format!(
+                "chain 324 has transaction filterer {filterer} set; it can censor L1->L2 priority transactions"
+            )
+        );
+        assert_eq!(warning.chain_id, Some(324));
+        // chainTypeManager is unmocked, so every field read through the CTM is
+        // unresolved rather than unset.
+        assert!(inspection.unresolved.contains("ctm"));
+        assert!(inspection.unresolved.contains("admin_owner"));
+        assert!(!inspection.unresolved.contains("transaction_filterer"));
+    }
+
+    #[test]
+    fn inspect_chain_reports_pending_admin_owner() {
+        let pending_owner = address!("0x5555555555555555555555555555555555555555");
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_chain_type_manager_calldata(324),
+                Ok(
+                    "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_get_chain_admin_calldata(324),
+                Ok(
+                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_pending_owner_calldata(),
+                Ok(format!("0x{:0>64}", hex::encode(pending_owner))),
+            );
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions::default(),
+        )
+        .expect("inspect should succeed");
+
+        assert_eq!(inspection.chain.admin_pending_owner, Some(pending_owner));
+        let warning = inspection
+            .warnings
+            .iter()
+            .find(|warning| warning.code == DiagnosticCode::PendingOwner)
+            .expect("pending owner warning");
+        assert_eq!(
+            warning.to_string(),
+            format!(
+                "admin of chain 324 has pending owner {pending_owner} awaiting acceptOwnership()"
+            )
+        );
+    }
+
+    #[test]
+    fn inspect_chain_builds_ownership_timeline() {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let chain_contract = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_get_zk_chain_calldata(324),
+                Ok(
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                        .to_string(),
+                ),
+            )
+            .with_log(
+                bridgehub,
+                50,
+                timeline::OwnershipTransferred {
+                    previousOwner: Address::repeat_byte(0x11),
+                    newOwner: Address::repeat_byte(0x22),
+                }
+                .encode_log_data(),
+            )
+            .with_log(
+                chain_contract,
+                20,
+                timeline::NewAdmin {
+                    oldAdmin: Address::ZERO,
+                    newAdmin: Address::repeat_byte(0xee),
+                }
+                .encode_log_data(),
+            );
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            bridgehub,
+            324,
+            &InspectOptions {
+                timeline: Some(HistoryOptions {
+                    from_block: 10,
+                    max_block_range: 1_000,
+                }),
+                ..InspectOptions::default()
+            },
+        )
+        .expect("inspect should succeed");
+
+        let timeline = inspection.timeline.expect("timeline should be built");
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|entry| (
+                    entry.block_number,
+                    entry.contract.as_str(),
+                    entry.event.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (20, "chain diamond", "NewAdmin"),
+                (50, "bridgehub", "OwnershipTransferred"),
+            ]
+        );
+        assert_eq!(
+            timeline[0].description,
+            "admin 0x0000000000000000000000000000000000000000 -> 0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"
+        );
+    }
+
+    #[test]
+    fn timeline_follows_owner_contracts_and_skips_eoas() {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let safe = Address::repeat_byte(0x5a);
+        let eoa = Address::repeat_byte(0xe0);
+        let filterer = Address::repeat_byte(0xf1);
+        let transfer = |to: u8| timeline::OwnershipTransferred {
+            previousOwner: Address::ZERO,
+            newOwner: Address::repeat_byte(to),
+        };
+        let mock = MockRpcClient::default()
+            .with_code(safe, "0x6080")
+            .with_code(eoa, "0x")
+            .with_code(filterer, "0x6080")
+            .with_log(safe, 30, transfer(0x31).encode_log_data())
+            .with_log(eoa, 40, transfer(0x41).encode_log_data())
+            .with_log(filterer, 60, transfer(0x61).encode_log_data());
+        let chain = ChainSummary {
+            chain_id: 324,
+            admin_owner: Some(safe),
+            validator_timelock_owner: Some(eoa),
+            transaction_filterer: Some(filterer),
+            ..Default::default()
+        };
+
+        let mut warnings = Vec::new();
+        let timeline = resolve_timeline(
+            &mock,
+            bridgehub,
+            &chain,
+            HistoryOptions {
+                from_block: 10,
+                max_block_range: 1_000,
+            },
+            &mut warnings,
+        )
+        .expect("timeline should be built");
+
+        assert!(warnings.is_empty());
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|entry| (entry.block_number, entry.contract.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(30, "chain admin owner"), (60, "transaction filterer")]
+        );
+    }
+
+    #[test]
+    fn upgrade_history_merges_diamond_and_ctm_events() {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let cut = upgrades::DiamondCutData {
+            facetCuts: vec![upgrades::FacetCut {
+                facet: Address::repeat_byte(0xf1),
+                action: 0,
+                isFreezable: true,
+                selectors: vec![[0x12, 0x34, 0x56, 0x78].into()],
+            }],
+            initAddress: Address::ZERO,
+            initCalldata: Default::default(),
+        };
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_chain_type_manager_calldata(324),
+                Ok(
+                    "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_get_zk_chain_calldata(324),
+                Ok(
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_get_chain_protocol_version_calldata(324),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000001d00000000"
+                        .to_string(),
+                ),
+            )
+            .with_log(
+                address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                100,
+                upgrades::NewUpgradeCutData {
+                    protocolVersion: U256::from(29u64) << 32usize,
+                    diamondCutData: cut.clone(),
+                }
+                .encode_log_data(),
+            )
+            .with_log(
+                address!("0xcccccccccccccccccccccccccccccccccccccccc"),
+                200,
+                upgrades::ExecuteUpgrade { diamondCut: cut }.encode_log_data(),
+            );
+
+        let history = scan_chain_upgrades(
+            &mock,
+            bridgehub,
+            324,
+            HistoryOptions {
+                from_block: 0,
+                max_block_range: 1_000,
+            },
+        )
+        .expect("upgrade scan should succeed");
+
+        assert_eq!(
+            history.current_protocol_version,
+            Some(ProtocolSemver::new(0, 29, 0))
+        );
+        assert_eq!(history.to_block, 200);
+        assert_eq!(
+            history
+                .events
+                .iter()
+                .map(|event| (
+                    event.block_number,
+                    event.source.as_str(),
+                    event.event.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (100, "ctm", "NewUpgradeCutData"),
+                (200, "chain diamond", "ExecuteUpgrade"),
+            ]
+        );
+        assert_eq!(
+            history.events[0].protocol_version,
+            Some(ProtocolSemver::new(0, 29, 0))
+        );
+        assert_eq!(history.events[1].facet_changes[0].action, "add");
+        assert!(history.warnings.is_empty());
+    }
+
+    #[test]
+    fn bisect_finds_admin_owner_change_block() {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_chain_type_manager_calldata(324),
+                Ok(
+                    "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_get_chain_admin_calldata(324),
+                Ok(
+                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                        .to_string(),
+                ),
+            )
+            .with_response_since(
+                &bridgehub::encode_owner_calldata(),
+                1_000,
+                "0x0000000000000000000000001111111111111111111111111111111111111111",
+            )
+            .with_response_since(
+                &bridgehub::encode_owner_calldata(),
+                4_321,
+                "0x0000000000000000000000002222222222222222222222222222222222222222",
+            );
+
+        let bisection = bisect_chain_field(
+            &mock,
+            bridgehub,
+            324,
+            BisectField::AdminOwner,
+            2_000,
+            Some(9_000),
+        )
+        .expect("bisect should succeed");
+
+        assert_eq!(bisection.changed_at_block, Some(4_321));
+        assert_eq!(
+            bisection.previous_value,
+            Some(address!("0x1111111111111111111111111111111111111111").to_string())
+        );
+        assert_eq!(
+            bisection.to_value,
+            Some(address!("0x2222222222222222222222222222222222222222").to_string())
+        );
+
+        let before_owner = bisect_chain_field(
+            &mock,
+            bridgehub,
+            324,
+            BisectField::AdminOwner,
+            0,
+            Some(2_000),
+        )
+        .expect("bisect should succeed");
+        assert_eq!(before_owner.from_value, None);
+        assert_eq!(before_owner.changed_at_block, Some(1_000));
+
+        assert!(matches!(
+            bisect_chain_field(&mock, bridgehub, 324, BisectField::AdminOwner, 10, Some(10)),
+            Err(ScanError::InvalidBlockRange(_))
+        ));
+    }
+
+    #[test]
+    fn capture_snapshot_records_block_and_inspects_every_chain() {
+        let mock = MockRpcClient {
+            block_number: Some(21_000_000),
+            ..MockRpcClient::default()
+        }
+        .with_chain_id(1)
+        .with_response(
+            &bridgehub::encode_get_all_zk_chain_chain_ids_calldata(),
+            Ok("0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000001440000000000000000000000000000000000000000000000000000000000000145".to_string()),
+        )
+        .with_response(
+            &bridgehub::encode_chain_type_manager_calldata(324),
+            Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+        )
+        .with_response(
+            &bridgehub::encode_chain_type_manager_calldata(325),
+            Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+        );
+
+        let snapshot = capture_snapshot(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions {
+                data_availability: true,
+                ..ScanOptions::default()
+            },
+            &InspectOptions::default(),
+        )
+        .expect("capture should succeed");
+
+        assert_eq!(snapshot.format_version, SNAPSHOT_FORMAT_VERSION);
+        assert_eq!(snapshot.network_chain_id, Some(1));
+        assert_eq!(snapshot.block_number, Some(21_000_000));
+        assert_eq!(
+            snapshot
+                .inspections
+                .iter()
+                .map(|inspection| inspection.chain.chain_id)
+                .collect::<Vec<_>>(),
+            vec![324, 325]
+        );
+        assert_eq!(
+            snapshot.inspections[0].chain.ctm,
+            Some(address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"))
+        );
+        // The DA breakdown comes from the inspections, not a second pass of
+        // topology reads.
+        assert_eq!(
+            snapshot
+                .topology
+                .chain_data_availability
+                .iter()
+                .map(|entry| entry.chain_id)
+                .collect::<Vec<_>>(),
+            vec![324, 325]
+        );
+        assert_eq!(
+            snapshot
+                .topology
+                .warnings
+                .iter()
+                .filter(|warning| warning.method.as_deref() == Some("getZKChain"))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn inspect_chain_verbose_resolves_multisig_commit_details() {
+        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
+        let chain_324_zk_chain_data = bridgehub::encode_get_zk_chain_calldata(324);
+        let validator_timelock_data = bridgehub::encode_validator_timelock_post_v29_calldata();
+        let owner_data = bridgehub::encode_owner_calldata();
+        let chain_324_admin_data = bridgehub::encode_get_chain_admin_calldata(324);
+        let chain_324_protocol_data = bridgehub::encode_get_chain_protocol_version_calldata(324);
+        let chain_contract = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+        let custom_signing_set_data =
+            bridgehub::encode_is_custom_signing_set_active_calldata(chain_contract);
+        let signing_threshold_data =
+            bridgehub::encode_get_signing_threshold_calldata(chain_contract);
+        let validators_count_data = bridgehub::encode_get_validators_count_calldata(chain_contract);
+        let validator_0_data = bridgehub::encode_get_validators_member_calldata(chain_contract, 0);
+        let validator_1_data = bridgehub::encode_get_validators_member_calldata(chain_contract, 1);
+
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_pending_owner_calldata(),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_data,
+                Ok(
+                    "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_zk_chain_data,
+                Ok(
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &validator_timelock_data,
+                Ok(
+                    "0x0000000000000000000000007777777777777777777777777777777777777777"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_admin_data,
+                Ok(
+                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &owner_data,
+                Ok(
+                    "0x0000000000000000000000004444444444444444444444444444444444444444"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &chain_324_protocol_data,
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000007"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &custom_signing_set_data,
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &signing_threshold_data,
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                        .to_string(),
+                ),
             )
             .with_response(
-                &chain_324_data,
-                Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                &validators_count_data,
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                        .to_string(),
+                ),
             )
             .with_response(
-                &chain_325_data,
-                Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+                &validator_0_data,
+                Ok(
+                    "0x0000000000000000000000001111111111111111111111111111111111111111"
+                        .to_string(),
+                ),
             )
             .with_response(
-                &protocol_version_data,
-                Ok("0x0000000000000000000000000000000000000000000000000000000000000007".to_string()),
-            );
+                &validator_1_data,
+                Ok(
+                    "0x0000000000000000000000002222222222222222222222222222222222222222"
+                        .to_string(),
+                ),
+            )
+            .with_empty_timelock_roles(chain_contract)
+            .with_eth_base_token(324)
+            .with_rollup_data_availability()
+            .with_l1_settlement(324)
+            .with_fee_configuration(address!("0x0000000000000000000000000000000000000000"));
 
-        let snapshot = scan_bridgehub_topology(&mock, "0x0000000000000000000000000000000000000001")
-            .expect("scan should succeed");
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions {
+                verbose: true,
+                ..InspectOptions::default()
+            },
+        )
+        .expect("inspect should succeed");
 
-        assert_eq!(snapshot.chain_ids, vec![324, 325]);
-        assert_eq!(snapshot.chain_ctms.len(), 2);
-        assert_eq!(snapshot.ctms.len(), 1);
-        assert!(snapshot.warnings.is_empty());
         assert_eq!(
-            snapshot.ctms[0].address,
-            "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+            inspection.chain.multisig_signing_set_mode.as_deref(),
+            Some("custom")
+        );
+        assert_eq!(inspection.chain.multisig_signing_threshold, Some(2));
+        assert_eq!(
+            inspection.chain.multisig_validators,
+            Some(vec![
+                address!("0x1111111111111111111111111111111111111111"),
+                address!("0x2222222222222222222222222222222222222222"),
+            ])
         );
-        assert_eq!(snapshot.ctms[0].protocol_version, Some("0.0.7".to_string()));
+        assert!(inspection.warnings.is_empty());
     }
 
     #[test]
-    fn inspect_chain_resolves_deep_details() {
-        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
-        let chain_324_zk_chain_data = bridgehub::encode_get_zk_chain_calldata(324);
-        let validator_timelock_data = bridgehub::encode_validator_timelock_post_v29_calldata();
-        let owner_data = bridgehub::encode_owner_calldata();
-        let chain_324_admin_data = bridgehub::encode_get_chain_admin_calldata(324);
-        let chain_324_protocol_data = bridgehub::encode_get_chain_protocol_version_calldata(324);
+    fn inspect_chain_groups_timelock_roles_per_operator() {
+        let chain_contract = address!("0xcccccccccccccccccccccccccccccccccccccccc");
+        let operator = address!("0x1111111111111111111111111111111111111111");
+        let executor = address!("0x2222222222222222222222222222222222222222");
 
         let mock = MockRpcClient::default()
             .with_response(
-                &chain_324_data,
+                &bridgehub::encode_chain_type_manager_calldata(324),
                 Ok(
                     "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
                         .to_string(),
                 ),
             )
             .with_response(
-                &chain_324_zk_chain_data,
+                &bridgehub::encode_get_zk_chain_calldata(324),
                 Ok(
                     "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
                         .to_string(),
                 ),
             )
             .with_response(
-                &validator_timelock_data,
+                &bridgehub::encode_validator_timelock_post_v29_calldata(),
                 Ok(
                     "0x0000000000000000000000007777777777777777777777777777777777777777"
                         .to_string(),
                 ),
             )
             .with_response(
-                &chain_324_admin_data,
+                &bridgehub::encode_execution_delay_calldata(),
                 Ok(
-                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                    "0x0000000000000000000000000000000000000000000000000000000000002a30"
                         .to_string(),
                 ),
             )
-            .with_response(
-                &owner_data,
-                Ok(
-                    "0x0000000000000000000000004444444444444444444444444444444444444444"
-                        .to_string(),
-                ),
+            .with_timelock_role_members(chain_contract, ValidatorTimelockRole::Precommitter, &[])
+            .with_timelock_role_members(
+                chain_contract,
+                ValidatorTimelockRole::Committer,
+                &[operator],
             )
-            .with_response(
-                &chain_324_protocol_data,
-                Ok(
-                    "0x0000000000000000000000000000000000000000000000000000000000000007"
-                        .to_string(),
-                ),
-            );
+            .with_timelock_role_members(chain_contract, ValidatorTimelockRole::Prover, &[operator])
+            .with_timelock_role_members(
+                chain_contract,
+                ValidatorTimelockRole::Executor,
+                &[operator, executor],
+            )
+            .with_timelock_role_members(chain_contract, ValidatorTimelockRole::Reverter, &[]);
 
         let inspection = inspect_bridgehub_chain(
             &mock,
-            "0x0000000000000000000000000000000000000001",
+            address!("0x0000000000000000000000000000000000000001"),
             324,
-            false,
+            &InspectOptions::default(),
         )
         .expect("inspect should succeed");
 
-        assert_eq!(inspection.chain.chain_id, 324);
-        assert_eq!(
-            inspection.chain.ctm.as_deref(),
-            Some("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
-        );
-        assert_eq!(
-            inspection.chain.chain_contract.as_deref(),
-            Some("0xcccccccccccccccccccccccccccccccccccccccc")
-        );
-        assert_eq!(
-            inspection.chain.validator_timelock.as_deref(),
-            Some("0x7777777777777777777777777777777777777777")
-        );
-        assert_eq!(
-            inspection.chain.validator_timelock_owner.as_deref(),
-            Some("0x4444444444444444444444444444444444444444")
-        );
         assert_eq!(
-            inspection.chain.admin.as_deref(),
-            Some("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee")
+            inspection.chain.validator_timelock_execution_delay,
+            Some(10800)
         );
         assert_eq!(
-            inspection.chain.admin_owner.as_deref(),
-            Some("0x4444444444444444444444444444444444444444")
+            inspection.chain.timelock_validators,
+            Some(vec![
+                TimelockValidator {
+                    address: operator,
+                    roles: vec![
+                        "committer".to_string(),
+                        "prover".to_string(),
+                        "executor".to_string(),
+                    ],
+                },
+                TimelockValidator {
+                    address: executor,
+                    roles: vec!["executor".to_string()],
+                },
+            ])
         );
-        assert_eq!(inspection.chain.protocol_version.as_deref(), Some("0.0.7"));
-        assert_eq!(inspection.chain.multisig_signing_set_mode, None);
-        assert_eq!(inspection.chain.multisig_signing_threshold, None);
-        assert_eq!(inspection.chain.multisig_validators, None);
-        assert!(inspection.warnings.is_empty());
     }
 
     #[test]
-    fn inspect_chain_verbose_resolves_multisig_commit_details() {
-        let chain_324_data = bridgehub::encode_chain_type_manager_calldata(324);
-        let chain_324_zk_chain_data = bridgehub::encode_get_zk_chain_calldata(324);
-        let validator_timelock_data = bridgehub::encode_validator_timelock_post_v29_calldata();
-        let owner_data = bridgehub::encode_owner_calldata();
-        let chain_324_admin_data = bridgehub::encode_get_chain_admin_calldata(324);
-        let chain_324_protocol_data = bridgehub::encode_get_chain_protocol_version_calldata(324);
-        let chain_contract = "0xcccccccccccccccccccccccccccccccccccccccc";
-        let custom_signing_set_data =
-            bridgehub::encode_is_custom_signing_set_active_calldata(chain_contract)
-                .expect("custom signing set calldata should encode");
-        let signing_threshold_data =
-            bridgehub::encode_get_signing_threshold_calldata(chain_contract)
-                .expect("signing threshold calldata should encode");
-        let validators_count_data = bridgehub::encode_get_validators_count_calldata(chain_contract)
-            .expect("validators count calldata should encode");
-        let validator_0_data = bridgehub::encode_get_validators_member_calldata(chain_contract, 0)
-            .expect("validator member calldata should encode");
-        let validator_1_data = bridgehub::encode_get_validators_member_calldata(chain_contract, 1)
-            .expect("validator member calldata should encode");
+    fn inspect_chain_probes_candidates_on_legacy_timelock() {
+        let operator = address!("0x1111111111111111111111111111111111111111");
+        let stranger = address!("0x2222222222222222222222222222222222222222");
 
         let mock = MockRpcClient::default()
             .with_response(
-                &chain_324_data,
+                &bridgehub::encode_chain_type_manager_calldata(324),
                 Ok(
                     "0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
                         .to_string(),
                 ),
             )
             .with_response(
-                &chain_324_zk_chain_data,
+                &bridgehub::encode_validator_timelock_post_v29_calldata(),
+                Err(RpcError::Transport("execution reverted".to_string())),
+            )
+            .with_response(
+                &bridgehub::encode_validator_timelock_calldata(),
                 Ok(
-                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
+                    "0x0000000000000000000000007777777777777777777777777777777777777777"
                         .to_string(),
                 ),
             )
             .with_response(
-                &validator_timelock_data,
+                &bridgehub::encode_legacy_validators_calldata(324, operator),
                 Ok(
-                    "0x0000000000000000000000007777777777777777777777777777777777777777"
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
                         .to_string(),
                 ),
             )
             .with_response(
-                &chain_324_admin_data,
+                &bridgehub::encode_legacy_validators_calldata(324, stranger),
                 Ok(
-                    "0x000000000000000000000000eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
                         .to_string(),
                 ),
+            );
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions {
+                validator_candidates: vec![operator, stranger],
+                ..InspectOptions::default()
+            },
+        )
+        .expect("inspect should succeed");
+
+        assert_eq!(
+            inspection.chain.timelock_validators,
+            Some(vec![TimelockValidator {
+                address: operator,
+                roles: vec!["validator".to_string()],
+            }])
+        );
+    }
+
+    #[test]
+    fn inspect_chain_resolves_custom_base_token_metadata() {
+        let asset_id = "0x1111111111111111111111111111111111111111111111111111111111111111";
+        let token = address!("0x9999999999999999999999999999999999999999");
+        let token_word = "0x0000000000000000000000009999999999999999999999999999999999999999";
+
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_base_token_asset_id_calldata(324),
+                Ok(asset_id.to_string()),
             )
             .with_response(
-                &owner_data,
+                &bridgehub::encode_base_token_calldata(324),
+                Ok(token_word.to_string()),
+            )
+            .with_response(
+                &bridgehub::encode_asset_router_calldata(),
                 Ok(
-                    "0x0000000000000000000000004444444444444444444444444444444444444444"
+                    "0x0000000000000000000000005555555555555555555555555555555555555555"
                         .to_string(),
                 ),
             )
             .with_response(
-                &chain_324_protocol_data,
+                &token::encode_native_token_vault_calldata(),
                 Ok(
-                    "0x0000000000000000000000000000000000000000000000000000000000000007"
+                    "0x0000000000000000000000006666666666666666666666666666666666666666"
                         .to_string(),
                 ),
             )
             .with_response(
-                &custom_signing_set_data,
+                &token::encode_token_address_calldata(asset_id)
+                    .expect("token address calldata should encode"),
+                Ok(token_word.to_string()),
+            )
+            .with_response(
+                &token::encode_origin_chain_id_calldata(asset_id)
+                    .expect("origin chain id calldata should encode"),
                 Ok(
                     "0x0000000000000000000000000000000000000000000000000000000000000001"
                         .to_string(),
                 ),
             )
             .with_response(
-                &signing_threshold_data,
+                &token::encode_symbol_calldata(),
+                Ok("0x\
+                    0000000000000000000000000000000000000000000000000000000000000020\
+                    0000000000000000000000000000000000000000000000000000000000000002\
+                    5a4b000000000000000000000000000000000000000000000000000000000000"
+                    .to_string()),
+            )
+            .with_response(
+                &token::encode_decimals_calldata(),
                 Ok(
-                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    "0x0000000000000000000000000000000000000000000000000000000000000012"
                         .to_string(),
                 ),
+            );
+
+        let inspection = inspect_bridgehub_chain(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            324,
+            &InspectOptions::default(),
+        )
+        .expect("inspect should succeed");
+
+        let base_token = &inspection.chain.base_token;
+        assert_eq!(base_token.asset_id.as_deref(), Some(asset_id));
+        assert_eq!(base_token.address, Some(token));
+        assert_eq!(base_token.origin_chain_id, Some(1));
+        assert_eq!(base_token.symbol.as_deref(), Some("ZK"));
+        assert_eq!(base_token.decimals, Some(18));
+        assert_eq!(base_token.name, None);
+        assert!(inspection.warnings.iter().any(|warning| {
+            warning.code == DiagnosticCode::ResolutionFailed
+                && warning.method.as_deref() == Some("name")
+                && warning
+                    .message
+                    .starts_with("failed to resolve name() for base token")
+        }));
+    }
+
+    #[test]
+    fn scan_breaks_down_data_availability_per_chain() {
+        let mock = MockRpcClient::default()
+            .with_response(
+                &bridgehub::encode_get_all_zk_chain_chain_ids_calldata(),
+                Ok("0x00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000014a".to_string()),
             )
             .with_response(
-                &validators_count_data,
+                &bridgehub::encode_chain_type_manager_calldata(330),
+                Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+            )
+            .with_response(
+                &bridgehub::encode_protocol_version_calldata(),
+                Ok("0x0000000000000000000000000000000000000000000000000000000000000007".to_string()),
+            )
+            .with_response(
+                &bridgehub::encode_get_zk_chain_calldata(330),
+                Ok("0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc".to_string()),
+            )
+            .with_response(
+                &diamond::encode_get_pubdata_pricing_mode_calldata(),
+                Ok("0x0000000000000000000000000000000000000000000000000000000000000001".to_string()),
+            )
+            .with_response(
+                &diamond::encode_get_da_validator_pair_calldata(),
+                Ok("0x\
+                    000000000000000000000000a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1\
+                    000000000000000000000000a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2"
+                    .to_string()),
+            );
+
+        let snapshot = scan_bridgehub_topology(
+            &mock,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions {
+                data_availability: true,
+                da_validators: vec![KnownDaValidator {
+                    address: address!("0xa1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1"),
+                    layer: data_availability::DaLayer::Avail,
+                }],
+                history: None,
+            },
+        )
+        .expect("scan should succeed");
+
+        assert!(snapshot.warnings.is_empty());
+        assert_eq!(snapshot.chain_data_availability.len(), 1);
+        let entry = &snapshot.chain_data_availability[0];
+        assert_eq!(entry.chain_id, 330);
+        assert_eq!(
+            entry.data_availability.pricing_mode.as_deref(),
+            Some("validium")
+        );
+        assert_eq!(
+            entry.data_availability.classification.as_deref(),
+            Some("validium (Avail)")
+        );
+    }
+
+    #[test]
+    fn inspect_follows_migrated_chain_to_gateway() {
+        let l1 = MockRpcClient::default()
+            .with_chain_id(1)
+            .with_response(
+                &bridgehub::encode_get_zk_chain_calldata(324),
                 Ok(
-                    "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    "0x000000000000000000000000cccccccccccccccccccccccccccccccccccccccc"
                         .to_string(),
                 ),
             )
             .with_response(
-                &validator_0_data,
+                &bridgehub::encode_settlement_layer_calldata(324),
                 Ok(
-                    "0x0000000000000000000000001111111111111111111111111111111111111111"
+                    "0x0000000000000000000000000000000000000000000000000000000000002373"
                         .to_string(),
                 ),
             )
             .with_response(
-                &validator_1_data,
+                &bridgehub::encode_whitelisted_settlement_layers_calldata(324),
                 Ok(
-                    "0x0000000000000000000000002222222222222222222222222222222222222222"
+                    "0x0000000000000000000000000000000000000000000000000000000000000000"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &bridgehub::encode_whitelisted_settlement_layers_calldata(9075),
+                Ok(
+                    "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        .to_string(),
+                ),
+            )
+            .with_response(
+                &diamond::encode_get_settlement_layer_calldata(),
+                Ok(
+                    "0x0000000000000000000000009999999999999999999999999999999999999999"
                         .to_string(),
                 ),
             );
 
-        let inspection = inspect_bridgehub_chain(
-            &mock,
-            "0x0000000000000000000000000000000000000001",
+        let mut inspection = inspect_bridgehub_chain(
+            &l1,
+            address!("0x0000000000000000000000000000000000000001"),
             324,
-            true,
+            &InspectOptions::default(),
         )
         .expect("inspect should succeed");
 
+        let settlement = &inspection.chain.settlement;
+        assert_eq!(settlement.settlement_chain_id, Some(9075));
+        assert_eq!(settlement.settlement_layer_whitelisted, Some(true));
+        assert_eq!(settlement.is_settlement_layer, Some(false));
         assert_eq!(
-            inspection.chain.multisig_signing_set_mode.as_deref(),
-            Some("custom")
+            settlement.diamond_settlement_layer,
+            Some(address!("0x9999999999999999999999999999999999999999"))
         );
-        assert_eq!(inspection.chain.multisig_signing_threshold, Some(2));
+        assert_eq!(settlement.migrated, Some(true));
+
+        let gateway = MockRpcClient::default().with_chain_id(9075).with_response(
+            &bridgehub::encode_chain_type_manager_calldata(324),
+            Ok("0x000000000000000000000000abababababababababababababababababababab".to_string()),
+        );
+        inspect_gateway_settlement(
+            &gateway,
+            bridgehub::L2_BRIDGEHUB_ADDRESS,
+            &mut inspection,
+            &InspectOptions::default(),
+        )
+        .expect("gateway inspection should succeed");
+
+        let followed = inspection
+            .gateway
+            .as_ref()
+            .expect("gateway should be followed");
+        assert_eq!(followed.bridgehub, bridgehub::L2_BRIDGEHUB_ADDRESS);
         assert_eq!(
-            inspection.chain.multisig_validators,
-            Some(vec![
-                "0x1111111111111111111111111111111111111111".to_string(),
-                "0x2222222222222222222222222222222222222222".to_string(),
-            ])
+            followed.chain.ctm,
+            Some(address!("0xabababababababababababababababababababab"))
+        );
+        assert!(followed.warnings.is_empty());
+        assert!(
+            inspection
+                .warnings
+                .iter()
+                .any(|warning| warning.message.starts_with("gateway: "))
+        );
+    }
+
+    #[test]
+    fn gateway_inspection_rejects_mismatched_gateway_rpc() {
+        let mut inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                settlement: SettlementLayer {
+                    settlement_chain_id: Some(9075),
+                    migrated: Some(true),
+                    ..SettlementLayer::default()
+                },
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+
+        inspect_gateway_settlement(
+            &MockRpcClient::default().with_chain_id(1),
+            bridgehub::L2_BRIDGEHUB_ADDRESS,
+            &mut inspection,
+            &InspectOptions::default(),
+        )
+        .expect("gateway inspection should not fail");
+
+        assert_eq!(inspection.gateway, None);
+        assert_eq!(
+            inspection.warnings,
+            vec![
+                Diagnostic::new(
+                    DiagnosticCode::SettlementLayerMismatch,
+                    "gateway rpc reports chain id 1, but chain 324 settles on chain 9075; skipping gateway inspection"
+                )
+                .chain(324)
+            ]
+        );
+    }
+
+    struct MockL2Node {
+        chain_id: u64,
+        responses: HashMap<&'static str, serde_json::Value>,
+        batch_timestamps: HashMap<u64, u64>,
+    }
+
+    impl RpcClient for MockL2Node {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            Ok(self.chain_id)
+        }
+
+        fn raw_request(
+            &self,
+            method: &'static str,
+            params: serde_json::Value,
+        ) -> Result<serde_json::Value, RpcError> {
+            if method == "zks_getL1BatchDetails" {
+                let batch = params[0].as_u64().unwrap_or_default();
+                return Ok(match self.batch_timestamps.get(&batch) {
+                    Some(timestamp) => {
+                        serde_json::json!({ "number": batch, "timestamp": timestamp })
+                    }
+                    None => serde_json::Value::Null,
+                });
+            }
+            self.responses
+                .get(method)
+                .cloned()
+                .ok_or(RpcError::Unsupported(method))
+        }
+    }
+
+    #[test]
+    fn l2_node_inspection_flags_disagreements_with_l1() {
+        let mut inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                chain_contract: Some(address!("0x00000000000000000000000000000000000000d1")),
+                settlement: SettlementLayer {
+                    l1_chain_id: Some(1),
+                    ..SettlementLayer::default()
+                },
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+        let node = MockL2Node {
+            chain_id: 325,
+            responses: HashMap::from([
+                ("zks_L1ChainId", serde_json::json!("0x1")),
+                (
+                    "zks_getBridgehubContract",
+                    serde_json::json!("0x0000000000000000000000000000000000000002"),
+                ),
+                (
+                    "zks_getMainContract",
+                    serde_json::json!("0x00000000000000000000000000000000000000d2"),
+                ),
+                ("zks_L1BatchNumber", serde_json::json!("0x10")),
+            ]),
+            batch_timestamps: HashMap::new(),
+        };
+
+        inspect_l2_node(&node, &mut inspection);
+
+        let l2 = inspection.l2.expect("l2 inspection should be recorded");
+        assert_eq!(l2.chain_id, Some(325));
+        assert_eq!(l2.l1_chain_id, Some(1));
+        assert_eq!(l2.latest_l1_batch, Some(16));
+        assert_eq!(l2.base_token_l1_address, None);
+        assert_eq!(
+            inspection
+                .warnings
+                .iter()
+                .filter(|warning| warning.code == DiagnosticCode::L2NodeMismatch)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "l2 node reports chain id 325, but L1 reports 324".to_string(),
+                "l2 node reports bridgehub 0x0000000000000000000000000000000000000002, but L1 reports 0x0000000000000000000000000000000000000001".to_string(),
+                "l2 node reports diamond proxy 0x00000000000000000000000000000000000000D2, but L1 reports 0x00000000000000000000000000000000000000D1".to_string(),
+            ]
+        );
+        assert_eq!(
+            inspection
+                .warnings
+                .iter()
+                .filter(|warning| warning.code == DiagnosticCode::ResolutionFailed)
+                .filter_map(|warning| warning.method.as_deref())
+                .collect::<Vec<_>>(),
+            vec!["zks_getBaseTokenL1Address", "zks_getBridgeContracts"]
+        );
+    }
+
+    #[test]
+    fn batch_progress_reports_lag_behind_latest_sealed_batch() {
+        let chain_contract = address!("0x00000000000000000000000000000000000000d1");
+        let mut inspection = ChainInspection {
+            bridgehub: address!("0x0000000000000000000000000000000000000001"),
+            chain: ChainSummary {
+                chain_id: 324,
+                chain_contract: Some(chain_contract),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: Some(L2Inspection {
+                latest_l1_batch: Some(100),
+                ..L2Inspection::default()
+            }),
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+        let settlement = MockRpcClient::default()
+            .with_response(
+                &diamond::encode_get_total_batches_committed_calldata(),
+                Ok(format!("0x{:064x}", 98)),
+            )
+            .with_response(
+                &diamond::encode_get_total_batches_verified_calldata(),
+                Ok(format!("0x{:064x}", 95)),
+            )
+            .with_response(
+                &diamond::encode_get_total_batches_executed_calldata(),
+                Ok(format!("0x{:064x}", 90)),
+            );
+        let node = MockL2Node {
+            chain_id: 324,
+            responses: HashMap::new(),
+            batch_timestamps: HashMap::from([(100, 10_000), (98, 9_880), (95, 9_700)]),
+        };
+
+        inspect_batch_progress(&settlement, &node, &mut inspection);
+
+        let progress = inspection
+            .l2
+            .and_then(|l2| l2.batch_progress)
+            .expect("batch progress should be recorded");
+        assert_eq!(
+            progress,
+            BatchProgress {
+                latest_sealed: 100,
+                committed: 98,
+                proven: 95,
+                executed: 90,
+                commit_lag: BatchLag {
+                    batches: 2,
+                    seconds: Some(120),
+                },
+                proof_lag: BatchLag {
+                    batches: 5,
+                    seconds: Some(300),
+                },
+                execution_lag: BatchLag {
+                    batches: 10,
+                    seconds: None,
+                },
+            }
+        );
+        assert_eq!(
+            inspection
+                .warnings
+                .iter()
+                .map(|warning| warning.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["failed to resolve l1 batch 90 from l2 rpc"]
         );
-        assert!(inspection.warnings.is_empty());
     }
 }
@@ -0,0 +1,386 @@
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{SolEvent, sol};
+
+use crate::model::TimelineEntry;
+use crate::rpc::{RpcClient, RpcLog};
+
+use super::bridgehub::{BridgehubError, ValidatorTimelockRole, decode_hex_data};
+use super::history::log_topics;
+use super::logs::fetch_logs;
+
+sol! {
+    event OwnershipTransferStarted(address indexed previousOwner, address indexed newOwner);
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+    event NewPendingAdmin(address indexed oldPendingAdmin, address indexed newPendingAdmin);
+    event NewAdmin(address indexed oldAdmin, address indexed newAdmin);
+    event AdminChanged(address previousAdmin, address newAdmin);
+    event Upgraded(address indexed implementation);
+    event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+    event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender);
+}
+
+sol! {
+    /// Role events of the v29+ validator timelock, scoped to a chain diamond.
+    interface PerChainAccessControl {
+        event RoleGranted(address indexed chainAddress, bytes32 indexed role, address indexed account);
+        event RoleRevoked(address indexed chainAddress, bytes32 indexed role, address indexed account);
+    }
+}
+
+/// Fetches control-change events emitted by `address` in
+/// `from_block..=to_block`. Per-chain role events are kept only when they
+/// target `chain_contract`.
+pub fn fetch_contract_timeline(
+    client: &dyn RpcClient,
+    contract: &str,
+    address: Address,
+    chain_contract: Option<Address>,
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+) -> Result<Vec<TimelineEntry>, BridgehubError> {
+    let logs = fetch_logs(
+        client,
+        address,
+        &timeline_event_signatures(),
+        from_block,
+        to_block,
+        max_block_range,
+    )?;
+
+    let mut entries = Vec::new();
+    for log in &logs {
+        if let Some((event, description)) = describe_log(log, chain_contract)? {
+            entries.push(TimelineEntry {
+                block_number: log.block_number,
+                log_index: log.log_index,
+                transaction_hash: log.transaction_hash.clone(),
+                contract: contract.to_string(),
+                address,
+                event: event_name(event).to_string(),
+                description,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Returns the block of the latest `OwnershipTransferStarted` on `address`
+/// that nominated `pending_owner`, i.e. when the pending transfer began.
+pub fn find_ownership_transfer_start(
+    client: &dyn RpcClient,
+    address: Address,
+    pending_owner: Address,
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+) -> Result<Option<u64>, BridgehubError> {
+    let logs = fetch_logs(
+        client,
+        address,
+        &[format!("{:#x}", OwnershipTransferStarted::SIGNATURE_HASH)],
+        from_block,
+        to_block,
+        max_block_range,
+    )?;
+
+    let mut started_at = None;
+    for log in &logs {
+        let topics = log_topics(log)?;
+        let data = decode_hex_data(&log.data)?;
+        let event = decode::<OwnershipTransferStarted>(topics.iter().copied(), &data)?;
+        if event.newOwner == pending_owner {
+            started_at = Some(log.block_number);
+        }
+    }
+    Ok(started_at)
+}
+
+pub fn timeline_event_signatures() -> Vec<String> {
+    [
+        OwnershipTransferStarted::SIGNATURE_HASH,
+        OwnershipTransferred::SIGNATURE_HASH,
+        NewPendingAdmin::SIGNATURE_HASH,
+        NewAdmin::SIGNATURE_HASH,
+        AdminChanged::SIGNATURE_HASH,
+        Upgraded::SIGNATURE_HASH,
+        RoleGranted::SIGNATURE_HASH,
+        RoleRevoked::SIGNATURE_HASH,
+        PerChainAccessControl::RoleGranted::SIGNATURE_HASH,
+        PerChainAccessControl::RoleRevoked::SIGNATURE_HASH,
+    ]
+    .iter()
+    .map(|hash| format!("{hash:#x}"))
+    .collect()
+}
+
+fn describe_log(
+    log: &RpcLog,
+    chain_contract: Option<Address>,
+) -> Result<Option<(&'static str, String)>, BridgehubError> {
+    let topics = log_topics(log)?;
+    let data = decode_hex_data(&log.data)?;
+    let Some(topic0) = topics.first().copied() else {
+        return Ok(None);
+    };
+    let topics = topics.iter().copied();
+
+    let described = match topic0 {
+        OwnershipTransferStarted::SIGNATURE_HASH => {
+            let event = decode::<OwnershipTransferStarted>(topics, &data)?;
+            (
+                OwnershipTransferStarted::SIGNATURE,
+                format!(
+                    "pending owner {} (current owner {})",
+                    event.newOwner, event.previousOwner
+                ),
+            )
+        }
+        OwnershipTransferred::SIGNATURE_HASH => {
+            let event = decode::<OwnershipTransferred>(topics, &data)?;
+            (
+                OwnershipTransferred::SIGNATURE,
+                format!("owner {} -> {}", event.previousOwner, event.newOwner),
+            )
+        }
+        NewPendingAdmin::SIGNATURE_HASH => {
+            let event = decode::<NewPendingAdmin>(topics, &data)?;
+            (
+                NewPendingAdmin::SIGNATURE,
+                format!(
+                    "pending admin {} -> {}",
+                    event.oldPendingAdmin, event.newPendingAdmin
+                ),
+            )
+        }
+        NewAdmin::SIGNATURE_HASH => {
+            let event = decode::<NewAdmin>(topics, &data)?;
+            (
+                NewAdmin::SIGNATURE,
+                format!("admin {} -> {}", event.oldAdmin, event.newAdmin),
+            )
+        }
+        AdminChanged::SIGNATURE_HASH => {
+            let event = decode::<AdminChanged>(topics, &data)?;
+            (
+                AdminChanged::SIGNATURE,
+                format!("proxy admin {} -> {}", event.previousAdmin, event.newAdmin),
+            )
+        }
+        Upgraded::SIGNATURE_HASH => {
+            let event = decode::<Upgraded>(topics, &data)?;
+            (
+                Upgraded::SIGNATURE,
+                format!("implementation -> {}", event.implementation),
+            )
+        }
+        RoleGranted::SIGNATURE_HASH => {
+            let event = decode::<RoleGranted>(topics, &data)?;
+            (
+                RoleGranted::SIGNATURE,
+                format!("{} granted to {}", role_name(event.role), event.account),
+            )
+        }
+        RoleRevoked::SIGNATURE_HASH => {
+            let event = decode::<RoleRevoked>(topics, &data)?;
+            (
+                RoleRevoked::SIGNATURE,
+                format!("{} revoked from {}", role_name(event.role), event.account),
+            )
+        }
+        PerChainAccessControl::RoleGranted::SIGNATURE_HASH => {
+            let event = decode::<PerChainAccessControl::RoleGranted>(topics, &data)?;
+            if !targets_chain(event.chainAddress, chain_contract) {
+                return Ok(None);
+            }
+            (
+                PerChainAccessControl::RoleGranted::SIGNATURE,
+                format!("{} granted to {}", role_name(event.role), event.account),
+            )
+        }
+        PerChainAccessControl::RoleRevoked::SIGNATURE_HASH => {
+            let event = decode::<PerChainAccessControl::RoleRevoked>(topics, &data)?;
+            if !targets_chain(event.chainAddress, chain_contract) {
+                return Ok(None);
+            }
+            (
+                PerChainAccessControl::RoleRevoked::SIGNATURE,
+                format!("{} revoked from {}", role_name(event.role), event.account),
+            )
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(described))
+}
+
+fn decode<E: SolEvent>(
+    topics: impl Iterator<Item = B256>,
+    data: &[u8],
+) -> Result<E, BridgehubError> {
+    E::decode_raw_log(topics, data).map_err(|err| BridgehubError::Decode(err.to_string()))
+}
+
+fn event_name(signature: &str) -> &str {
+    signature.split('(').next().unwrap_or(signature)
+}
+
+fn targets_chain(chain_address: Address, chain_contract: Option<Address>) -> bool {
+    chain_contract.is_none_or(|chain_contract| chain_address == chain_contract)
+}
+
+fn role_name(role: B256) -> String {
+    if role == B256::ZERO {
+        return "DEFAULT_ADMIN_ROLE".to_string();
+    }
+    ValidatorTimelockRole::ALL
+        .iter()
+        .find(|candidate| candidate.role_id() == role)
+        .map(|candidate| candidate.name().to_string())
+        .unwrap_or_else(|| format!("role {role:#x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{LogFilter, RpcError};
+    use alloy_primitives::address;
+
+    struct ScriptedLogs(Vec<RpcLog>);
+
+    impl RpcClient for ScriptedLogs {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_logs(&self, _filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn raw_log(block_number: u64, log: alloy_primitives::LogData) -> RpcLog {
+        RpcLog {
+            address: "0x0000000000000000000000000000000000000007".to_string(),
+            topics: log
+                .topics()
+                .iter()
+                .map(|topic| format!("{topic:#x}"))
+                .collect(),
+            data: format!("0x{}", hex::encode(&log.data)),
+            block_number,
+            transaction_hash: format!("0x{block_number:064x}"),
+            log_index: 0,
+        }
+    }
+
+    #[test]
+    fn describes_ownership_and_per_chain_role_events() {
+        let chain_contract = Address::repeat_byte(0xcc);
+        let rpc = ScriptedLogs(vec![
+            raw_log(
+                10,
+                OwnershipTransferred {
+                    previousOwner: Address::ZERO,
+                    newOwner: Address::repeat_byte(0x11),
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                20,
+                PerChainAccessControl::RoleGranted {
+                    chainAddress: chain_contract,
+                    role: ValidatorTimelockRole::Committer.role_id(),
+                    account: Address::repeat_byte(0x22),
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                30,
+                PerChainAccessControl::RoleGranted {
+                    chainAddress: Address::repeat_byte(0xdd),
+                    role: ValidatorTimelockRole::Committer.role_id(),
+                    account: Address::repeat_byte(0x33),
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                40,
+                AdminChanged {
+                    previousAdmin: Address::repeat_byte(0x44),
+                    newAdmin: Address::repeat_byte(0x55),
+                }
+                .encode_log_data(),
+            ),
+        ]);
+
+        let entries = fetch_contract_timeline(
+            &rpc,
+            "validator timelock",
+            address!("0x0000000000000000000000000000000000000007"),
+            Some(chain_contract),
+            0,
+            100,
+            1_000,
+        )
+        .expect("timeline should be fetched");
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.block_number, entry.description.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    10,
+                    "owner 0x0000000000000000000000000000000000000000 -> 0x1111111111111111111111111111111111111111"
+                ),
+                (
+                    20,
+                    "committer granted to 0x2222222222222222222222222222222222222222"
+                ),
+                (
+                    40,
+                    "proxy admin 0x4444444444444444444444444444444444444444 -> 0x5555555555555555555555555555555555555555"
+                ),
+            ]
+        );
+        assert_eq!(entries[0].event, "OwnershipTransferred");
+        assert_eq!(entries[1].contract, "validator timelock");
+    }
+
+    #[test]
+    fn finds_latest_transfer_start_for_pending_owner() {
+        let nominate = |block, owner: u8| {
+            raw_log(
+                block,
+                OwnershipTransferStarted {
+                    previousOwner: Address::repeat_byte(0x01),
+                    newOwner: Address::repeat_byte(owner),
+                }
+                .encode_log_data(),
+            )
+        };
+        let rpc = ScriptedLogs(vec![
+            nominate(10, 0x22),
+            nominate(20, 0x33),
+            nominate(30, 0x22),
+        ]);
+
+        let started_at = find_ownership_transfer_start(
+            &rpc,
+            address!("0x0000000000000000000000000000000000000007"),
+            address!("0x2222222222222222222222222222222222222222"),
+            0,
+            100,
+            1_000,
+        )
+        .expect("logs should be scanned");
+        assert_eq!(started_at, Some(30));
+    }
+
+    #[test]
+    fn names_default_admin_and_unknown_roles() {
+        assert_eq!(role_name(B256::ZERO), "DEFAULT_ADMIN_ROLE");
+        assert_eq!(role_name(ValidatorTimelockRole::Prover.role_id()), "prover");
+        assert!(role_name(B256::repeat_byte(0x01)).starts_with("role 0x0101"));
+    }
+}
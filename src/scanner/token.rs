@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use alloy_primitives::{Address, B256, address};
+use alloy_sol_types::{SolCall, sol};
+
+use crate::rpc::RpcClient;
+
+use super::bridgehub::{BridgehubError, call_contract, u256_to_u64};
+
+/// Sentinel address the asset router and native token vault use for ETH.
+pub const ETH_TOKEN_ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+
+sol! {
+    function nativeTokenVault() external view returns (address vault);
+    function tokenAddress(bytes32 assetId) external view returns (address token);
+    function originChainId(bytes32 assetId) external view returns (uint256 chainId);
+    function name() external view returns (string);
+    function symbol() external view returns (string);
+    function decimals() external view returns (uint8);
+}
+
+pub fn get_native_token_vault(
+    client: &dyn RpcClient,
+    asset_router: Address,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_native_token_vault_calldata();
+    let bytes = call_contract(client, asset_router, &calldata)?;
+    let decoded = nativeTokenVaultCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_vault_token_address(
+    client: &dyn RpcClient,
+    native_token_vault: Address,
+    asset_id: &str,
+) -> Result<Address, BridgehubError> {
+    let calldata = encode_token_address_calldata(asset_id)?;
+    let bytes = call_contract(client, native_token_vault, &calldata)?;
+    let decoded = tokenAddressCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    Ok(decoded)
+}
+
+pub fn get_vault_origin_chain_id(
+    client: &dyn RpcClient,
+    native_token_vault: Address,
+    asset_id: &str,
+) -> Result<u64, BridgehubError> {
+    let calldata = encode_origin_chain_id_calldata(asset_id)?;
+    let bytes = call_contract(client, native_token_vault, &calldata)?;
+    let decoded = originChainIdCall::abi_decode_returns(&bytes)
+        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+    u256_to_u64(decoded)
+}
+
+pub fn get_erc20_name(client: &dyn RpcClient, token: Address) -> Result<String, BridgehubError> {
+    let calldata = encode_name_calldata();
+    let bytes = call_contract(client, token, &calldata)?;
+    nameCall::abi_decode_returns(&bytes).map_err(|err| BridgehubError::Decode(err.to_string()))
+}
+
+pub fn get_erc20_symbol(client: &dyn RpcClient, token: Address) -> Result<String, BridgehubError> {
+    let calldata = encode_symbol_calldata();
+    let bytes = call_contract(client, token, &calldata)?;
+    symbolCall::abi_decode_returns(&bytes).map_err(|err| BridgehubError::Decode(err.to_string()))
+}
+
+pub fn get_erc20_decimals(client: &dyn RpcClient, token: Address) -> Result<u8, BridgehubError> {
+    let calldata = encode_decimals_calldata();
+    let bytes = call_contract(client, token, &calldata)?;
+    decimalsCall::abi_decode_returns(&bytes).map_err(|err| BridgehubError::Decode(err.to_string()))
+}
+
+pub fn is_eth_token(address: Address) -> bool {
+    address == ETH_TOKEN_ADDRESS
+}
+
+pub fn encode_native_token_vault_calldata() -> String {
+    format!("0x{}", hex::encode(nativeTokenVaultCall {}.abi_encode()))
+}
+
+pub fn encode_token_address_calldata(asset_id: &str) -> Result<String, BridgehubError> {
+    let calldata = tokenAddressCall {
+        assetId: parse_asset_id(asset_id)?,
+    }
+    .abi_encode();
+    Ok(format!("0x{}", hex::encode(calldata)))
+}
+
+pub fn encode_origin_chain_id_calldata(asset_id: &str) -> Result<String, BridgehubError> {
+    let calldata = originChainIdCall {
+        assetId: parse_asset_id(asset_id)?,
+    }
+    .abi_encode();
+    Ok(format!("0x{}", hex::encode(calldata)))
+}
+
+pub fn encode_name_calldata() -> String {
+    format!("0x{}", hex::encode(nameCall {}.abi_encode()))
+}
+
+pub fn encode_symbol_calldata() -> String {
+    format!("0x{}", hex::encode(symbolCall {}.abi_encode()))
+}
+
+pub fn encode_decimals_calldata() -> String {
+    format!("0x{}", hex::encode(decimalsCall {}.abi_encode()))
+}
+
+fn parse_asset_id(value: &str) -> Result<B256, BridgehubError> {
+    B256::from_str(value).map_err(|err| BridgehubError::Decode(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::bridgehub::decode_hex_data;
+
+    #[test]
+    fn encodes_erc20_metadata_calldata() {
+        assert_eq!(encode_name_calldata(), "0x06fdde03");
+        assert_eq!(encode_symbol_calldata(), "0x95d89b41");
+        assert_eq!(encode_decimals_calldata(), "0x313ce567");
+    }
+
+    #[test]
+    fn encodes_vault_asset_lookup_calldata() {
+        let asset_id = "0x05e1c3ae2ad34c6c8ecb6c6e2b1fea6c7c4e1e77bd1e7b9d3f5bbcf1ad2a0f2b";
+        let data =
+            encode_token_address_calldata(asset_id).expect("token address calldata should encode");
+        assert!(data.ends_with(asset_id.trim_start_matches("0x")));
+        assert_eq!(data.len(), 2 + 8 + 64);
+    }
+
+    #[test]
+    fn decodes_erc20_string_return() {
+        let data = "0x\
+                    0000000000000000000000000000000000000000000000000000000000000020\
+                    0000000000000000000000000000000000000000000000000000000000000002\
+                    5a4b000000000000000000000000000000000000000000000000000000000000";
+        let bytes = decode_hex_data(data).expect("hex decode should succeed");
+        let decoded = symbolCall::abi_decode_returns(&bytes).expect("abi decode should work");
+        assert_eq!(decoded, "ZK");
+    }
+
+    #[test]
+    fn recognizes_eth_sentinel_address() {
+        assert!(is_eth_token(address!(
+            "0x0000000000000000000000000000000000000001"
+        )));
+        assert!(!is_eth_token(address!(
+            "0x0000000000000000000000000000000000000002"
+        )));
+    }
+}
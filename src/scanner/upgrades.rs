@@ -0,0 +1,394 @@
+use alloy_primitives::{Address, keccak256};
+use alloy_sol_types::{SolEvent, sol};
+
+use crate::model::{FacetChange, UpgradeEvent};
+use crate::rpc::{RpcClient, RpcLog};
+
+use super::bridgehub::{BridgehubError, decode_hex_data, decode_packed_semver};
+use super::history::log_topics;
+use super::logs::fetch_logs;
+
+sol! {
+    struct FacetCut {
+        address facet;
+        uint8 action;
+        bool isFreezable;
+        bytes4[] selectors;
+    }
+
+    struct DiamondCutData {
+        FacetCut[] facetCuts;
+        address initAddress;
+        bytes initCalldata;
+    }
+
+    event ExecuteUpgrade(DiamondCutData diamondCut);
+    event DiamondCut(FacetCut[] facetCuts, address initAddress, bytes initCalldata);
+    event NewUpgradeCutData(uint256 indexed protocolVersion, DiamondCutData diamondCutData);
+}
+
+sol! {
+    /// Emitted by the chain diamond when an upgrade bumps its version.
+    interface DiamondVersionEvents {
+        event NewProtocolVersion(uint256 previousProtocolVersion, uint256 newProtocolVersion);
+    }
+
+    /// Emitted by the CTM when a new protocol version is published.
+    interface CtmVersionEvents {
+        event NewProtocolVersion(uint256 indexed oldProtocolVersion, uint256 indexed newProtocolVersion);
+    }
+}
+
+/// Fetches upgrade events emitted by `address` and folds the events of a
+/// single transaction (e.g. `ExecuteUpgrade` + `DiamondCut` +
+/// `NewProtocolVersion`) into one entry.
+pub fn fetch_upgrade_events(
+    client: &dyn RpcClient,
+    source: &str,
+    address: Address,
+    from_block: u64,
+    to_block: u64,
+    max_block_range: u64,
+) -> Result<Vec<UpgradeEvent>, BridgehubError> {
+    let logs = fetch_logs(
+        client,
+        address,
+        &upgrade_event_signatures(),
+        from_block,
+        to_block,
+        max_block_range,
+    )?;
+
+    let mut events: Vec<UpgradeEvent> = Vec::new();
+    for log in &logs {
+        let Some(event) = decode_upgrade_log(log, source)? else {
+            continue;
+        };
+        match events.last_mut() {
+            Some(previous) if previous.transaction_hash == event.transaction_hash => {
+                absorb(previous, event)
+            }
+            _ => events.push(event),
+        }
+    }
+    Ok(events)
+}
+
+pub fn upgrade_event_signatures() -> Vec<String> {
+    [
+        ExecuteUpgrade::SIGNATURE_HASH,
+        DiamondCut::SIGNATURE_HASH,
+        NewUpgradeCutData::SIGNATURE_HASH,
+        DiamondVersionEvents::NewProtocolVersion::SIGNATURE_HASH,
+    ]
+    .iter()
+    .map(|hash| format!("{hash:#x}"))
+    .collect()
+}
+
+fn decode_upgrade_log(log: &RpcLog, source: &str) -> Result<Option<UpgradeEvent>, BridgehubError> {
+    let topics = log_topics(log)?;
+    let data = decode_hex_data(&log.data)?;
+    let Some(topic0) = topics.first().copied() else {
+        return Ok(None);
+    };
+
+    let mut event = UpgradeEvent {
+        block_number: log.block_number,
+        log_index: log.log_index,
+        transaction_hash: log.transaction_hash.clone(),
+        source: source.to_string(),
+        event: String::new(),
+        previous_protocol_version: None,
+        protocol_version: None,
+        facet_changes: vec![],
+        init_address: None,
+        init_calldata_hash: None,
+    };
+
+    match topic0 {
+        ExecuteUpgrade::SIGNATURE_HASH => {
+            let decoded = ExecuteUpgrade::decode_raw_log(topics, &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            event.event = "ExecuteUpgrade".to_string();
+            apply_cut(
+                &mut event,
+                &decoded.diamondCut.facetCuts,
+                decoded.diamondCut.initAddress,
+                &decoded.diamondCut.initCalldata,
+            );
+        }
+        DiamondCut::SIGNATURE_HASH => {
+            let decoded = DiamondCut::decode_raw_log(topics, &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            event.event = "DiamondCut".to_string();
+            apply_cut(
+                &mut event,
+                &decoded.facetCuts,
+                decoded.initAddress,
+                &decoded.initCalldata,
+            );
+        }
+        NewUpgradeCutData::SIGNATURE_HASH => {
+            let decoded = NewUpgradeCutData::decode_raw_log(topics, &data)
+                .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+            event.event = "NewUpgradeCutData".to_string();
+            event.protocol_version = Some(decode_packed_semver(decoded.protocolVersion)?);
+            apply_cut(
+                &mut event,
+                &decoded.diamondCutData.facetCuts,
+                decoded.diamondCutData.initAddress,
+                &decoded.diamondCutData.initCalldata,
+            );
+        }
+        // Same signature on both contracts; only the CTM indexes the versions.
+        DiamondVersionEvents::NewProtocolVersion::SIGNATURE_HASH => {
+            let (previous, new) = if topics.len() == 3 {
+                let decoded = CtmVersionEvents::NewProtocolVersion::decode_raw_log(topics, &data)
+                    .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+                (decoded.oldProtocolVersion, decoded.newProtocolVersion)
+            } else {
+                let decoded =
+                    DiamondVersionEvents::NewProtocolVersion::decode_raw_log(topics, &data)
+                        .map_err(|err| BridgehubError::Decode(err.to_string()))?;
+                (decoded.previousProtocolVersion, decoded.newProtocolVersion)
+            };
+            event.event = "NewProtocolVersion".to_string();
+            event.previous_protocol_version = Some(decode_packed_semver(previous)?);
+            event.protocol_version = Some(decode_packed_semver(new)?);
+        }
+        _ => return Ok(None),
+    }
+
+    Ok(Some(event))
+}
+
+fn apply_cut(
+    event: &mut UpgradeEvent,
+    facet_cuts: &[FacetCut],
+    init_address: Address,
+    init_calldata: &[u8],
+) {
+    event.facet_changes = facet_cuts
+        .iter()
+        .map(|cut| FacetChange {
+            facet: cut.facet,
+            action: facet_action_name(cut.action),
+            selectors: cut
+                .selectors
+                .iter()
+                .map(|selector| format!("{selector:#x}"))
+                .collect(),
+        })
+        .collect();
+    event.init_address = (!init_address.is_zero()).then_some(init_address);
+    event.init_calldata_hash =
+        (!init_calldata.is_empty()).then(|| format!("{:#x}", keccak256(init_calldata)));
+}
+
+/// Merges `other` into `into`, preferring the cut-carrying event's name and
+/// filling in whatever fields `into` is still missing.
+fn absorb(into: &mut UpgradeEvent, other: UpgradeEvent) {
+    if into.event == "NewProtocolVersion" || other.event == "ExecuteUpgrade" {
+        into.event = other.event;
+    }
+    if into.facet_changes.is_empty() {
+        into.facet_changes = other.facet_changes;
+    }
+    into.previous_protocol_version = into
+        .previous_protocol_version
+        .take()
+        .or(other.previous_protocol_version);
+    into.protocol_version = into.protocol_version.take().or(other.protocol_version);
+    into.init_address = into.init_address.take().or(other.init_address);
+    into.init_calldata_hash = into.init_calldata_hash.take().or(other.init_calldata_hash);
+}
+
+fn facet_action_name(action: u8) -> String {
+    match action {
+        0 => "add".to_string(),
+        1 => "replace".to_string(),
+        2 => "remove".to_string(),
+        other => format!("unknown action {other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Bytes, FixedBytes, LogData, U256, address};
+
+    use super::*;
+    use crate::model::ProtocolSemver;
+    use crate::rpc::{LogFilter, RpcError};
+
+    struct ScriptedLogs(Vec<RpcLog>);
+
+    impl RpcClient for ScriptedLogs {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Unsupported("eth_call"))
+        }
+
+        fn eth_get_logs(&self, _filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn raw_log(block_number: u64, log_index: u64, log: LogData) -> RpcLog {
+        RpcLog {
+            address: "0x0000000000000000000000000000000000000007".to_string(),
+            topics: log
+                .topics()
+                .iter()
+                .map(|topic| format!("{topic:#x}"))
+                .collect(),
+            data: format!("0x{}", hex::encode(&log.data)),
+            block_number,
+            transaction_hash: format!("0x{block_number:064x}"),
+            log_index,
+        }
+    }
+
+    fn packed_semver(minor: u64) -> U256 {
+        U256::from(minor) << 32usize
+    }
+
+    fn cut_data() -> DiamondCutData {
+        DiamondCutData {
+            facetCuts: vec![
+                FacetCut {
+                    facet: Address::repeat_byte(0xf1),
+                    action: 1,
+                    isFreezable: false,
+                    selectors: vec![FixedBytes([0x12, 0x34, 0x56, 0x78])],
+                },
+                FacetCut {
+                    facet: Address::ZERO,
+                    action: 2,
+                    isFreezable: false,
+                    selectors: vec![FixedBytes([0xde, 0xad, 0xbe, 0xef])],
+                },
+            ],
+            initAddress: Address::repeat_byte(0x1a),
+            initCalldata: Bytes::from_static(&[0x01, 0x02]),
+        }
+    }
+
+    #[test]
+    fn folds_diamond_upgrade_transaction_into_one_event() {
+        let rpc = ScriptedLogs(vec![
+            raw_log(
+                100,
+                0,
+                ExecuteUpgrade {
+                    diamondCut: cut_data(),
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                100,
+                1,
+                DiamondCut {
+                    facetCuts: cut_data().facetCuts,
+                    initAddress: cut_data().initAddress,
+                    initCalldata: cut_data().initCalldata,
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                100,
+                2,
+                DiamondVersionEvents::NewProtocolVersion {
+                    previousProtocolVersion: packed_semver(28),
+                    newProtocolVersion: packed_semver(29),
+                }
+                .encode_log_data(),
+            ),
+        ]);
+
+        let events = fetch_upgrade_events(
+            &rpc,
+            "chain diamond",
+            address!("0x0000000000000000000000000000000000000007"),
+            0,
+            200,
+            1_000,
+        )
+        .expect("upgrade events should decode");
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.event, "ExecuteUpgrade");
+        assert_eq!(
+            event.previous_protocol_version,
+            Some(ProtocolSemver::new(0, 28, 0))
+        );
+        assert_eq!(event.protocol_version, Some(ProtocolSemver::new(0, 29, 0)));
+        assert_eq!(
+            event.facet_changes,
+            vec![
+                FacetChange {
+                    facet: address!("0xf1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1f1"),
+                    action: "replace".to_string(),
+                    selectors: vec!["0x12345678".to_string()],
+                },
+                FacetChange {
+                    facet: address!("0x0000000000000000000000000000000000000000"),
+                    action: "remove".to_string(),
+                    selectors: vec!["0xdeadbeef".to_string()],
+                },
+            ]
+        );
+        assert_eq!(event.init_address, Some(Address::repeat_byte(0x1a)));
+        assert_eq!(
+            event.init_calldata_hash,
+            Some(format!("{:#x}", keccak256([0x01u8, 0x02])))
+        );
+    }
+
+    #[test]
+    fn decodes_ctm_published_upgrade() {
+        let rpc = ScriptedLogs(vec![
+            raw_log(
+                50,
+                0,
+                NewUpgradeCutData {
+                    protocolVersion: packed_semver(29),
+                    diamondCutData: cut_data(),
+                }
+                .encode_log_data(),
+            ),
+            raw_log(
+                50,
+                1,
+                CtmVersionEvents::NewProtocolVersion {
+                    oldProtocolVersion: packed_semver(28),
+                    newProtocolVersion: packed_semver(29),
+                }
+                .encode_log_data(),
+            ),
+        ]);
+
+        let events = fetch_upgrade_events(
+            &rpc,
+            "ctm",
+            address!("0x0000000000000000000000000000000000000007"),
+            0,
+            200,
+            1_000,
+        )
+        .expect("upgrade events should decode");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "NewUpgradeCutData");
+        assert_eq!(events[0].source, "ctm");
+        assert_eq!(
+            events[0].previous_protocol_version,
+            Some(ProtocolSemver::new(0, 28, 0))
+        );
+        assert_eq!(
+            events[0].protocol_version,
+            Some(ProtocolSemver::new(0, 29, 0))
+        );
+        assert_eq!(events[0].facet_changes.len(), 2);
+    }
+}
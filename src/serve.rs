@@ -0,0 +1,692 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use alloy_primitives::Address;
+use serde_json::Value;
+
+use crate::api;
+use crate::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::model::SavedSnapshot;
+use crate::rpc::{LogFilter, RpcClient, RpcError, RpcLog};
+use crate::scanner::{InspectOptions, ScanOptions, capture_snapshot, diamond};
+
+/// Upper bounds, in seconds, of the RPC latency histogram buckets.
+pub const LATENCY_BUCKETS: [f64; 10] = [0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServeOptions {
+    /// Delay between scan rounds.
+    pub interval: Duration,
+    /// Stop after this many scan rounds; runs until interrupted by default.
+    pub max_rounds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Histogram {
+    /// Cumulative counts per `LATENCY_BUCKETS` bound.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+/// Per-method RPC latency, shared between the timed client and the server.
+#[derive(Debug, Clone, Default)]
+pub struct RpcLatency {
+    histograms: Arc<Mutex<BTreeMap<&'static str, Histogram>>>,
+}
+
+impl RpcLatency {
+    pub fn observe(&self, method: &'static str, elapsed: Duration) {
+        self.histograms
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(method)
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    fn snapshot(&self) -> BTreeMap<&'static str, Histogram> {
+        self.histograms
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Records the latency of every call of the wrapped client.
+pub struct TimedRpcClient<C> {
+    client: C,
+    latency: RpcLatency,
+}
+
+impl<C: RpcClient> TimedRpcClient<C> {
+    pub fn new(client: C, latency: RpcLatency) -> Self {
+        Self { client, latency }
+    }
+
+    fn timed<T>(&self, method: &'static str, call: impl FnOnce(&C) -> T) -> T {
+        let started = Instant::now();
+        let result = call(&self.client);
+        self.latency.observe(method, started.elapsed());
+        result
+    }
+}
+
+impl<C: RpcClient> RpcClient for TimedRpcClient<C> {
+    fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+        self.timed("eth_call", |client| client.eth_call(to, data))
+    }
+
+    fn eth_call_at_block(&self, to: &str, data: &str, block: u64) -> Result<String, RpcError> {
+        self.timed("eth_call", |client| {
+            client.eth_call_at_block(to, data, block)
+        })
+    }
+
+    fn eth_chain_id(&self) -> Result<u64, RpcError> {
+        self.timed("eth_chainId", |client| client.eth_chain_id())
+    }
+
+    fn eth_get_storage_at(&self, address: &str, slot: &str) -> Result<String, RpcError> {
+        self.timed("eth_getStorageAt", |client| {
+            client.eth_get_storage_at(address, slot)
+        })
+    }
+
+    fn eth_get_storage_at_block(
+        &self,
+        address: &str,
+        slot: &str,
+        block: u64,
+    ) -> Result<String, RpcError> {
+        self.timed("eth_getStorageAt", |client| {
+            client.eth_get_storage_at_block(address, slot, block)
+        })
+    }
+
+    fn eth_block_number(&self) -> Result<u64, RpcError> {
+        self.timed("eth_blockNumber", |client| client.eth_block_number())
+    }
+
+    fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+        self.timed("eth_getLogs", |client| client.eth_get_logs(filter))
+    }
+
+    fn eth_get_code(&self, address: &str) -> Result<String, RpcError> {
+        self.timed("eth_getCode", |client| client.eth_get_code(address))
+    }
+
+    fn eth_get_code_at_block(&self, address: &str, block: u64) -> Result<String, RpcError> {
+        self.timed("eth_getCode", |client| {
+            client.eth_get_code_at_block(address, block)
+        })
+    }
+
+    fn eth_get_block_timestamp(&self, block: u64) -> Result<u64, RpcError> {
+        self.timed("eth_getBlockByNumber", |client| {
+            client.eth_get_block_timestamp(block)
+        })
+    }
+
+    fn eth_get_block_hash(&self, block: u64) -> Result<String, RpcError> {
+        self.timed("eth_getBlockByNumber", |client| {
+            client.eth_get_block_hash(block)
+        })
+    }
+
+    fn raw_request(&self, method: &'static str, params: Value) -> Result<Value, RpcError> {
+        self.timed(method, |client| client.raw_request(method, params))
+    }
+}
+
+/// `(committed, proven, executed)` batch counters of a chain.
+pub type BatchCounters = (u64, u64, u64);
+
+/// Result of the latest successful round plus counters across all rounds.
+#[derive(Debug, Clone, Default)]
+pub struct ExporterState {
+    pub snapshot: Option<SavedSnapshot>,
+    /// Batch counters keyed by chain ID.
+    pub batches: BTreeMap<u64, BatchCounters>,
+    pub rounds_total: u64,
+    pub round_failures_total: u64,
+    pub warnings_total: BTreeMap<DiagnosticCode, u64>,
+    pub last_success_unix: Option<u64>,
+}
+
+/// Runs a scan round every `options.interval`, publishing each result to
+/// `state`. Round failures are counted, reported through `on_warning` and
+/// retried on the next interval.
+pub fn run_exporter(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    scan_options: &ScanOptions,
+    inspect_options: &InspectOptions,
+    options: &ServeOptions,
+    state: &Mutex<ExporterState>,
+    on_warning: &mut dyn FnMut(&Diagnostic),
+) {
+    let mut rounds = 0;
+    let mut previous_warnings = Vec::new();
+    loop {
+        rounds += 1;
+        let (snapshot, batches, warnings) =
+            match capture_snapshot(client, bridgehub, scan_options, inspect_options) {
+                Ok(snapshot) => {
+                    let (batches, warnings) = collect_round(client, &snapshot);
+                    (Some(snapshot), batches, warnings)
+                }
+                Err(err) => {
+                    let warning =
+                        Diagnostic::new(DiagnosticCode::ScanFailed, "exporter round failed")
+                            .error(err);
+                    (None, BTreeMap::new(), vec![warning])
+                }
+            };
+        for warning in &warnings {
+            if !previous_warnings.contains(warning) {
+                on_warning(warning);
+            }
+        }
+
+        {
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.rounds_total += 1;
+            for warning in &warnings {
+                *state.warnings_total.entry(warning.code).or_default() += 1;
+            }
+            match snapshot {
+                Some(snapshot) => {
+                    state.last_success_unix = Some(snapshot.saved_at_unix);
+                    state.batches = batches;
+                    state.snapshot = Some(snapshot);
+                }
+                None => state.round_failures_total += 1,
+            }
+        }
+        previous_warnings = warnings;
+
+        if options
+            .max_rounds
+            .is_some_and(|max_rounds| rounds >= max_rounds)
+        {
+            return;
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+/// Reads the batch counters of every inspected chain and gathers the round's
+/// warnings.
+fn collect_round(
+    client: &dyn RpcClient,
+    snapshot: &SavedSnapshot,
+) -> (BTreeMap<u64, BatchCounters>, Vec<Diagnostic>) {
+    let mut warnings = snapshot.topology.warnings.clone();
+    warnings.extend(
+        snapshot
+            .inspections
+            .iter()
+            .flat_map(|inspection| inspection.warnings.iter().cloned()),
+    );
+    let mut batches = BTreeMap::new();
+    for inspection in &snapshot.inspections {
+        let chain_id = inspection.chain.chain_id;
+        let Some(chain_contract) = inspection.chain.chain_contract else {
+            continue;
+        };
+        match diamond::get_total_batches(client, chain_contract) {
+            Ok(counters) => {
+                batches.insert(chain_id, counters);
+            }
+            Err(err) => warnings.push(
+                Diagnostic::resolution_failed(
+                    "getTotalBatchesCommitted",
+                    format!("failed to resolve batch counters for chain {chain_id}"),
+                    err,
+                )
+                .chain(chain_id)
+                .contract(chain_contract),
+            ),
+        }
+    }
+    (batches, warnings)
+}
+
+/// Renders the exporter state in the Prometheus text exposition format.
+pub fn render_metrics(state: &ExporterState, latency: &RpcLatency) -> String {
+    let mut out = String::new();
+
+    let name = "mercator_scan_rounds_total";
+    header(&mut out, name, "counter", "Scan rounds started.");
+    let _ = writeln!(out, "{name} {}", state.rounds_total);
+
+    let name = "mercator_scan_round_failures_total";
+    header(
+        &mut out,
+        name,
+        "counter",
+        "Scan rounds that failed outright.",
+    );
+    let _ = writeln!(out, "{name} {}", state.round_failures_total);
+
+    let name = "mercator_scan_warnings_total";
+    header(
+        &mut out,
+        name,
+        "counter",
+        "Warnings raised across all rounds, by code.",
+    );
+    for (code, count) in &state.warnings_total {
+        let _ = writeln!(out, "{name}{{code=\"{code}\"}} {count}");
+    }
+
+    if let Some(timestamp) = state.last_success_unix {
+        let name = "mercator_scan_last_success_timestamp_seconds";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Unix time of the latest successful round.",
+        );
+        let _ = writeln!(out, "{name} {timestamp}");
+    }
+
+    if let Some(snapshot) = state.snapshot.as_ref() {
+        let name = "mercator_chains";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Chains registered on the Bridgehub.",
+        );
+        let _ = writeln!(out, "{name} {}", snapshot.topology.chain_ids.len());
+
+        let name = "mercator_ctm_protocol_version";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Minor protocol version of each CTM; `version` holds the full semver.",
+        );
+        for ctm in &snapshot.topology.ctms {
+            if let Some(version) = ctm.protocol_version {
+                let _ = writeln!(
+                    out,
+                    "{name}{{ctm=\"{}\",version=\"{version}\"}} {}",
+                    ctm.address, version.minor
+                );
+            }
+        }
+
+        let name = "mercator_chain_protocol_version";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Minor protocol version of each chain; `version` holds the full semver.",
+        );
+        for inspection in &snapshot.inspections {
+            if let Some(version) = inspection.chain.protocol_version {
+                let _ = writeln!(
+                    out,
+                    "{name}{{chain_id=\"{}\",version=\"{version}\"}} {}",
+                    inspection.chain.chain_id, version.minor
+                );
+            }
+        }
+
+        for (stage, index) in [("committed", 0), ("proven", 1), ("executed", 2)] {
+            let name = format!("mercator_chain_batches_{stage}");
+            header(
+                &mut out,
+                &name,
+                "gauge",
+                &format!("Batches {stage} on the chain diamond."),
+            );
+            for (chain_id, (committed, proven, executed)) in &state.batches {
+                let value = [committed, proven, executed][index];
+                let _ = writeln!(out, "{name}{{chain_id=\"{chain_id}\"}} {value}");
+            }
+        }
+
+        let name = "mercator_multisig_threshold";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "Signing threshold of the chain's multisig committer.",
+        );
+        for inspection in &snapshot.inspections {
+            if let Some(threshold) = inspection.chain.multisig_signing_threshold {
+                let _ = writeln!(
+                    out,
+                    "{name}{{chain_id=\"{}\"}} {threshold}",
+                    inspection.chain.chain_id
+                );
+            }
+        }
+
+        let name = "mercator_ownership_pending";
+        header(
+            &mut out,
+            name,
+            "gauge",
+            "1 while an Ownable2Step transfer awaits acceptance.",
+        );
+        for inspection in &snapshot.inspections {
+            let chain = &inspection.chain;
+            for (role, contract, pending_owner) in [
+                ("admin", chain.admin, chain.admin_pending_owner),
+                (
+                    "validator_timelock",
+                    chain.validator_timelock,
+                    chain.validator_timelock_pending_owner,
+                ),
+            ] {
+                if let Some(contract) = contract {
+                    let _ = writeln!(
+                        out,
+                        "{name}{{chain_id=\"{}\",role=\"{role}\",contract=\"{contract}\"}} {}",
+                        chain.chain_id,
+                        u8::from(pending_owner.is_some())
+                    );
+                }
+            }
+        }
+    }
+
+    let name = "mercator_rpc_request_duration_seconds";
+    header(
+        &mut out,
+        name,
+        "histogram",
+        "Latency of RPC requests, by method.",
+    );
+    for (method, histogram) in latency.snapshot() {
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{method=\"{method}\",le=\"{bound}\"}} {count}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{method=\"{method}\",le=\"+Inf\"}} {}",
+            histogram.count
+        );
+        let _ = writeln!(out, "{name}_sum{{method=\"{method}\"}} {}", histogram.sum);
+        let _ = writeln!(
+            out,
+            "{name}_count{{method=\"{method}\"}} {}",
+            histogram.count
+        );
+    }
+    out
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Serves `GET /metrics`, plus the JSON endpoints of `api::respond` when
+/// `api` is set, on `listener` until the process exits. Each connection is
+/// answered on its own thread, so a slow client never delays a scrape.
+pub fn serve_http(
+    listener: TcpListener,
+    state: Arc<Mutex<ExporterState>>,
+    latency: RpcLatency,
+    api: bool,
+) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let state = Arc::clone(&state);
+        let latency = latency.clone();
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &state, &latency, api) {
+                eprintln!("warning: failed to answer http request: {err}");
+            }
+        });
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    state: &Mutex<ExporterState>,
+    latency: &RpcLatency,
+    api: bool,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; requests carry no body.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            (
+                "200 OK",
+                "text/plain; version=0.0.4",
+                render_metrics(&state, latency),
+            )
+        }
+        (Some("GET"), Some(path)) if api => {
+            let state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            let response = api::respond(&state, path);
+            (response.status, "application/json", response.body)
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    use alloy_primitives::address;
+
+    use crate::model::{
+        ChainCtm, ChainInspection, ChainSummary, CtmSummary, ProtocolSemver, TopologySnapshot,
+    };
+
+    struct Unreachable;
+
+    impl RpcClient for Unreachable {
+        fn eth_call(&self, _to: &str, _data: &str) -> Result<String, RpcError> {
+            Err(RpcError::Transport("connection refused".to_string()))
+        }
+    }
+
+    fn state() -> ExporterState {
+        let bridgehub = address!("0x0000000000000000000000000000000000000001");
+        let ctm = address!("0x000000000000000000000000000000000000000c");
+        let inspection = ChainInspection {
+            bridgehub,
+            chain: ChainSummary {
+                chain_id: 324,
+                ctm: Some(ctm),
+                validator_timelock: Some(address!("0x00000000000000000000000000000000000000d2")),
+                chain_contract: Some(address!("0x00000000000000000000000000000000000000d1")),
+                admin: Some(address!("0x00000000000000000000000000000000000000a1")),
+                admin_pending_owner: Some(address!("0x0000000000000000000000000000000000000005")),
+                protocol_version: Some(ProtocolSemver::new(0, 28, 1)),
+                multisig_signing_threshold: Some(2),
+                ..Default::default()
+            },
+            gateway: None,
+            timeline: None,
+            l2: None,
+            warnings: vec![],
+            unresolved: Default::default(),
+        };
+        ExporterState {
+            snapshot: Some(SavedSnapshot {
+                format_version: 1,
+                network_chain_id: Some(1),
+                block_number: Some(100),
+                saved_at_unix: 1_700_000_000,
+                topology: TopologySnapshot {
+                    bridgehub,
+                    chain_ids: vec![324],
+                    chain_ctms: vec![ChainCtm { chain_id: 324, ctm }],
+                    ctms: vec![CtmSummary {
+                        address: ctm,
+                        protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+                    }],
+                    chain_data_availability: vec![],
+                    history: None,
+                    warnings: vec![],
+                },
+                inspections: vec![inspection],
+            }),
+            batches: BTreeMap::from([(324, (98, 95, 90))]),
+            rounds_total: 3,
+            round_failures_total: 1,
+            warnings_total: BTreeMap::from([(DiagnosticCode::PendingOwner, 3)]),
+            last_success_unix: Some(1_700_000_000),
+        }
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let latency = RpcLatency::default();
+        latency.observe("eth_call", Duration::from_millis(30));
+        latency.observe("eth_call", Duration::from_secs(20));
+
+        let histogram = latency.snapshot()["eth_call"].clone();
+        assert_eq!(histogram.count, 2);
+        assert_eq!(histogram.buckets[..3], [0, 0, 1]);
+        assert_eq!(histogram.buckets[LATENCY_BUCKETS.len() - 1], 1);
+    }
+
+    #[test]
+    fn renders_prometheus_metrics() {
+        let latency = RpcLatency::default();
+        latency.observe("eth_call", Duration::from_millis(30));
+        let output = render_metrics(&state(), &latency);
+
+        for line in [
+            "# TYPE mercator_scan_rounds_total counter",
+            "mercator_scan_rounds_total 3",
+            "mercator_scan_round_failures_total 1",
+            "mercator_scan_warnings_total{code=\"W004\"} 3",
+            "mercator_scan_last_success_timestamp_seconds 1700000000",
+            "mercator_ctm_protocol_version{ctm=\"0x000000000000000000000000000000000000000C\",version=\"0.29.0\"} 29",
+            "mercator_chain_protocol_version{chain_id=\"324\",version=\"0.28.1\"} 28",
+            "mercator_chain_batches_committed{chain_id=\"324\"} 98",
+            "mercator_chain_batches_proven{chain_id=\"324\"} 95",
+            "mercator_chain_batches_executed{chain_id=\"324\"} 90",
+            "mercator_multisig_threshold{chain_id=\"324\"} 2",
+            "mercator_ownership_pending{chain_id=\"324\",role=\"admin\",contract=\"0x00000000000000000000000000000000000000A1\"} 1",
+            "mercator_ownership_pending{chain_id=\"324\",role=\"validator_timelock\",contract=\"0x00000000000000000000000000000000000000D2\"} 0",
+            "# TYPE mercator_rpc_request_duration_seconds histogram",
+            "mercator_rpc_request_duration_seconds_bucket{method=\"eth_call\",le=\"0.025\"} 0",
+            "mercator_rpc_request_duration_seconds_bucket{method=\"eth_call\",le=\"0.05\"} 1",
+            "mercator_rpc_request_duration_seconds_bucket{method=\"eth_call\",le=\"+Inf\"} 1",
+            "mercator_rpc_request_duration_seconds_count{method=\"eth_call\"} 1",
+        ] {
+            assert!(
+                output.lines().any(|candidate| candidate == line),
+                "missing `{line}` in:\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn counts_failed_rounds_and_times_rpc_calls() {
+        let latency = RpcLatency::default();
+        let client = TimedRpcClient::new(Unreachable, latency.clone());
+        let state = Mutex::new(ExporterState::default());
+        let mut warnings = Vec::new();
+
+        run_exporter(
+            &client,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions::default(),
+            &InspectOptions::default(),
+            &ServeOptions {
+                interval: Duration::ZERO,
+                max_rounds: Some(2),
+            },
+            &state,
+            &mut |warning| warnings.push(warning.clone()),
+        );
+
+        let state = state.into_inner().unwrap();
+        assert_eq!(state.rounds_total, 2);
+        assert_eq!(state.round_failures_total, 2);
+        assert_eq!(state.warnings_total[&DiagnosticCode::ScanFailed], 2);
+        assert!(state.snapshot.is_none());
+        // Repeated warnings are only reported once.
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(latency.snapshot()["eth_call"].count, 2);
+    }
+
+    #[test]
+    fn serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind should succeed");
+        let address = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(state()));
+        thread::spawn(move || serve_http(listener, state, RpcLatency::default(), true));
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(address).expect("connect should succeed");
+            write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        // A client that never sends its request must not hold up a scrape.
+        let _idle = TcpStream::connect(address).expect("connect should succeed");
+        let started = std::time::Instant::now();
+        let response = get("/metrics");
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4\r\n"));
+        assert!(response.contains("\r\n\r\n# HELP mercator_scan_rounds_total"));
+
+        let response = get("/topology");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(get("/other").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::{fs, path::Path};
+
+use thiserror::Error;
+
+use crate::model::SavedSnapshot;
+
+/// Bumped whenever `SavedSnapshot` changes incompatibly.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("failed to access snapshot {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("invalid snapshot {path}: {source}")]
+    Json {
+        path: String,
+        source: serde_json::Error,
+    },
+    #[error("snapshot {path} has format version {found}, expected {SNAPSHOT_FORMAT_VERSION}")]
+    UnsupportedVersion { path: String, found: u32 },
+}
+
+pub fn save_snapshot(path: &Path, snapshot: &SavedSnapshot) -> Result<(), SnapshotError> {
+    let json = serde_json::to_string_pretty(snapshot).map_err(|source| SnapshotError::Json {
+        path: path.display().to_string(),
+        source,
+    })?;
+    fs::write(path, json + "\n").map_err(|source| SnapshotError::Io {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+pub fn load_snapshot(path: &Path) -> Result<SavedSnapshot, SnapshotError> {
+    let json = fs::read_to_string(path).map_err(|source| SnapshotError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let snapshot: SavedSnapshot =
+        serde_json::from_str(&json).map_err(|source| SnapshotError::Json {
+            path: path.display().to_string(),
+            source,
+        })?;
+    if snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion {
+            path: path.display().to_string(),
+            found: snapshot.format_version,
+        });
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    use crate::model::TopologySnapshot;
+
+    fn snapshot(format_version: u32) -> SavedSnapshot {
+        SavedSnapshot {
+            format_version,
+            network_chain_id: Some(1),
+            block_number: Some(21_000_000),
+            saved_at_unix: 1_700_000_000,
+            topology: TopologySnapshot {
+                bridgehub: address!("0x0000000000000000000000000000000000000001"),
+                chain_ids: vec![324],
+                chain_ctms: vec![],
+                ctms: vec![],
+                chain_data_availability: vec![],
+                history: None,
+                warnings: vec![],
+            },
+            inspections: vec![],
+        }
+    }
+
+    #[test]
+    fn round_trips_snapshot_through_disk() {
+        let path = std::env::temp_dir().join(format!(
+            "mercator-snapshot-roundtrip-{}.json",
+            std::process::id()
+        ));
+        let saved = snapshot(SNAPSHOT_FORMAT_VERSION);
+
+        save_snapshot(&path, &saved).expect("snapshot should save");
+        let loaded = load_snapshot(&path).expect("snapshot should load");
+        fs::remove_file(&path).ok();
+
+        assert_eq!(loaded, saved);
+    }
+
+    #[test]
+    fn rejects_unknown_format_version() {
+        let path = std::env::temp_dir().join(format!(
+            "mercator-snapshot-version-{}.json",
+            std::process::id()
+        ));
+        save_snapshot(&path, &snapshot(99)).expect("snapshot should save");
+        let err = load_snapshot(&path).expect_err("version 99 should be rejected");
+        fs::remove_file(&path).ok();
+
+        assert!(matches!(
+            err,
+            SnapshotError::UnsupportedVersion { found: 99, .. }
+        ));
+    }
+}
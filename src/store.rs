@@ -0,0 +1,418 @@
+use std::path::Path;
+
+use alloy_primitives::Address;
+use rusqlite::{Connection, params};
+use thiserror::Error;
+
+use crate::diagnostic::Diagnostic;
+use crate::model::{CHAIN_FIELDS, FieldHistory, FieldHistoryEntry, SavedSnapshot};
+
+/// Bumped whenever the table layout changes incompatibly; stored as the
+/// database's `user_version`.
+pub const STORE_SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY,
+    block_number INTEGER,
+    network_chain_id INTEGER,
+    bridgehub TEXT NOT NULL,
+    saved_at_unix INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS snapshots_by_block ON snapshots (block_number);
+CREATE TABLE IF NOT EXISTS ctms (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots (id),
+    address TEXT NOT NULL,
+    protocol_version TEXT,
+    PRIMARY KEY (snapshot_id, address)
+);
+CREATE TABLE IF NOT EXISTS chains (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots (id),
+    chain_id INTEGER NOT NULL,
+    ctm TEXT,
+    summary TEXT NOT NULL,
+    PRIMARY KEY (snapshot_id, chain_id)
+);
+CREATE TABLE IF NOT EXISTS chain_fields (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots (id),
+    chain_id INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    value TEXT,
+    PRIMARY KEY (snapshot_id, chain_id, field)
+);
+CREATE INDEX IF NOT EXISTS chain_fields_by_field ON chain_fields (chain_id, field);
+CREATE TABLE IF NOT EXISTS diagnostics (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots (id),
+    chain_id INTEGER,
+    code TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    contract TEXT,
+    method TEXT,
+    message TEXT NOT NULL,
+    error TEXT
+);
+";
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("failed to access snapshot store {path}: {source}")]
+    Sqlite {
+        path: String,
+        source: rusqlite::Error,
+    },
+    #[error("invalid chain summary in snapshot store {path}: {source}")]
+    Json {
+        path: String,
+        source: serde_json::Error,
+    },
+    #[error("snapshot store {path} has schema version {found}, expected {STORE_SCHEMA_VERSION}")]
+    UnsupportedVersion { path: String, found: i64 },
+}
+
+/// SQLite database of every snapshot written with `scan --db`. Each snapshot
+/// row is keyed by its block; CTMs, chain summaries (as JSON), the chain
+/// fields `audit` rules can reference and diagnostics hang off it.
+pub struct SnapshotStore {
+    path: String,
+    connection: Connection,
+}
+
+impl SnapshotStore {
+    /// Opens the store at `path`, creating the file and tables when missing.
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        let path = path.display().to_string();
+        let sqlite = |source| StoreError::Sqlite {
+            path: path.clone(),
+            source,
+        };
+        let connection = Connection::open(&path).map_err(sqlite)?;
+        let version: i64 = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(sqlite)?;
+        match version {
+            0 => connection
+                .execute_batch(&format!(
+                    "BEGIN;{SCHEMA}PRAGMA user_version = {STORE_SCHEMA_VERSION};COMMIT;"
+                ))
+                .map_err(sqlite)?,
+            STORE_SCHEMA_VERSION => {}
+            found => return Err(StoreError::UnsupportedVersion { path, found }),
+        }
+        Ok(Self { path, connection })
+    }
+
+    /// Writes `snapshot` in a single transaction and returns its row ID.
+    pub fn insert_snapshot(&mut self, snapshot: &SavedSnapshot) -> Result<i64, StoreError> {
+        let path = self.path.clone();
+        let sqlite = |source| StoreError::Sqlite {
+            path: path.clone(),
+            source,
+        };
+        let transaction = self.connection.transaction().map_err(sqlite)?;
+        transaction
+            .execute(
+                "INSERT INTO snapshots (block_number, network_chain_id, bridgehub, saved_at_unix)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    snapshot.block_number,
+                    snapshot.network_chain_id,
+                    snapshot.topology.bridgehub.to_string(),
+                    snapshot.saved_at_unix,
+                ],
+            )
+            .map_err(sqlite)?;
+        let snapshot_id = transaction.last_insert_rowid();
+
+        for ctm in &snapshot.topology.ctms {
+            transaction
+                .execute(
+                    "INSERT INTO ctms (snapshot_id, address, protocol_version) VALUES (?1, ?2, ?3)",
+                    params![
+                        snapshot_id,
+                        ctm.address.to_string(),
+                        ctm.protocol_version.map(|version| version.to_string()),
+                    ],
+                )
+                .map_err(sqlite)?;
+        }
+
+        let mut diagnostics = snapshot.topology.warnings.iter().collect::<Vec<_>>();
+        for inspection in &snapshot.inspections {
+            let chain = &inspection.chain;
+            let summary = serde_json::to_string(chain).map_err(|source| StoreError::Json {
+                path: path.clone(),
+                source,
+            })?;
+            transaction
+                .execute(
+                    "INSERT INTO chains (snapshot_id, chain_id, ctm, summary) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        snapshot_id,
+                        chain.chain_id,
+                        chain.ctm.map(|ctm| ctm.to_string()),
+                        summary,
+                    ],
+                )
+                .map_err(sqlite)?;
+            for field in CHAIN_FIELDS {
+                transaction
+                    .execute(
+                        "INSERT INTO chain_fields (snapshot_id, chain_id, field, value)
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![snapshot_id, chain.chain_id, field, chain.field(field)],
+                    )
+                    .map_err(sqlite)?;
+            }
+            diagnostics.extend(&inspection.warnings);
+        }
+
+        for diagnostic in diagnostics {
+            insert_diagnostic(&transaction, snapshot_id, diagnostic).map_err(sqlite)?;
+        }
+        transaction.commit().map_err(sqlite)?;
+        Ok(snapshot_id)
+    }
+
+    /// How `field` of `chain_id` evolved across the snapshots stored for
+    /// `bridgehub` (and `network_chain_id`, when given), ordered by block and
+    /// then by save time.
+    pub fn field_history(
+        &self,
+        bridgehub: Address,
+        network_chain_id: Option<u64>,
+        chain_id: u64,
+        field: &str,
+    ) -> Result<FieldHistory, StoreError> {
+        let sqlite = |source| StoreError::Sqlite {
+            path: self.path.clone(),
+            source,
+        };
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT snapshots.block_number, chain_fields.value
+                 FROM chain_fields JOIN snapshots ON snapshots.id = chain_fields.snapshot_id
+                 WHERE chain_fields.chain_id = ?1 AND chain_fields.field = ?2
+                   AND snapshots.bridgehub = ?3
+                   AND (?4 IS NULL OR snapshots.network_chain_id = ?4)
+                 ORDER BY snapshots.block_number, snapshots.saved_at_unix, snapshots.id",
+            )
+            .map_err(sqlite)?;
+        let samples = statement
+            .query_map(
+                params![chain_id, field, bridgehub.to_string(), network_chain_id],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<u64>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                },
+            )
+            .map_err(sqlite)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sqlite)?;
+
+        let mut entries: Vec<FieldHistoryEntry> = Vec::new();
+        for (block, value) in samples {
+            match entries.last_mut() {
+                Some(entry) if entry.value == value => {
+                    entry.last_block = block;
+                    entry.snapshots += 1;
+                }
+                _ => entries.push(FieldHistoryEntry {
+                    value,
+                    first_block: block,
+                    last_block: block,
+                    snapshots: 1,
+                }),
+            }
+        }
+        Ok(FieldHistory {
+            chain_id,
+            field: field.to_string(),
+            entries,
+        })
+    }
+}
+
+fn insert_diagnostic(
+    connection: &Connection,
+    snapshot_id: i64,
+    diagnostic: &Diagnostic,
+) -> rusqlite::Result<usize> {
+    connection.execute(
+        "INSERT INTO diagnostics
+         (snapshot_id, chain_id, code, severity, contract, method, message, error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            snapshot_id,
+            diagnostic.chain_id,
+            diagnostic.code.to_string(),
+            diagnostic.severity.to_string(),
+            diagnostic.contract.map(|contract| contract.to_string()),
+            diagnostic.method,
+            diagnostic.message,
+            diagnostic.error,
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use alloy_primitives::address;
+
+    use crate::diagnostic::DiagnosticCode;
+    use crate::model::{
+        ChainInspection, ChainSummary, CtmSummary, ProtocolSemver, TopologySnapshot,
+    };
+
+    fn temp_db(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mercator-{name}-{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    const BRIDGEHUB: Address = address!("0x0000000000000000000000000000000000000001");
+
+    fn snapshot(block_number: u64, version: Option<ProtocolSemver>) -> SavedSnapshot {
+        let bridgehub = BRIDGEHUB;
+        let ctm = address!("0x000000000000000000000000000000000000000c");
+        SavedSnapshot {
+            format_version: 1,
+            network_chain_id: Some(1),
+            block_number: Some(block_number),
+            saved_at_unix: 1_700_000_000 + block_number,
+            topology: TopologySnapshot {
+                bridgehub,
+                chain_ids: vec![324],
+                chain_ctms: vec![],
+                ctms: vec![CtmSummary {
+                    address: ctm,
+                    protocol_version: Some(ProtocolSemver::new(0, 29, 0)),
+                }],
+                chain_data_availability: vec![],
+                history: None,
+                warnings: vec![],
+            },
+            inspections: vec![ChainInspection {
+                bridgehub,
+                chain: ChainSummary {
+                    chain_id: 324,
+                    ctm: Some(ctm),
+                    protocol_version: version,
+                    ..Default::default()
+                },
+                gateway: None,
+                timeline: None,
+                l2: None,
+                warnings: vec![
+                    Diagnostic::new(DiagnosticCode::PendingOwner, "pending owner").chain(324),
+                ],
+                unresolved: Default::default(),
+            }],
+        }
+    }
+
+    #[test]
+    fn collapses_field_history_into_runs() {
+        let path = temp_db("history");
+        let mut store = SnapshotStore::open(&path).expect("store should open");
+        // Inserted out of block order on purpose.
+        for (block, version) in [
+            (300, Some(ProtocolSemver::new(0, 29, 0))),
+            (100, Some(ProtocolSemver::new(0, 28, 0))),
+            (200, Some(ProtocolSemver::new(0, 28, 0))),
+            (400, None),
+        ] {
+            store
+                .insert_snapshot(&snapshot(block, version))
+                .expect("snapshot should insert");
+        }
+        // Same chain ID behind another Bridgehub and on another L1.
+        let mut other_bridgehub = snapshot(250, Some(ProtocolSemver::new(0, 30, 0)));
+        other_bridgehub.topology.bridgehub = address!("0x0000000000000000000000000000000000000002");
+        store.insert_snapshot(&other_bridgehub).unwrap();
+        let mut other_network = snapshot(260, Some(ProtocolSemver::new(0, 30, 0)));
+        other_network.network_chain_id = Some(11_155_111);
+        store.insert_snapshot(&other_network).unwrap();
+
+        let history = store
+            .field_history(BRIDGEHUB, Some(1), 324, "protocol_version")
+            .expect("history should load");
+        let entry = |value: Option<&str>, first_block, last_block, snapshots| FieldHistoryEntry {
+            value: value.map(str::to_string),
+            first_block: Some(first_block),
+            last_block: Some(last_block),
+            snapshots,
+        };
+        assert_eq!(
+            history.entries,
+            vec![
+                entry(Some("0.28.0"), 100, 200, 2),
+                entry(Some("0.29.0"), 300, 300, 1),
+                entry(None, 400, 400, 1),
+            ]
+        );
+        let unscoped = store
+            .field_history(BRIDGEHUB, None, 324, "protocol_version")
+            .unwrap();
+        assert_eq!(unscoped.entries[1].value.as_deref(), Some("0.30.0"));
+        assert!(
+            store
+                .field_history(BRIDGEHUB, Some(1), 325, "protocol_version")
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reopens_and_normalizes_snapshots() {
+        let path = temp_db("reopen");
+        SnapshotStore::open(&path)
+            .unwrap()
+            .insert_snapshot(&snapshot(100, None))
+            .unwrap();
+
+        let store = SnapshotStore::open(&path).expect("store should reopen");
+        let count = |table: &str| -> i64 {
+            store
+                .connection
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+        assert_eq!(count("snapshots"), 1);
+        assert_eq!(count("ctms"), 1);
+        assert_eq!(count("chains"), 1);
+        assert_eq!(count("chain_fields"), CHAIN_FIELDS.len() as i64);
+        let code: String = store
+            .connection
+            .query_row(
+                "SELECT code FROM diagnostics WHERE chain_id = 324",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(code, "W004");
+
+        store
+            .connection
+            .execute_batch("PRAGMA user_version = 7")
+            .unwrap();
+        drop(store);
+        assert!(matches!(
+            SnapshotStore::open(&path),
+            Err(StoreError::UnsupportedVersion { found: 7, .. })
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,562 @@
+use std::{thread, time::Duration};
+
+use alloy_primitives::Address;
+use thiserror::Error;
+
+use crate::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::diff::{ctm_unresolved, diff_snapshots};
+use crate::model::{
+    ChainCtm, ChainSummary, SavedSnapshot, SnapshotChange, SnapshotDiff, WatchEvent, WatchEventKind,
+};
+use crate::rpc::RpcClient;
+use crate::scanner::{InspectOptions, ScanError, ScanOptions, capture_snapshot};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchInterval {
+    /// Re-scan once the head advanced by this many blocks.
+    Blocks(u64),
+    /// Re-scan after sleeping this many seconds.
+    Seconds(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchOptions {
+    pub interval: WatchInterval,
+    /// Delay between `eth_blockNumber` polls for `WatchInterval::Blocks`.
+    pub poll_interval: Duration,
+    /// Stop after this many scan rounds, including the baseline.
+    pub max_rounds: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+pub enum WatchError {
+    #[error("invalid webhook url: {0}")]
+    InvalidWebhookUrl(String),
+    #[error("webhook request failed: {0}")]
+    Webhook(String),
+}
+
+/// Re-runs the `scan --save` pipeline on `options.interval` and reports every
+/// change against the previous round through `on_event`.
+///
+/// Only a failed baseline scan is fatal; later round failures are reported
+/// through `on_warning` and retried on the next interval.
+pub fn watch(
+    client: &dyn RpcClient,
+    bridgehub: Address,
+    scan_options: &ScanOptions,
+    inspect_options: &InspectOptions,
+    options: &WatchOptions,
+    on_event: &mut dyn FnMut(&WatchEvent),
+    on_warning: &mut dyn FnMut(&Diagnostic),
+) -> Result<(), ScanError> {
+    let mut previous = capture_snapshot(client, bridgehub, scan_options, inspect_options)?;
+    for warning in &previous.topology.warnings {
+        on_warning(warning);
+    }
+
+    let mut rounds = 1;
+    while options
+        .max_rounds
+        .is_none_or(|max_rounds| rounds < max_rounds)
+    {
+        wait_for_next_round(client, options, previous.block_number, on_warning);
+        rounds += 1;
+
+        let mut current = match capture_snapshot(client, bridgehub, scan_options, inspect_options) {
+            Ok(current) => current,
+            Err(err) => {
+                on_warning(
+                    &Diagnostic::new(DiagnosticCode::ScanFailed, "watch round failed").error(err),
+                );
+                continue;
+            }
+        };
+        for warning in &current.topology.warnings {
+            if !previous.topology.warnings.contains(warning) {
+                on_warning(warning);
+            }
+        }
+        carry_forward_unresolved(&previous, &mut current);
+        for event in watch_events(&diff_snapshots(&previous, &current)) {
+            on_event(&event);
+        }
+        previous = current;
+    }
+    Ok(())
+}
+
+/// Keeps the previous round's value of every field `current` failed to
+/// resolve, including topology CTM assignments and CTM versions, so a
+/// transient RPC error neither reports a change now nor a change back once
+/// the read succeeds again.
+fn carry_forward_unresolved(previous: &SavedSnapshot, current: &mut SavedSnapshot) {
+    let carried: Vec<ChainCtm> = previous
+        .topology
+        .chain_ctms
+        .iter()
+        .filter(|before| {
+            current.topology.chain_ids.contains(&before.chain_id)
+                && !current
+                    .topology
+                    .chain_ctms
+                    .iter()
+                    .any(|chain_ctm| chain_ctm.chain_id == before.chain_id)
+                && ctm_unresolved(current, before.chain_id)
+        })
+        .cloned()
+        .collect();
+    let topology = &mut current.topology;
+    if !carried.is_empty() {
+        for chain_ctm in carried {
+            if !topology.ctms.iter().any(|ctm| ctm.address == chain_ctm.ctm)
+                && let Some(before) = previous
+                    .topology
+                    .ctms
+                    .iter()
+                    .find(|ctm| ctm.address == chain_ctm.ctm)
+            {
+                topology.ctms.push(before.clone());
+            }
+            topology.chain_ctms.push(chain_ctm);
+        }
+        topology.chain_ctms.sort_by_key(|chain_ctm| {
+            topology
+                .chain_ids
+                .iter()
+                .position(|chain_id| *chain_id == chain_ctm.chain_id)
+        });
+        topology.ctms.sort_by_key(|ctm| ctm.address);
+    }
+    for ctm in &mut topology.ctms {
+        if ctm.protocol_version.is_none()
+            && let Some(before) = previous
+                .topology
+                .ctms
+                .iter()
+                .find(|before| before.address == ctm.address)
+        {
+            ctm.protocol_version = before.protocol_version;
+        }
+    }
+
+    for inspection in &mut current.inspections {
+        let Some(before) = previous
+            .inspections
+            .iter()
+            .find(|before| before.chain.chain_id == inspection.chain.chain_id)
+        else {
+            continue;
+        };
+        inspection.unresolved.retain(|field| {
+            if before.unresolved.contains(field) {
+                return true;
+            }
+            copy_field(&before.chain, &mut inspection.chain, field);
+            false
+        });
+    }
+}
+
+/// Copies the summary field(s) behind a diff field name.
+fn copy_field(from: &ChainSummary, to: &mut ChainSummary, field: &str) {
+    match field {
+        "ctm" => to.ctm = from.ctm,
+        "chain_contract" => to.chain_contract = from.chain_contract,
+        "validator_timelock" => to.validator_timelock = from.validator_timelock,
+        "validator_timelock_owner" => to.validator_timelock_owner = from.validator_timelock_owner,
+        "validator_timelock_pending_owner" => {
+            to.validator_timelock_pending_owner = from.validator_timelock_pending_owner
+        }
+        "validator_timelock_execution_delay" => {
+            to.validator_timelock_execution_delay = from.validator_timelock_execution_delay
+        }
+        "timelock_validators" => to.timelock_validators = from.timelock_validators.clone(),
+        "admin" => to.admin = from.admin,
+        "admin_owner" => to.admin_owner = from.admin_owner,
+        "admin_pending_owner" => to.admin_pending_owner = from.admin_pending_owner,
+        "protocol_version" => to.protocol_version = from.protocol_version,
+        "base_token" => to.base_token = from.base_token.clone(),
+        "l1_da_validator" | "l2_da_validator" => {
+            to.data_availability = from.data_availability.clone()
+        }
+        "settlement_chain_id" => to.settlement = from.settlement.clone(),
+        "priority_tx_max_gas_limit" | "transaction_filterer" => {
+            to.fee_params = from.fee_params.clone();
+            to.priority_tx_max_gas_limit = from.priority_tx_max_gas_limit;
+            to.transaction_filterer = from.transaction_filterer;
+        }
+        "multisig_signing_threshold" | "multisig_validators" => {
+            to.multisig_signing_set_mode = from.multisig_signing_set_mode.clone();
+            to.multisig_signing_threshold = from.multisig_signing_threshold;
+            to.multisig_validators = from.multisig_validators.clone();
+        }
+        _ => {}
+    }
+}
+
+fn wait_for_next_round(
+    client: &dyn RpcClient,
+    options: &WatchOptions,
+    last_block: Option<u64>,
+    on_warning: &mut dyn FnMut(&Diagnostic),
+) {
+    let blocks = match options.interval {
+        WatchInterval::Seconds(seconds) => {
+            thread::sleep(Duration::from_secs(seconds));
+            return;
+        }
+        WatchInterval::Blocks(blocks) => blocks,
+    };
+    let Some(last_block) = last_block else {
+        // Without a reference block, fall back to a single poll interval.
+        thread::sleep(options.poll_interval);
+        return;
+    };
+
+    let target = last_block.saturating_add(blocks);
+    loop {
+        thread::sleep(options.poll_interval);
+        match client.eth_block_number() {
+            Ok(head) if head >= target => return,
+            Ok(_) => {}
+            Err(err) => on_warning(&Diagnostic::resolution_failed(
+                "eth_blockNumber",
+                "failed to poll block number",
+                err,
+            )),
+        }
+    }
+}
+
+/// Classifies snapshot changes into watch events.
+pub fn watch_events(diff: &SnapshotDiff) -> Vec<WatchEvent> {
+    diff.changes
+        .iter()
+        .map(|change| WatchEvent {
+            kind: classify(change),
+            block_number: diff.new_block_number,
+            chain_id: change.chain_id,
+            field: change.field.clone(),
+            old: change.old.clone(),
+            new: change.new.clone(),
+            security_relevant: change.security_relevant,
+        })
+        .collect()
+}
+
+fn classify(change: &SnapshotChange) -> WatchEventKind {
+    match change.field.as_str() {
+        "chain" if change.new.is_some() => WatchEventKind::NewChain,
+        "chain" => WatchEventKind::ChainRemoved,
+        "ctm" => WatchEventKind::CtmChanged,
+        "admin" => WatchEventKind::AdminChanged,
+        "admin_owner" | "validator_timelock_owner" => WatchEventKind::OwnerChanged,
+        "admin_pending_owner" | "validator_timelock_pending_owner" if change.new.is_some() => {
+            WatchEventKind::PendingOwnerAppeared
+        }
+        "admin_pending_owner" | "validator_timelock_pending_owner" => {
+            WatchEventKind::PendingOwnerCleared
+        }
+        "validator_timelock"
+        | "timelock_validators"
+        | "multisig_validators"
+        | "multisig_signing_threshold" => WatchEventKind::ValidatorSetChanged,
+        field if field == "protocol_version" || field.ends_with(" protocol_version") => {
+            WatchEventKind::ProtocolBumped
+        }
+        _ => WatchEventKind::ConfigChanged,
+    }
+}
+
+/// POSTs each watch event as a JSON body to a user-configured URL.
+pub struct Webhook {
+    url: reqwest::Url,
+    client: reqwest::blocking::Client,
+}
+
+impl Webhook {
+    pub fn new(url: &str, timeout_secs: u64) -> Result<Self, WatchError> {
+        let url = reqwest::Url::parse(url)
+            .map_err(|err| WatchError::InvalidWebhookUrl(err.to_string()))?;
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()
+            .map_err(|err| WatchError::Webhook(err.to_string()))?;
+        Ok(Self { url, client })
+    }
+
+    pub fn post(&self, event: &WatchEvent) -> Result<(), WatchError> {
+        let body = serde_json::to_vec(event).map_err(|err| WatchError::Webhook(err.to_string()))?;
+        self.client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| WatchError::Webhook(err.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    use alloy_primitives::address;
+
+    use crate::rpc::RpcError;
+    use crate::scanner::bridgehub;
+
+    /// Advances 100 blocks per `eth_blockNumber`; chain 325 appears at block 200.
+    struct GrowingBridgehub {
+        head: Cell<u64>,
+    }
+
+    impl RpcClient for GrowingBridgehub {
+        fn eth_call(&self, _to: &str, data: &str) -> Result<String, RpcError> {
+            if data != bridgehub::encode_get_all_zk_chain_chain_ids_calldata() {
+                return Err(RpcError::Unsupported("eth_call"));
+            }
+            let chain_ids: &[u64] = if self.head.get() < 200 {
+                &[324]
+            } else {
+                &[324, 325]
+            };
+            let mut response = format!("0x{:064x}{:064x}", 0x20, chain_ids.len());
+            for chain_id in chain_ids {
+                response.push_str(&format!("{chain_id:064x}"));
+            }
+            Ok(response)
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            Ok(1)
+        }
+
+        fn eth_block_number(&self) -> Result<u64, RpcError> {
+            self.head.set(self.head.get() + 100);
+            Ok(self.head.get())
+        }
+    }
+
+    const ADMIN: &str = "0x00000000000000000000000000000000000000a1";
+
+    const CTM: &str = "0x000000000000000000000000000000000000000c";
+
+    /// Chain 324 under CTM 0x..0c with admin 0x..a1; `owner()` on the admin
+    /// answers from `owners` in turn, `None` being a failed read. Every CTM
+    /// read fails in round `ctm_failure_round`.
+    struct FlakyOwner {
+        owners: Vec<Option<Address>>,
+        calls: Cell<usize>,
+        ctm_failure_round: usize,
+        rounds: Cell<usize>,
+    }
+
+    impl FlakyOwner {
+        fn ctm_read(&self, response: String) -> Result<String, RpcError> {
+            if self.rounds.get() == self.ctm_failure_round {
+                Err(RpcError::Transport("connection reset".to_string()))
+            } else {
+                Ok(response)
+            }
+        }
+    }
+
+    impl RpcClient for FlakyOwner {
+        fn eth_call(&self, to: &str, data: &str) -> Result<String, RpcError> {
+            let word = |address: &str| format!("0x{:0>64}", address.trim_start_matches("0x"));
+            if data == bridgehub::encode_get_all_zk_chain_chain_ids_calldata() {
+                self.rounds.set(self.rounds.get() + 1);
+                Ok(format!("0x{:064x}{:064x}{:064x}", 0x20, 1, 324))
+            } else if data == bridgehub::encode_chain_type_manager_calldata(324) {
+                self.ctm_read(word(CTM))
+            } else if to.eq_ignore_ascii_case(CTM)
+                && data == bridgehub::encode_protocol_version_calldata()
+            {
+                self.ctm_read(format!("0x{:064x}", 29u64 << 32))
+            } else if data == bridgehub::encode_get_chain_admin_calldata(324) {
+                Ok(word(ADMIN))
+            } else if to.eq_ignore_ascii_case(ADMIN) && data == bridgehub::encode_owner_calldata() {
+                let call = self.calls.get();
+                self.calls.set(call + 1);
+                match self.owners.get(call).copied().flatten() {
+                    Some(owner) => Ok(word(&owner.to_string())),
+                    None => Err(RpcError::Transport("connection reset".to_string())),
+                }
+            } else {
+                Err(RpcError::Unsupported("eth_call"))
+            }
+        }
+
+        fn eth_chain_id(&self) -> Result<u64, RpcError> {
+            Ok(1)
+        }
+    }
+
+    fn event(kind: WatchEventKind, field: &str) -> WatchEvent {
+        WatchEvent {
+            kind,
+            block_number: Some(200),
+            chain_id: Some(324),
+            field: field.to_string(),
+            old: Some("0x0000000000000000000000000000000000000002".to_string()),
+            new: Some("0x0000000000000000000000000000000000000003".to_string()),
+            security_relevant: true,
+        }
+    }
+
+    #[test]
+    fn classifies_snapshot_changes() {
+        let change = |field: &str, new: Option<&str>| SnapshotChange {
+            chain_id: Some(324),
+            field: field.to_string(),
+            old: None,
+            new: new.map(str::to_string),
+            security_relevant: true,
+        };
+        let diff = SnapshotDiff {
+            old_block_number: Some(100),
+            new_block_number: Some(200),
+            changes: vec![
+                change("chain", Some("registered")),
+                change("chain", None),
+                change("admin", Some("0x01")),
+                change("admin_pending_owner", Some("0x02")),
+                change("validator_timelock_pending_owner", None),
+                change("timelock_validators", Some("0x03 (committer)")),
+                change("ctm 0x0a protocol_version", Some("0.29.0")),
+                change("l1_da_validator", Some("0x04")),
+            ],
+        };
+
+        let kinds: Vec<_> = watch_events(&diff).iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                WatchEventKind::NewChain,
+                WatchEventKind::ChainRemoved,
+                WatchEventKind::AdminChanged,
+                WatchEventKind::PendingOwnerAppeared,
+                WatchEventKind::PendingOwnerCleared,
+                WatchEventKind::ValidatorSetChanged,
+                WatchEventKind::ProtocolBumped,
+                WatchEventKind::ConfigChanged,
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_reports_chains_registered_between_rounds() {
+        let client = GrowingBridgehub { head: Cell::new(0) };
+        let mut events = Vec::new();
+        watch(
+            &client,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions::default(),
+            &InspectOptions::default(),
+            &WatchOptions {
+                interval: WatchInterval::Blocks(50),
+                poll_interval: Duration::ZERO,
+                max_rounds: Some(2),
+            },
+            &mut |event| events.push(event.clone()),
+            &mut |_| {},
+        )
+        .expect("watch should succeed");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WatchEventKind::NewChain);
+        assert_eq!(events[0].chain_id, Some(325));
+        assert_eq!(events[0].block_number, Some(300));
+    }
+
+    #[test]
+    fn watch_carries_values_forward_over_a_failed_read() {
+        let before = address!("0x00000000000000000000000000000000000000b1");
+        let after = address!("0x00000000000000000000000000000000000000d1");
+        let client = FlakyOwner {
+            owners: vec![Some(before), None, Some(after)],
+            calls: Cell::new(0),
+            ctm_failure_round: 3,
+            rounds: Cell::new(0),
+        };
+        let mut events = Vec::new();
+        watch(
+            &client,
+            address!("0x0000000000000000000000000000000000000001"),
+            &ScanOptions::default(),
+            &InspectOptions::default(),
+            &WatchOptions {
+                interval: WatchInterval::Seconds(0),
+                poll_interval: Duration::ZERO,
+                max_rounds: Some(4),
+            },
+            &mut |event| events.push(event.clone()),
+            &mut |_| {},
+        )
+        .expect("watch should succeed");
+
+        // The failed owner read in the second round and the failed CTM reads
+        // in the third neither report a change nor hide the owner change in
+        // the fourth. Without a CTM there is no admin to ask for its owner.
+        assert_eq!(client.rounds.get(), 4);
+        assert_eq!(client.calls.get(), 3);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WatchEventKind::OwnerChanged);
+        assert_eq!(events[0].old, Some(before.to_string()));
+        assert_eq!(events[0].new, Some(after.to_string()));
+    }
+
+    #[test]
+    fn webhook_posts_event_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("webhook should connect");
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nconnection: close\r\n\r\n")
+                .unwrap();
+            (request_line, body)
+        });
+
+        let sent = event(WatchEventKind::AdminChanged, "admin");
+        Webhook::new(&url, 5)
+            .expect("webhook should build")
+            .post(&sent)
+            .expect("webhook should accept the event");
+
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /hook "));
+        let received: WatchEvent = serde_json::from_slice(&body).expect("body should be json");
+        assert_eq!(received, sent);
+        assert!(
+            String::from_utf8(body)
+                .unwrap()
+                .contains("\"kind\":\"admin_changed\"")
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use std::collections::HashMap;
 
+use alloy_primitives::{address, keccak256};
 use mercator::{
-    rpc::{RpcClient, RpcError},
-    scanner::scan_bridgehub_topology,
+    model::ProtocolSemver,
+    rpc::{LogFilter, RpcClient, RpcError, RpcLog},
+    scanner::{HistoryOptions, ScanOptions, scan_bridgehub_topology},
 };
 
 #[derive(Default)]
 struct ScriptedRpc {
     responses: HashMap<String, Result<String, RpcError>>,
+    block_number: u64,
+    logs: Vec<RpcLog>,
 }
 
 impl ScriptedRpc {
@@ -15,6 +19,26 @@ impl ScriptedRpc {
         self.responses.insert(data.to_string(), response);
         self
     }
+
+    fn with_new_chain_log(mut self, block_number: u64, chain_id: u64, ctm: &str) -> Self {
+        self.block_number = self.block_number.max(block_number);
+        self.logs.push(RpcLog {
+            address: "0x1111111111111111111111111111111111111111".to_string(),
+            topics: vec![
+                format!(
+                    "{:#x}",
+                    keccak256("NewChain(uint256,address,address)".as_bytes())
+                ),
+                format!("0x{chain_id:064x}"),
+                format!("0x{:0>64}", "99"),
+            ],
+            data: format!("0x{:0>64}", ctm.trim_start_matches("0x")),
+            block_number,
+            transaction_hash: format!("0x{block_number:064x}"),
+            log_index: 0,
+        });
+        self
+    }
 }
 
 impl RpcClient for ScriptedRpc {
@@ -25,11 +49,24 @@ impl RpcClient for ScriptedRpc {
             )))
         })
     }
+
+    fn eth_block_number(&self) -> Result<u64, RpcError> {
+        Ok(self.block_number)
+    }
+
+    fn eth_get_logs(&self, filter: &LogFilter) -> Result<Vec<RpcLog>, RpcError> {
+        Ok(self
+            .logs
+            .iter()
+            .filter(|log| (filter.from_block..=filter.to_block).contains(&log.block_number))
+            .cloned()
+            .collect())
+    }
 }
 
 #[test]
 fn scan_bridgehub_topology_with_scripted_rpc() {
-    let bridgehub = "0x1111111111111111111111111111111111111111";
+    let bridgehub = address!("0x1111111111111111111111111111111111111111");
     let chain_ids_data = "0x68b8d331";
     let protocol_version_data = "0x2ae9c600";
 
@@ -62,20 +99,82 @@ fn scan_bridgehub_topology_with_scripted_rpc() {
             Ok("0x000000000000000000000000000000000000000000000000000000000000002a".to_string()),
         );
 
-    let snapshot = scan_bridgehub_topology(&rpc, bridgehub).expect("scan should succeed");
+    let snapshot = scan_bridgehub_topology(&rpc, bridgehub, &ScanOptions::default())
+        .expect("scan should succeed");
 
     assert_eq!(snapshot.chain_ids, vec![324, 325, 326]);
     assert_eq!(snapshot.chain_ctms.len(), 3);
     assert_eq!(snapshot.ctms.len(), 2);
     assert!(snapshot.warnings.is_empty());
+    assert!(snapshot.chain_data_availability.is_empty());
     assert_eq!(
         snapshot.ctms[0].address,
-        "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"
+        address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+    );
+    assert_eq!(
+        snapshot.ctms[0].protocol_version,
+        Some(ProtocolSemver::new(0, 0, 42))
     );
-    assert_eq!(snapshot.ctms[0].protocol_version.as_deref(), Some("0.0.42"));
     assert_eq!(
         snapshot.ctms[1].address,
-        "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+        address!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb")
     );
-    assert_eq!(snapshot.ctms[1].protocol_version.as_deref(), Some("0.0.42"));
+    assert_eq!(
+        snapshot.ctms[1].protocol_version,
+        Some(ProtocolSemver::new(0, 0, 42))
+    );
+}
+
+#[test]
+fn scan_bridgehub_history_with_scripted_rpc() {
+    let bridgehub = address!("0x1111111111111111111111111111111111111111");
+    let ctm = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    let rpc = ScriptedRpc::default()
+        .with_response(
+            "0x68b8d331",
+            Ok(
+                "0x0000000000000000000000000000000000000000000000000000000000000020\
+                0000000000000000000000000000000000000000000000000000000000000001\
+                0000000000000000000000000000000000000000000000000000000000000144"
+                    .to_string(),
+            ),
+        )
+        .with_response(
+            "0x9d5bd3da0000000000000000000000000000000000000000000000000000000000000144",
+            Ok("0x000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string()),
+        )
+        .with_response(
+            "0x2ae9c600",
+            Ok("0x000000000000000000000000000000000000000000000000000000000000002a".to_string()),
+        )
+        .with_new_chain_log(1_200, 324, ctm)
+        .with_new_chain_log(25_000, 325, ctm);
+
+    let snapshot = scan_bridgehub_topology(
+        &rpc,
+        bridgehub,
+        &ScanOptions {
+            history: Some(HistoryOptions {
+                from_block: 1_000,
+                max_block_range: 10_000,
+            }),
+            ..ScanOptions::default()
+        },
+    )
+    .expect("scan should succeed");
+
+    let history = snapshot.history.expect("history should be indexed");
+    assert_eq!(history.from_block, 1_000);
+    assert_eq!(history.to_block, 25_000);
+    assert_eq!(history.chain_registrations.len(), 2);
+    assert_eq!(history.chain_registrations[0].chain_id, 324);
+    assert_eq!(
+        history.chain_registrations[0].ctm,
+        address!("0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+    );
+    assert!(!history.chain_registrations[0].removed);
+    assert_eq!(history.chain_registrations[1].chain_id, 325);
+    assert!(history.chain_registrations[1].removed);
+    assert!(snapshot.warnings.is_empty());
 }